@@ -1,4 +1,4 @@
-use crate::{Rgba, fast_round, linear_f32_from_linear_u8};
+use crate::{Rgba, fast_round, linear_f32_from_gamma_u8, linear_f32_from_linear_u8};
 
 /// This format is used for space-efficient color representation (32 bits).
 ///
@@ -376,6 +376,25 @@ impl Color32 {
     pub fn intensity(&self) -> f32 {
         (self.r() as f32 * 0.299 + self.g() as f32 * 0.587 + self.b() as f32 * 0.114) / 255.0
     }
+
+    /// Relative luminance of the color, as defined by [WCAG 2](https://www.w3.org/TR/WCAG21/#dfn-relative-luminance).
+    ///
+    /// Ignores alpha. Returns a value in the range 0-1.
+    pub fn relative_luminance(&self) -> f32 {
+        0.2126 * linear_f32_from_gamma_u8(self.r())
+            + 0.7152 * linear_f32_from_gamma_u8(self.g())
+            + 0.0722 * linear_f32_from_gamma_u8(self.b())
+    }
+
+    /// The [WCAG 2 contrast ratio](https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio) between two opaque colors.
+    ///
+    /// Returns a value in the range 1-21, where 1 means no contrast (same luminance)
+    /// and 21 is black on white.
+    pub fn contrast_ratio(self, other: Self) -> f32 {
+        let a = self.relative_luminance();
+        let b = other.relative_luminance();
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
 }
 
 impl std::ops::Mul for Color32 {
@@ -473,6 +492,21 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_contrast_ratio() {
+        let ratio = Color32::BLACK.contrast_ratio(Color32::WHITE);
+        assert!((ratio - 21.0).abs() < 1e-4, "{ratio}");
+        assert_eq!(
+            Color32::BLACK.contrast_ratio(Color32::WHITE),
+            Color32::WHITE.contrast_ratio(Color32::BLACK)
+        );
+        assert_eq!(Color32::RED.contrast_ratio(Color32::RED), 1.0);
+
+        // #777 on white is the classic "just below AA" example:
+        let ratio = Color32::from_gray(0x77).contrast_ratio(Color32::WHITE);
+        assert!((4.47..4.49).contains(&ratio), "{ratio}");
+    }
+
     #[test]
     fn color32_unmultiplied_round_trip() {
         for in_rgba in test_rgba() {
@@ -25,6 +25,9 @@ pub fn create_egui_context(storage: Option<&dyn crate::Storage>) -> egui::Contex
 
     egui_ctx.set_embed_viewports(!IS_DESKTOP);
 
+    // Both the glow and wgpu renderers support distance field textures:
+    egui_ctx.set_distance_field_supported(true);
+
     egui_ctx.options_mut(|o| {
         // eframe supports multi-pass (Context::request_discard).
         o.max_passes = 2.try_into().unwrap();
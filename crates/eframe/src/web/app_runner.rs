@@ -46,6 +46,9 @@ impl AppRunner {
     ) -> Result<Self, String> {
         let egui_ctx = egui::Context::default();
 
+        // Both the glow and wgpu renderers support distance field textures:
+        egui_ctx.set_distance_field_supported(true);
+
         #[allow(clippy::allow_attributes, unused_assignments)]
         #[cfg(feature = "glow")]
         let mut gl = None;
@@ -171,6 +174,7 @@ impl AppRunner {
             .or_default()
             .native_pixels_per_point = Some(super::native_pixels_per_point());
         runner.input.raw.system_theme = super::system_theme();
+        runner.input.raw.supports_rich_clipboard = true;
 
         Ok(runner)
     }
@@ -349,7 +353,7 @@ impl AppRunner {
     fn handle_platform_output(&self, platform_output: egui::PlatformOutput) {
         #[cfg(feature = "web_screen_reader")]
         if self.egui_ctx.options(|o| o.screen_reader) {
-            super::screen_reader::speak(&platform_output.events_description());
+            super::screen_reader::speak_all(&platform_output.speech_descriptions());
         }
 
         let egui::PlatformOutput {
@@ -371,6 +375,9 @@ impl AppRunner {
                 egui::OutputCommand::CopyImage(image) => {
                     super::set_clipboard_image(&image);
                 }
+                egui::OutputCommand::CopyRichText(text) => {
+                    super::set_clipboard_rich_text(&text);
+                }
                 egui::OutputCommand::OpenUrl(open_url) => {
                     super::open_url(&open_url.url, open_url.new_tab);
                 }
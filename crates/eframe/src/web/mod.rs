@@ -228,7 +228,7 @@ fn set_clipboard_image(image: &egui::ColorImage) {
 
         let mime = "image/png";
 
-        let item = match create_clipboard_item(mime, &png_bytes) {
+        let item = match create_clipboard_item(&[(mime, &png_bytes)]) {
             Ok(item) => item,
             Err(err) => {
                 log::error!("Failed to copy image: {}", string_from_js_value(&err));
@@ -250,6 +250,44 @@ fn set_clipboard_image(image: &egui::ColorImage) {
     }
 }
 
+/// Set the clipboard text, as both plain text and HTML if we have it.
+fn set_clipboard_rich_text(text: &egui::RichClipboardText) {
+    let Some(html) = &text.html else {
+        set_clipboard_text(&text.plain);
+        return;
+    };
+
+    if let Some(window) = web_sys::window() {
+        if !window.is_secure_context() {
+            log::error!(
+                "Clipboard is not available because we are not in a secure context. \
+                See https://developer.mozilla.org/en-US/docs/Web/Security/Secure_Contexts"
+            );
+            return;
+        }
+
+        let item = match create_clipboard_item(&[
+            ("text/plain", text.plain.as_bytes()),
+            ("text/html", html.as_bytes()),
+        ]) {
+            Ok(item) => item,
+            Err(err) => {
+                log::error!("Failed to copy text: {}", string_from_js_value(&err));
+                return;
+            }
+        };
+        let items = js_sys::Array::of1(&item);
+        let promise = window.navigator().clipboard().write(&items);
+        let future = wasm_bindgen_futures::JsFuture::from(promise);
+        let future = async move {
+            if let Err(err) = future.await {
+                log::error!("Copy/cut action failed: {}", string_from_js_value(&err));
+            }
+        };
+        wasm_bindgen_futures::spawn_local(future);
+    }
+}
+
 fn to_image(image: &egui::ColorImage) -> Result<image::RgbaImage, String> {
     profiling::function_scope!();
     image::RgbaImage::from_raw(
@@ -272,23 +310,26 @@ fn to_png_bytes(image: &image::RgbaImage) -> Result<Vec<u8>, String> {
     Ok(png_bytes)
 }
 
-fn create_clipboard_item(mime: &str, bytes: &[u8]) -> Result<web_sys::ClipboardItem, JsValue> {
-    let array = js_sys::Uint8Array::from(bytes);
-    let blob_parts = js_sys::Array::new();
-    blob_parts.push(&array);
+/// One clipboard item with the same content in several formats, given as `(mime, bytes)`.
+fn create_clipboard_item(formats: &[(&str, &[u8])]) -> Result<web_sys::ClipboardItem, JsValue> {
+    let items = js_sys::Object::new();
 
-    let options = web_sys::BlobPropertyBag::new();
-    options.set_type(mime);
+    for &(mime, bytes) in formats {
+        let array = js_sys::Uint8Array::from(bytes);
+        let blob_parts = js_sys::Array::new();
+        blob_parts.push(&array);
 
-    let blob = web_sys::Blob::new_with_u8_array_sequence_and_options(&blob_parts, &options)?;
+        let options = web_sys::BlobPropertyBag::new();
+        options.set_type(mime);
 
-    let items = js_sys::Object::new();
+        let blob = web_sys::Blob::new_with_u8_array_sequence_and_options(&blob_parts, &options)?;
 
-    // SAFETY: I hope so
-    #[expect(unsafe_code, unused_unsafe)] // Weird false positive
-    unsafe {
-        js_sys::Reflect::set(&items, &JsValue::from_str(mime), &blob)?
-    };
+        // SAFETY: I hope so
+        #[expect(unsafe_code, unused_unsafe)] // Weird false positive
+        unsafe {
+            js_sys::Reflect::set(&items, &JsValue::from_str(mime), &blob)?
+        };
+    }
 
     let clipboard_item = web_sys::ClipboardItem::new_with_record_from_str_to_blob_promise(&items)?;
 
@@ -1,20 +1,26 @@
-/// Speak the given text out loud.
-pub fn speak(text: &str) {
-    if text.is_empty() {
+/// Speak the given texts out loud, one after the other.
+pub fn speak_all(texts: &[impl AsRef<str>]) {
+    if texts.iter().all(|text| text.as_ref().is_empty()) {
         return;
     }
 
-    if let Some(window) = web_sys::window() {
-        log::debug!("Speaking {text:?}");
+    if let Some(window) = web_sys::window()
+        && let Ok(speech_synthesis) = window.speech_synthesis()
+    {
+        speech_synthesis.cancel(); // interrupt previous speech, if any
 
-        if let Ok(speech_synthesis) = window.speech_synthesis() {
-            speech_synthesis.cancel(); // interrupt previous speech, if any
+        for text in texts {
+            let text = text.as_ref();
+            if text.is_empty() {
+                continue;
+            }
+            log::debug!("Speaking {text:?}");
 
             if let Ok(utterance) = web_sys::SpeechSynthesisUtterance::new_with_text(text) {
                 utterance.set_rate(1.0);
                 utterance.set_pitch(1.0);
                 utterance.set_volume(1.0);
-                speech_synthesis.speak(&utterance);
+                speech_synthesis.speak(&utterance); // queued after the previous utterance
             }
         }
     }
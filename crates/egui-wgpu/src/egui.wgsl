@@ -130,10 +130,18 @@ fn sample_texture(in: VertexOutput) -> vec4<f32> {
     }
 }
 
-@fragment
-fn fs_main_linear_framebuffer(in: VertexOutput) -> @location(0) vec4<f32> {
+// For textures with `TextureOptions::distance_field`:
+// turn the signed distance in the alpha channel into coverage,
+// with an anti-aliased edge that is one pixel wide at any scale.
+fn sample_distance_field(in: VertexOutput) -> vec4<f32> {
+    let distance = sample_texture(in).a;
+    let pixel_width = max(fwidth(distance), 0.0001);
+    let coverage = clamp((distance - 0.5) / pixel_width + 0.5, 0.0, 1.0);
+    return vec4<f32>(coverage);
+}
+
+fn linear_framebuffer_color(in: VertexOutput, tex_gamma: vec4<f32>) -> vec4<f32> {
     // We expect "normal" textures that are NOT sRGB-aware.
-    let tex_gamma = sample_texture(in);
     var out_color_gamma = in.color * tex_gamma;
     // Dither the float color down to eight bits to reduce banding.
     // This step is optional for egui backends.
@@ -147,10 +155,8 @@ fn fs_main_linear_framebuffer(in: VertexOutput) -> @location(0) vec4<f32> {
     return vec4<f32>(out_color_linear, out_color_gamma.a);
 }
 
-@fragment
-fn fs_main_gamma_framebuffer(in: VertexOutput) -> @location(0) vec4<f32> {
+fn gamma_framebuffer_color(in: VertexOutput, tex_gamma: vec4<f32>) -> vec4<f32> {
     // We expect "normal" textures that are NOT sRGB-aware.
-    let tex_gamma = sample_texture(in);
     var out_color_gamma = in.color * tex_gamma;
     // Dither the float color down to eight bits to reduce banding.
     // This step is optional for egui backends.
@@ -160,3 +166,23 @@ fn fs_main_gamma_framebuffer(in: VertexOutput) -> @location(0) vec4<f32> {
     }
     return out_color_gamma;
 }
+
+@fragment
+fn fs_main_linear_framebuffer(in: VertexOutput) -> @location(0) vec4<f32> {
+    return linear_framebuffer_color(in, sample_texture(in));
+}
+
+@fragment
+fn fs_main_gamma_framebuffer(in: VertexOutput) -> @location(0) vec4<f32> {
+    return gamma_framebuffer_color(in, sample_texture(in));
+}
+
+@fragment
+fn fs_main_linear_framebuffer_distance_field(in: VertexOutput) -> @location(0) vec4<f32> {
+    return linear_framebuffer_color(in, sample_distance_field(in));
+}
+
+@fragment
+fn fs_main_gamma_framebuffer_distance_field(in: VertexOutput) -> @location(0) vec4<f32> {
+    return gamma_framebuffer_color(in, sample_distance_field(in));
+}
@@ -238,6 +238,9 @@ impl Default for RendererOptions {
 pub struct Renderer {
     pipeline: wgpu::RenderPipeline,
 
+    /// Used for textures with [`epaint::textures::TextureOptions::distance_field`].
+    distance_field_pipeline: wgpu::RenderPipeline,
+
     index_buffer: SlicedBuffer,
     vertex_buffer: SlicedBuffer,
 
@@ -366,10 +369,19 @@ impl Renderer {
                 bias: wgpu::DepthBiasState::default(),
             });
 
-        let pipeline = {
+        let fs_main_framebuffer = if output_color_format.is_srgb() {
+            log::warn!(
+                "Detected a linear (sRGBA aware) framebuffer {output_color_format:?}. egui prefers Rgba8Unorm or Bgra8Unorm"
+            );
+            "fs_main_linear_framebuffer"
+        } else {
+            "fs_main_gamma_framebuffer" // this is what we prefer
+        };
+
+        let create_pipeline = |label: &str, fragment_entry_point: &str| {
             profiling::scope!("create_render_pipeline");
             device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-                label: Some("egui_pipeline"),
+                label: Some(label),
                 layout: Some(&pipeline_layout),
                 vertex: wgpu::VertexState {
                     entry_point: Some("vs_main"),
@@ -393,7 +405,7 @@ impl Renderer {
                     polygon_mode: wgpu::PolygonMode::default(),
                     strip_index_format: None,
                 },
-                depth_stencil,
+                depth_stencil: depth_stencil.clone(),
                 multisample: wgpu::MultisampleState {
                     alpha_to_coverage_enabled: false,
                     count: options.msaa_samples.max(1),
@@ -402,12 +414,7 @@ impl Renderer {
 
                 fragment: Some(wgpu::FragmentState {
                     module: &module,
-                    entry_point: Some(if output_color_format.is_srgb() {
-                        log::warn!("Detected a linear (sRGBA aware) framebuffer {output_color_format:?}. egui prefers Rgba8Unorm or Bgra8Unorm");
-                        "fs_main_linear_framebuffer"
-                    } else {
-                        "fs_main_gamma_framebuffer" // this is what we prefer
-                    }),
+                    entry_point: Some(fragment_entry_point),
                     targets: &[Some(wgpu::ColorTargetState {
                         format: output_color_format,
                         blend: Some(wgpu::BlendState {
@@ -431,6 +438,11 @@ impl Renderer {
             }
         )
         };
+        let pipeline = create_pipeline("egui_pipeline", fs_main_framebuffer);
+        let distance_field_pipeline = create_pipeline(
+            "egui_distance_field_pipeline",
+            &format!("{fs_main_framebuffer}_distance_field"),
+        );
 
         const VERTEX_BUFFER_START_CAPACITY: wgpu::BufferAddress =
             (std::mem::size_of::<Vertex>() * 1024) as _;
@@ -439,6 +451,7 @@ impl Renderer {
 
         Self {
             pipeline,
+            distance_field_pipeline,
             vertex_buffer: SlicedBuffer {
                 buffer: create_vertex_buffer(device, VERTEX_BUFFER_START_CAPACITY),
                 slices: Vec::with_capacity(64),
@@ -484,6 +497,9 @@ impl Renderer {
         // run.
         let mut needs_reset = true;
 
+        // Is `distance_field_pipeline` set, instead of `pipeline`?
+        let mut distance_field_pipeline_is_set = false;
+
         let mut index_buffer_slices = self.index_buffer.slices.iter();
         let mut vertex_buffer_slices = self.vertex_buffer.slices.iter();
 
@@ -503,6 +519,7 @@ impl Renderer {
                 );
                 render_pass.set_pipeline(&self.pipeline);
                 render_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
+                distance_field_pipeline_is_set = false;
                 needs_reset = false;
             }
 
@@ -527,7 +544,21 @@ impl Renderer {
                     let index_buffer_slice = index_buffer_slices.next().unwrap();
                     let vertex_buffer_slice = vertex_buffer_slices.next().unwrap();
 
-                    if let Some(Texture { bind_group, .. }) = self.textures.get(&mesh.texture_id) {
+                    if let Some(Texture {
+                        bind_group,
+                        options,
+                        ..
+                    }) = self.textures.get(&mesh.texture_id)
+                    {
+                        let distance_field = options.is_some_and(|options| options.distance_field);
+                        if distance_field != distance_field_pipeline_is_set {
+                            render_pass.set_pipeline(if distance_field {
+                                &self.distance_field_pipeline
+                            } else {
+                                &self.pipeline
+                            });
+                            distance_field_pipeline_is_set = distance_field;
+                        }
                         render_pass.set_bind_group(1, bind_group, &[]);
                         render_pass.set_index_buffer(
                             self.index_buffer.buffer.slice(
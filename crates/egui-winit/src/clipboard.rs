@@ -81,6 +81,7 @@ impl Clipboard {
         if let Some(clipboard) = &mut self.arboard {
             return match clipboard.get_text() {
                 Ok(text) => Some(text),
+                Err(arboard::Error::ContentNotAvailable) => None, // e.g. an image
                 Err(err) => {
                     log::error!("arboard paste error: {err}");
                     None
@@ -91,6 +92,76 @@ impl Clipboard {
         Some(self.clipboard.clone())
     }
 
+    /// Get the events a "paste" command should deliver: the copied text, image and files.
+    ///
+    /// There is one event for each format the clipboard holds, and the text always comes first,
+    /// so that widgets that only know about text keep working.
+    pub fn get_paste(&mut self) -> Vec<egui::Event> {
+        let mut events = vec![];
+        if let Some(text) = self.get() {
+            let text = text.replace("\r\n", "\n");
+            if !text.is_empty() {
+                events.push(egui::Event::Paste(text));
+            }
+        }
+        if let Some(image) = self.get_image() {
+            events.push(egui::Event::PasteImage(image));
+        }
+        if let Some(files) = self.get_files()
+            && !files.is_empty()
+        {
+            events.push(egui::Event::PasteFiles(files));
+        }
+        events
+    }
+
+    /// Get the image in the clipboard, if any (e.g. a screenshot).
+    ///
+    /// Requires the "clipboard" feature.
+    pub fn get_image(&mut self) -> Option<egui::ColorImage> {
+        #[cfg(all(
+            not(any(target_os = "android", target_os = "ios")),
+            feature = "arboard",
+        ))]
+        if let Some(clipboard) = &mut self.arboard {
+            return match clipboard.get_image() {
+                Ok(image) => Some(egui::ColorImage::from_rgba_unmultiplied(
+                    [image.width, image.height],
+                    &image.bytes,
+                )),
+                Err(arboard::Error::ContentNotAvailable) => None,
+                Err(err) => {
+                    log::error!("arboard paste error: {err}");
+                    None
+                }
+            };
+        }
+
+        None
+    }
+
+    /// Get the list of files in the clipboard, if any (e.g. copied from a file manager).
+    ///
+    /// Requires the "clipboard" feature.
+    pub fn get_files(&mut self) -> Option<Vec<std::path::PathBuf>> {
+        #[cfg(all(
+            not(any(target_os = "android", target_os = "ios")),
+            feature = "arboard",
+        ))]
+        if let Some(clipboard) = &mut self.arboard {
+            return match clipboard.get().file_list() {
+                Ok(files) => Some(files),
+                Err(arboard::Error::ContentNotAvailable) => None,
+                Err(err) => {
+                    log::error!("arboard paste error: {err}");
+                    None
+                }
+            };
+        }
+
+        None
+    }
+
     pub fn set_text(&mut self, text: String) {
         #[cfg(all(
             any(
@@ -121,6 +192,43 @@ impl Clipboard {
         self.clipboard = text;
     }
 
+    /// Set the plain text, and the HTML if the platform clipboard supports it.
+    pub fn set_rich_text(&mut self, text: egui::RichClipboardText) {
+        let egui::RichClipboardText { plain, html } = text;
+
+        #[cfg(all(
+            any(
+                target_os = "linux",
+                target_os = "dragonfly",
+                target_os = "freebsd",
+                target_os = "netbsd",
+                target_os = "openbsd"
+            ),
+            feature = "smithay-clipboard"
+        ))]
+        if self.smithay.is_some() {
+            // smithay-clipboard only supports plain text
+            self.set_text(plain);
+            return;
+        }
+
+        #[cfg(all(
+            not(any(target_os = "android", target_os = "ios")),
+            feature = "arboard",
+        ))]
+        if let Some(clipboard) = &mut self.arboard
+            && let Some(html) = html.as_deref()
+        {
+            if let Err(err) = clipboard.set_html(html, Some(plain.as_str())) {
+                log::error!("arboard copy/cut error: {err}");
+            }
+            return;
+        }
+
+        _ = html;
+        self.set_text(plain);
+    }
+
     pub fn set_image(&mut self, image: &egui::ColorImage) {
         #[cfg(all(
             not(any(target_os = "android", target_os = "ios")),
@@ -172,6 +172,7 @@ impl State {
             .or_default()
             .native_pixels_per_point = native_pixels_per_point;
         slf.egui_input.system_theme = theme.map(to_egui_theme);
+        slf.egui_input.supports_rich_clipboard = true;
 
         if let Some(max_texture_side) = max_texture_side {
             slf.set_max_texture_side(max_texture_side);
@@ -983,12 +984,7 @@ impl State {
                     self.egui_input.events.push(egui::Event::Copy);
                     return;
                 } else if is_paste_command(self.egui_input.modifiers, active_key) {
-                    if let Some(contents) = self.clipboard.get() {
-                        let contents = contents.replace("\r\n", "\n");
-                        if !contents.is_empty() {
-                            self.egui_input.events.push(egui::Event::Paste(contents));
-                        }
-                    }
+                    self.egui_input.events.extend(self.clipboard.get_paste());
                     return;
                 }
             }
@@ -1060,6 +1056,9 @@ impl State {
                 egui::OutputCommand::CopyImage(image) => {
                     self.clipboard.set_image(&image);
                 }
+                egui::OutputCommand::CopyRichText(text) => {
+                    self.clipboard.set_rich_text(text);
+                }
                 egui::OutputCommand::OpenUrl(open_url) => {
                     open_url_in_browser(&open_url.url);
                 }
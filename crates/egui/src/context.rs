@@ -17,13 +17,13 @@ use epaint::{
 use crate::{
     Align2, CursorIcon, DeferredViewportUiCallback, FontDefinitions, Grid, Id, ImmediateViewport,
     ImmediateViewportRendererCallback, Key, KeyboardShortcut, Label, LayerId, Memory,
-    ModifierNames, Modifiers, NumExt as _, Order, Painter, RawInput, Response, RichText,
-    SafeAreaInsets, ScrollArea, Sense, Style, TextStyle, TextureHandle, TextureOptions, Ui,
-    ViewportBuilder, ViewportCommand, ViewportId, ViewportIdMap, ViewportIdPair, ViewportIdSet,
+    ModifierNames, Modifiers, NumExt as _, Order, Painter, Politeness, RawInput, Response,
+    RichText, SafeAreaInsets, ScrollArea, Sense, Style, TextStyle, TextureHandle, TextureOptions,
+    Ui, ViewportBuilder, ViewportCommand, ViewportId, ViewportIdMap, ViewportIdPair, ViewportIdSet,
     ViewportOutput, Visuals, Widget as _, WidgetRect, WidgetText,
     animation_manager::AnimationManager,
     containers::{self, area::AreaState},
-    data::output::PlatformOutput,
+    data::output::{OutputEvent, PlatformOutput},
     epaint,
     hit_test::WidgetHits,
     input_state::{InputState, MultiTouchInfo, PointerEvent, SurrenderFocusOn},
@@ -86,6 +86,21 @@ impl Default for WrappedTextureManager {
             "font id should be equal to TextureId::default(), but was {font_id:?}",
         );
 
+        // Will be filled in once some text uses `GlyphRendering::DistanceField`
+        let distance_field_font_id = tex_mngr.alloc(
+            "egui_distance_field_font_texture".into(),
+            epaint::ColorImage::filled([1, 1], Color32::TRANSPARENT).into(),
+            epaint::textures::TextureOptions {
+                distance_field: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            distance_field_font_id,
+            TextureId::DISTANCE_FIELD_FONT,
+            "distance field font id should be equal to TextureId::DISTANCE_FIELD_FONT, but was {distance_field_font_id:?}",
+        );
+
         Self(Arc::new(RwLock::new(tex_mngr)))
     }
 }
@@ -240,11 +255,24 @@ pub struct ViewportState {
     pub output: PlatformOutput,
     pub commands: Vec<ViewportCommand>,
 
+    /// Recent [`Context::announce`]ments, kept in the AccessKit tree for a while.
+    announcements: Vec<Announcement>,
+
     // ----------------------
     // Cross-frame statistics:
     pub num_multipass_in_row: usize,
 }
 
+/// See [`Context::announce`].
+struct Announcement {
+    id: Id,
+    text: String,
+    politeness: Politeness,
+
+    /// [`InputState::time`] when the announcement is removed from the AccessKit tree.
+    expires: f64,
+}
+
 /// What called [`Context::request_repaint`] or [`Context::request_discard`]?
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub struct RepaintCause {
@@ -402,6 +430,8 @@ struct ContextImpl {
 
     embed_viewports: bool,
 
+    distance_field_supported: bool,
+
     is_accesskit_enabled: bool,
 
     loaders: Arc<Loaders>,
@@ -581,6 +611,7 @@ impl ContextImpl {
         {
             profiling::scope!("Fonts::begin_pass");
             fonts.begin_pass(text_options);
+            fonts.set_distance_field_supported(self.distance_field_supported);
         }
     }
 
@@ -618,6 +649,41 @@ impl ContextImpl {
         builders.get_mut(&id).unwrap()
     }
 
+    /// Put the active announcements in the AccessKit tree as live regions,
+    /// and forget the ones that have expired.
+    fn announcement_nodes(&mut self) {
+        let viewport_id = self.viewport_id();
+        let viewport = self.viewport();
+        let time = viewport.input.time;
+        viewport.announcements.retain(|a| time < a.expires);
+
+        let Some(next_expiry) = viewport
+            .announcements
+            .iter()
+            .map(|a| a.expires)
+            .reduce(f64::min)
+        else {
+            return;
+        };
+
+        if viewport.this_pass.accesskit_state.is_some() {
+            let announcements = std::mem::take(&mut viewport.announcements);
+            for announcement in &announcements {
+                let node = self.accesskit_node_builder(announcement.id);
+                node.set_role(accesskit::Role::Status);
+                node.set_live(announcement.politeness.into());
+                node.set_value(announcement.text.clone());
+            }
+            self.viewport().announcements = announcements;
+        }
+
+        self.request_repaint_after(
+            Duration::from_secs_f64(next_expiry - time),
+            viewport_id,
+            RepaintCause::new_reason("announcement expiry"),
+        );
+    }
+
     fn pixels_per_point(&mut self) -> f32 {
         self.viewport().input.pixels_per_point
     }
@@ -1594,6 +1660,23 @@ impl Context {
         self.send_cmd(crate::OutputCommand::CopyImage(image));
     }
 
+    /// Copy text to the system clipboard in several formats, e.g. both as plain text and HTML.
+    ///
+    /// This is what copying selected text from a [`crate::Label`] does.
+    ///
+    /// Only the plain text is copied unless the integration sets [`crate::RawInput::supports_rich_clipboard`].
+    ///
+    /// Note that in web applications, the clipboard is only accessible in secure contexts (e.g.,
+    /// HTTPS or localhost). If this method is used outside of a secure context, it will log an
+    /// error and do nothing. See <https://developer.mozilla.org/en-US/docs/Web/Security/Secure_Contexts>.
+    pub fn copy_rich_text(&self, text: crate::RichClipboardText) {
+        if self.input(|i| i.raw.supports_rich_clipboard) {
+            self.send_cmd(crate::OutputCommand::CopyRichText(text));
+        } else {
+            self.send_cmd(crate::OutputCommand::CopyText(text.plain));
+        }
+    }
+
     fn can_show_modifier_symbols(&self) -> bool {
         let ModifierNames {
             alt,
@@ -2199,6 +2282,25 @@ impl Context {
         self.style_mut_of(theme, |style| style.visuals = visuals);
     }
 
+    /// Replace the visuals of both the dark and light theme with ones derived from `palette`.
+    ///
+    /// This resets any other changes to the visuals.
+    ///
+    /// Example:
+    /// ```
+    /// # let mut ctx = egui::Context::default();
+    /// let brand = egui::ThemedColor::new(
+    ///     egui::Color32::from_rgb(255, 140, 60),
+    ///     egui::Color32::from_rgb(200, 80, 0),
+    /// );
+    /// ctx.set_palette(&egui::Palette::default().with("hyperlink", brand).with("brand", brand));
+    /// ```
+    pub fn set_palette(&self, palette: &crate::Palette) {
+        for theme in [Theme::Dark, Theme::Light] {
+            self.set_visuals_of(theme, crate::Visuals::from_palette(theme, palette.clone()));
+        }
+    }
+
     /// The [`crate::Visuals`] used by all subsequent popups, menus, etc.
     ///
     /// You can also use [`Ui::visuals_mut`] to change the visuals of a single [`Ui`].
@@ -2549,6 +2651,8 @@ impl Context {
 
 impl ContextImpl {
     fn end_pass(&mut self) -> FullOutput {
+        self.announcement_nodes();
+
         let ended_viewport_id = self.viewport_id();
         let viewport = self.viewports.entry(ended_viewport_id).or_default();
         let pixels_per_point = viewport.input.pixels_per_point;
@@ -2565,6 +2669,9 @@ impl ContextImpl {
                 // A partial font atlas update, e.g. a new glyph has been entered.
                 tex_mngr.set(TextureId::default(), font_image_delta);
             }
+            if let Some(font_image_delta) = fonts.distance_field_font_image_delta() {
+                tex_mngr.set(TextureId::DISTANCE_FIELD_FONT, font_image_delta);
+            }
         }
 
         // Inform the backend of all textures that have been updated (including font atlas).
@@ -2596,10 +2703,29 @@ impl ContextImpl {
             }
         }
 
-        let shapes = viewport
+        #[cfg_attr(not(debug_assertions), expect(unused_mut))]
+        let mut shapes = viewport
             .graphics
             .drain(self.memory.areas().order(), &self.memory.to_global);
 
+        #[cfg(debug_assertions)]
+        {
+            let style = self.memory.options.style();
+            if let Some(level) = style.debug.show_contrast_issues {
+                let issues =
+                    crate::contrast::audit_contrast(&shapes, level, style.visuals.panel_fill);
+                shapes.extend(issues.into_iter().map(|issue| ClippedShape {
+                    clip_rect: Rect::EVERYTHING,
+                    shape: epaint::Shape::rect_stroke(
+                        issue.rect,
+                        0.0,
+                        (1.0, Color32::RED),
+                        StrokeKind::Outside,
+                    ),
+                }));
+            }
+        }
+
         let mut repaint_needed = false;
 
         if self.memory.options.repaint_on_widget_change {
@@ -3249,7 +3375,7 @@ impl Context {
 
         for (name, data) in &mut font_definitions.font_data {
             ui.collapsing(name, |ui| {
-                let mut tweak = data.tweak;
+                let mut tweak = data.tweak.clone();
                 if tweak.ui(ui).changed() {
                     Arc::make_mut(data).tweak = tweak;
                     changed = true;
@@ -3648,7 +3774,14 @@ impl Context {
         })
     }
 
-    pub(crate) fn register_accesskit_parent(&self, id: Id, parent_id: Id) {
+    /// Make `parent_id` the parent of the AccessKit node `id`, once it is created.
+    ///
+    /// Use this to group nodes under a node that doesn't correspond to a [`Ui`],
+    /// e.g. the rows of a table.
+    /// Must be called before the node `id` is created with [`Self::accesskit_node_builder`].
+    ///
+    /// Does nothing if accesskit is off.
+    pub fn register_accesskit_parent(&self, id: Id, parent_id: Id) {
         self.write(|ctx| {
             if let Some(state) = ctx.viewport().this_pass.accesskit_state.as_mut() {
                 state.parent_map.insert(id, parent_id);
@@ -3656,6 +3789,38 @@ impl Context {
         });
     }
 
+    /// Have a screen reader read out `text`, without moving the focus.
+    ///
+    /// Use this for things like "File saved" or "3 results found".
+    ///
+    /// The text is added to the AccessKit tree as a live region for
+    /// [`Self::ANNOUNCEMENT_DURATION`], and is also reported as an [`OutputEvent::Announcement`],
+    /// which `eframe` speaks out loud on the web if [`crate::Options::screen_reader`] is on.
+    pub fn announce(&self, text: impl Into<String>, politeness: Politeness) {
+        let text = text.into();
+        self.write(|ctx| {
+            let viewport = ctx.viewport();
+            let id = Id::new("__egui_announcement").with((
+                viewport.repaint.cumulative_pass_nr,
+                viewport.announcements.len(),
+            ));
+            let expires = viewport.input.time + Self::ANNOUNCEMENT_DURATION.as_secs_f64();
+            viewport.announcements.push(Announcement {
+                id,
+                text: text.clone(),
+                politeness,
+                expires,
+            });
+        });
+        self.output_mut(|o| {
+            o.events
+                .push(OutputEvent::Announcement { text, politeness });
+        });
+    }
+
+    /// How long an [`Self::announce`]ment stays in the AccessKit tree.
+    pub const ANNOUNCEMENT_DURATION: Duration = Duration::from_secs(5);
+
     /// Enable generation of AccessKit tree updates in all future frames.
     pub fn enable_accesskit(&self) {
         self.write(|ctx| ctx.is_accesskit_enabled = true);
@@ -3969,6 +4134,32 @@ impl Context {
         self.write(|ctx| ctx.embed_viewports = value);
     }
 
+    /// Can the backend render textures with [`epaint::textures::TextureOptions::distance_field`]?
+    ///
+    /// If not, text with [`crate::text::GlyphRendering::DistanceField`]
+    /// is rendered with [`crate::text::GlyphRendering::Bitmap`] instead.
+    ///
+    /// `eframe` sets this to `true`, but the default value is `false`.
+    pub fn distance_field_supported(&self) -> bool {
+        self.read(|ctx| ctx.distance_field_supported)
+    }
+
+    /// Can the backend render textures with [`epaint::textures::TextureOptions::distance_field`]?
+    ///
+    /// If not, text with [`crate::text::GlyphRendering::DistanceField`]
+    /// is rendered with [`crate::text::GlyphRendering::Bitmap`] instead.
+    ///
+    /// Backends that support it (like `egui_glow` and `egui-wgpu`) should set this to `true`.
+    /// The default value is `false`.
+    pub fn set_distance_field_supported(&self, value: bool) {
+        self.write(|ctx| {
+            ctx.distance_field_supported = value;
+            if let Some(fonts) = &mut ctx.fonts {
+                fonts.set_distance_field_supported(value);
+            }
+        });
+    }
+
     /// Send a command to the current viewport.
     ///
     /// This lets you affect the current viewport, e.g. resizing the window.
@@ -0,0 +1,310 @@
+//! Check that painted text has enough contrast against its background,
+//! following the [WCAG 2](https://www.w3.org/TR/WCAG21/#contrast-minimum) guidelines.
+//!
+//! The audit works on the shapes that egui painted, so it catches low-contrast text
+//! no matter where the colors came from.
+//! It understands filled rectangles as backgrounds, which covers panels, windows, frames and buttons,
+//! but not e.g. images or custom meshes.
+//!
+//! You can see the problems live by enabling [`crate::style::DebugOptions::show_contrast_issues`],
+//! or check them in a test with `egui_kittest`.
+
+use epaint::{ClippedShape, Color32, Rect, Shape, TextShape, emath::Rot2};
+
+/// The level of the WCAG contrast requirement to check against.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[expect(clippy::upper_case_acronyms)] // That's what the levels are called
+pub enum WcagLevel {
+    /// Contrast of at least 4.5:1 for normal text, and 3:1 for large text.
+    #[default]
+    AA,
+
+    /// Contrast of at least 7:1 for normal text, and 4.5:1 for large text.
+    AAA,
+}
+
+impl WcagLevel {
+    /// Text with a line height of at least this many points counts as "large".
+    ///
+    /// WCAG defines large text as 18pt, which is 24 CSS pixels (= egui points).
+    pub const LARGE_TEXT_HEIGHT: f32 = 24.0;
+
+    /// The minimum contrast ratio required for text of the given size.
+    pub fn min_ratio(self, large_text: bool) -> f32 {
+        match (self, large_text) {
+            (Self::AA, true) => 3.0,
+            (Self::AA, false) | (Self::AAA, true) => 4.5,
+            (Self::AAA, false) => 7.0,
+        }
+    }
+}
+
+impl std::fmt::Display for WcagLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AA => f.write_str("AA"),
+            Self::AAA => f.write_str("AAA"),
+        }
+    }
+}
+
+/// A run of text that doesn't have enough contrast against its background.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContrastIssue {
+    /// Where the text is, in points.
+    pub rect: Rect,
+
+    /// The text with too little contrast.
+    pub text: String,
+
+    /// The color of the text, after blending it with the background.
+    pub text_color: Color32,
+
+    /// The color behind the text.
+    pub background: Color32,
+
+    /// The contrast ratio between [`Self::text_color`] and [`Self::background`].
+    pub ratio: f32,
+
+    /// The contrast ratio required by the level that was checked.
+    pub required: f32,
+}
+
+impl std::fmt::Display for ContrastIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self {
+            rect,
+            text,
+            text_color,
+            background,
+            ratio,
+            required,
+        } = self;
+        write!(
+            f,
+            "{text:?} at {rect:?}: {} on {} has contrast {ratio:.2}:1, but {required}:1 is required",
+            text_color.to_hex(),
+            background.to_hex(),
+        )
+    }
+}
+
+/// Find all text in `shapes` that doesn't meet the contrast requirement of `level`.
+///
+/// `shapes` should be in painting order, e.g. [`crate::FullOutput::shapes`].
+/// `background` is the color behind everything, usually [`crate::Visuals::panel_fill`]
+/// or whatever the backend clears the screen with.
+pub fn audit_contrast(
+    shapes: &[ClippedShape],
+    level: WcagLevel,
+    background: Color32,
+) -> Vec<ContrastIssue> {
+    let mut auditor = Auditor {
+        level,
+        background,
+        fills: Vec::new(),
+        issues: Vec::new(),
+    };
+    for ClippedShape { clip_rect, shape } in shapes {
+        auditor.shape(*clip_rect, shape);
+    }
+    auditor.issues
+}
+
+struct Auditor {
+    level: WcagLevel,
+    background: Color32,
+
+    /// Filled rectangles painted so far, clipped, in painting order.
+    fills: Vec<(Rect, Color32)>,
+
+    issues: Vec<ContrastIssue>,
+}
+
+impl Auditor {
+    fn shape(&mut self, clip_rect: Rect, shape: &Shape) {
+        match shape {
+            Shape::Vec(shapes) => {
+                for shape in shapes {
+                    self.shape(clip_rect, shape);
+                }
+            }
+            Shape::Rect(rect_shape) => {
+                // Blurred rectangles are shadows, which don't act as a background.
+                if rect_shape.blur_width == 0.0 && rect_shape.fill.a() > 0 {
+                    let rect = rect_shape.rect.intersect(clip_rect);
+                    if rect.is_positive() {
+                        self.fills.push((rect, rect_shape.fill));
+                    }
+                }
+            }
+            Shape::Text(text_shape) => self.text(clip_rect, text_shape),
+            _ => {}
+        }
+    }
+
+    /// The color behind the center of `rect`.
+    fn background_at(&self, rect: Rect) -> Color32 {
+        let center = rect.center();
+        self.fills
+            .iter()
+            .filter(|(fill_rect, _)| fill_rect.contains(center))
+            .fold(self.background, |color, (_, fill)| color.blend(*fill))
+    }
+
+    fn text(&mut self, clip_rect: Rect, text_shape: &TextShape) {
+        let TextShape {
+            pos,
+            galley,
+            underline: _,
+            override_text_color,
+            fallback_color,
+            opacity_factor,
+            angle,
+        } = text_shape;
+
+        if *opacity_factor <= 0.0 {
+            return;
+        }
+
+        let rotator = Rot2::from_angle(*angle);
+
+        for row in &galley.rows {
+            let mesh = &row.visuals.mesh;
+            let large_text = row.height() >= WcagLevel::LARGE_TEXT_HEIGHT;
+
+            // Group consecutive glyphs of the same color into runs.
+            let mut run: Option<(Rect, String, Color32)> = None;
+            for glyph in &row.glyphs {
+                let vertex = glyph.first_vertex as usize;
+                if glyph.chr.is_whitespace()
+                    || glyph.uv_rect.colored
+                    || !row.visuals.glyph_vertex_range.contains(&vertex)
+                {
+                    if let Some((_, text, _)) = &mut run {
+                        text.push(glyph.chr);
+                    }
+                    continue;
+                }
+
+                let mut color = override_text_color.unwrap_or(mesh.vertices[vertex].color);
+                if color == Color32::PLACEHOLDER {
+                    color = *fallback_color;
+                }
+                if *opacity_factor < 1.0 {
+                    color = color.gamma_multiply(*opacity_factor);
+                }
+
+                let glyph_rect = glyph.logical_rect().translate(row.pos.to_vec2());
+                let glyph_rect = galley.rect_from_row_space(glyph_rect);
+                let glyph_rect = if *angle == 0.0 {
+                    glyph_rect.translate(pos.to_vec2())
+                } else {
+                    glyph_rect.rotate_bb(rotator).translate(pos.to_vec2())
+                };
+
+                match &mut run {
+                    Some((rect, text, run_color)) if *run_color == color => {
+                        *rect = rect.union(glyph_rect);
+                        text.push(glyph.chr);
+                    }
+                    _ => {
+                        if let Some(run) = run.take() {
+                            self.check_run(clip_rect, run, large_text);
+                        }
+                        run = Some((glyph_rect, glyph.chr.to_string(), color));
+                    }
+                }
+            }
+            if let Some(run) = run {
+                self.check_run(clip_rect, run, large_text);
+            }
+        }
+    }
+
+    fn check_run(
+        &mut self,
+        clip_rect: Rect,
+        (rect, text, color): (Rect, String, Color32),
+        large_text: bool,
+    ) {
+        let visible = rect.intersect(clip_rect);
+        if !visible.is_positive() || color.a() == 0 {
+            return;
+        }
+
+        let background = self.background_at(visible);
+        let text_color = background.blend(color);
+        let ratio = text_color.contrast_ratio(background);
+        let required = self.level.min_ratio(large_text);
+
+        if ratio < required {
+            self.issues.push(ContrastIssue {
+                rect,
+                text: text.trim_end().to_owned(),
+                text_color,
+                background,
+                ratio,
+                required,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Context, FontId, RawInput, Visuals};
+
+    fn issues_for(visuals: Visuals, text_color: Color32) -> Vec<ContrastIssue> {
+        let ctx = Context::default();
+        ctx.set_visuals(visuals);
+        let output = ctx.run(RawInput::default(), |ctx| {
+            crate::CentralPanel::default().show(ctx, |ui| {
+                ui.label("Readable text");
+                ui.painter().text(
+                    ui.max_rect().center(),
+                    crate::Align2::CENTER_CENTER,
+                    "Colored",
+                    FontId::proportional(14.0),
+                    text_color,
+                );
+            });
+        });
+        audit_contrast(&output.shapes, WcagLevel::AA, Color32::BLACK)
+    }
+
+    #[test]
+    fn finds_low_contrast_text() {
+        let issues = issues_for(Visuals::high_contrast_dark(), Color32::from_gray(60));
+        assert_eq!(issues.len(), 1, "{issues:#?}");
+        assert_eq!(issues[0].text, "Colored");
+        assert_eq!(issues[0].background, Color32::BLACK);
+        assert!(issues[0].ratio < 4.5);
+    }
+
+    #[test]
+    fn high_contrast_themes_pass_aaa() {
+        for visuals in [
+            Visuals::high_contrast_dark(),
+            Visuals::high_contrast_light(),
+        ] {
+            let ctx = Context::default();
+            let background = visuals.panel_fill;
+            ctx.set_visuals(visuals);
+            let output = ctx.run(RawInput::default(), |ctx| {
+                crate::CentralPanel::default().show(ctx, |ui| {
+                    ui.label("Label");
+                    ui.weak("Weak");
+                    ui.hyperlink("https://www.egui.rs");
+                    let _ = ui.button("Button");
+                    ui.colored_label(ui.visuals().error_fg_color, "Error");
+                    ui.colored_label(ui.visuals().warn_fg_color, "Warning");
+                });
+            });
+            let issues = audit_contrast(&output.shapes, WcagLevel::AAA, background);
+            assert!(issues.is_empty(), "{issues:#?}");
+        }
+    }
+}
@@ -86,6 +86,12 @@ pub struct RawInput {
     ///
     /// `None` means "don't know".
     pub system_theme: Option<Theme>,
+
+    /// Does the integration handle [`crate::OutputCommand::CopyRichText`]?
+    ///
+    /// If not, [`crate::Context::copy_rich_text`] only copies the plain text,
+    /// with [`crate::OutputCommand::CopyText`].
+    pub supports_rich_clipboard: bool,
 }
 
 impl Default for RawInput {
@@ -103,6 +109,7 @@ impl Default for RawInput {
             dropped_files: Default::default(),
             focused: true, // integrations opt into global focus tracking
             system_theme: None,
+            supports_rich_clipboard: false,
             safe_area_insets: Default::default(),
         }
     }
@@ -138,6 +145,7 @@ impl RawInput {
             dropped_files: std::mem::take(&mut self.dropped_files),
             focused: self.focused,
             system_theme: self.system_theme,
+            supports_rich_clipboard: self.supports_rich_clipboard,
         }
     }
 
@@ -156,6 +164,7 @@ impl RawInput {
             mut dropped_files,
             focused,
             system_theme,
+            supports_rich_clipboard,
             safe_area_insets: safe_area,
         } = newer;
 
@@ -171,6 +180,7 @@ impl RawInput {
         self.dropped_files.append(&mut dropped_files);
         self.focused = focused;
         self.system_theme = system_theme;
+        self.supports_rich_clipboard = supports_rich_clipboard;
         self.safe_area_insets = safe_area;
     }
 }
@@ -404,6 +414,21 @@ pub enum Event {
     /// The integration detected a "paste" event (e.g. Cmd+V).
     Paste(String),
 
+    /// The integration detected a "paste" event (e.g. Cmd+V) of an image, e.g. a screenshot.
+    ///
+    /// If the clipboard also holds text, this comes after a [`Self::Paste`] of it.
+    ///
+    /// See also [`crate::Response::pasted_images`].
+    PasteImage(ColorImage),
+
+    /// The integration detected a "paste" event (e.g. Cmd+V) of a list of files,
+    /// e.g. copied from a file manager.
+    ///
+    /// If the clipboard also holds text, this comes after a [`Self::Paste`] of it.
+    ///
+    /// See also [`crate::Response::pasted_files`].
+    PasteFiles(Vec<std::path::PathBuf>),
+
     /// Text input, e.g. via keyboard.
     ///
     /// When the user presses enter/return, do not send a [`Text`](Event::Text) (just [`Key::Enter`]).
@@ -1145,6 +1170,7 @@ impl RawInput {
             dropped_files,
             focused,
             system_theme,
+            supports_rich_clipboard,
             safe_area_insets: safe_area,
         } = self;
 
@@ -1175,6 +1201,9 @@ impl RawInput {
         ui.label(format!("dropped_files: {}", dropped_files.len()));
         ui.label(format!("focused: {focused}"));
         ui.label(format!("system_theme: {system_theme:?}"));
+        ui.label(format!(
+            "supports_rich_clipboard: {supports_rich_clipboard}"
+        ));
         ui.label(format!("safe_area: {safe_area:?}"));
         ui.scope(|ui| {
             ui.set_min_height(150.0);
@@ -95,10 +95,40 @@ pub enum OutputCommand {
     /// Put this image to the system clipboard.
     CopyImage(crate::ColorImage),
 
+    /// Put text to the system clipboard in several formats at once,
+    /// so that apps that understand e.g. HTML keep the formatting when pasting.
+    ///
+    /// Integrations set as many of the formats as the platform supports.
+    /// This is only sent to integrations that set [`crate::RawInput::supports_rich_clipboard`].
+    CopyRichText(RichClipboardText),
+
     /// Open this url in a browser.
     OpenUrl(OpenUrl),
 }
 
+/// Text for the system clipboard, in several formats.
+///
+/// See [`OutputCommand::CopyRichText`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct RichClipboardText {
+    /// For apps that don't understand any of the other formats.
+    pub plain: String,
+
+    /// An HTML fragment, e.g. `<span style="color: #ff0000">red</span>`.
+    pub html: Option<String>,
+}
+
+impl RichClipboardText {
+    /// Only plain text.
+    pub fn plain(text: impl Into<String>) -> Self {
+        Self {
+            plain: text.into(),
+            html: None,
+        }
+    }
+}
+
 /// The non-rendering part of what egui emits each frame.
 ///
 /// You can access (and modify) this with [`crate::Context::output`].
@@ -148,7 +178,8 @@ pub struct PlatformOutput {
 }
 
 impl PlatformOutput {
-    /// This can be used by a text-to-speech system to describe the events (if any).
+    /// This can be used by a text-to-speech system to describe the events (if any),
+    /// including [`OutputEvent::Announcement`]s.
     pub fn events_description(&self) -> String {
         // only describe last event:
         if let Some(event) = self.events.iter().next_back() {
@@ -161,11 +192,32 @@ impl PlatformOutput {
                 | OutputEvent::ValueChanged(widget_info) => {
                     return widget_info.description();
                 }
+                OutputEvent::Announcement { text, .. } => {
+                    return text.clone();
+                }
             }
         }
         Default::default()
     }
 
+    /// What a text-to-speech system should say about the events (if any), in order.
+    ///
+    /// This is every [`OutputEvent::Announcement`],
+    /// followed by the description of the last event about a widget.
+    pub fn speech_descriptions(&self) -> Vec<String> {
+        let mut descriptions: Vec<String> = self
+            .events
+            .iter()
+            .filter_map(OutputEvent::announcement)
+            .map(ToOwned::to_owned)
+            .collect();
+        if let Some(widget_info) = self.events.iter().rev().find_map(OutputEvent::widget_info) {
+            descriptions.push(widget_info.description());
+        }
+        descriptions.retain(|description| !description.is_empty());
+        descriptions
+    }
+
     /// Add on new output.
     pub fn append(&mut self, newer: Self) {
         let Self {
@@ -447,17 +499,36 @@ pub enum OutputEvent {
 
     /// A widget's value changed.
     ValueChanged(WidgetInfo),
+
+    /// Some text that a screen reader should read out,
+    /// without any widget getting focus.
+    ///
+    /// See [`crate::Context::announce`].
+    Announcement {
+        text: String,
+        politeness: Politeness,
+    },
 }
 
 impl OutputEvent {
-    pub fn widget_info(&self) -> &WidgetInfo {
+    /// The widget this event is about, if any.
+    pub fn widget_info(&self) -> Option<&WidgetInfo> {
         match self {
             Self::Clicked(info)
             | Self::DoubleClicked(info)
             | Self::TripleClicked(info)
             | Self::FocusGained(info)
             | Self::TextSelectionChanged(info)
-            | Self::ValueChanged(info) => info,
+            | Self::ValueChanged(info) => Some(info),
+            Self::Announcement { .. } => None,
+        }
+    }
+
+    /// The text of an [`Self::Announcement`].
+    pub fn announcement(&self) -> Option<&str> {
+        match self {
+            Self::Announcement { text, .. } => Some(text),
+            _ => None,
         }
     }
 }
@@ -471,6 +542,36 @@ impl std::fmt::Debug for OutputEvent {
             Self::FocusGained(wi) => write!(f, "FocusGained({wi:?})"),
             Self::TextSelectionChanged(wi) => write!(f, "TextSelectionChanged({wi:?})"),
             Self::ValueChanged(wi) => write!(f, "ValueChanged({wi:?})"),
+            Self::Announcement { text, politeness } => {
+                write!(f, "Announcement({text:?}, {politeness:?})")
+            }
+        }
+    }
+}
+
+/// How urgently a screen reader should read out an announcement.
+///
+/// See [`crate::Context::announce`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Politeness {
+    /// Wait until the screen reader is done with what it is currently saying.
+    ///
+    /// Use this for most things, e.g. "File saved" or "3 results found".
+    #[default]
+    Polite,
+
+    /// Interrupt whatever the screen reader is currently saying.
+    ///
+    /// Only use this for urgent things, like errors.
+    Assertive,
+}
+
+impl From<Politeness> for accesskit::Live {
+    fn from(politeness: Politeness) -> Self {
+        match politeness {
+            Politeness::Polite => Self::Polite,
+            Politeness::Assertive => Self::Assertive,
         }
     }
 }
@@ -79,10 +79,14 @@ pub(crate) struct GridLayout {
     min_cell_size: Vec2,
     max_cell_size: Vec2,
     color_picker: Option<ColorPickerFn>,
+    header_row: bool,
 
     // Cursor:
     col: usize,
     row: usize,
+
+    /// The `(row, col)` of the cell the last widget was placed in.
+    last_cell: Option<(usize, usize)>,
 }
 
 impl GridLayout {
@@ -114,9 +118,11 @@ impl GridLayout {
             min_cell_size: ui.spacing().interact_size,
             max_cell_size: Vec2::INFINITY,
             color_picker: None,
+            header_row: false,
 
             col: 0,
             row: 0,
+            last_cell: None,
         }
     }
 }
@@ -233,6 +239,7 @@ impl GridLayout {
             .set_min_row_height(self.row, widget_rect.height().max(self.min_cell_size.y));
 
         cursor.min.x += self.prev_col_width(self.col) + self.spacing.x;
+        self.last_cell = Some((self.row, self.col));
         self.col += 1;
     }
 
@@ -270,6 +277,63 @@ impl GridLayout {
         self.paint_row(cursor, painter);
     }
 
+    /// The `(row, col)` of the cell that the next widget will be placed in.
+    pub(crate) fn next_cell_index(&self) -> (usize, usize) {
+        (self.row, self.col)
+    }
+
+    /// The `(row, col)` of the cell that the last widget was placed in.
+    ///
+    /// `None` before the first widget has been placed.
+    pub(crate) fn last_cell_index(&self) -> Option<(usize, usize)> {
+        self.last_cell
+    }
+
+    /// Create the AccessKit nodes for a cell and its row, and return the id of the cell node.
+    ///
+    /// `table_id` is the id of the [`Ui`] the grid is in.
+    pub(crate) fn accesskit_cell(&self, table_id: Id, (row, col): (usize, usize)) -> Id {
+        let row_id = table_id.with(("grid_row", row));
+        let cell_id = row_id.with(col);
+
+        let is_accesskit_enabled = self.ctx.accesskit_node_builder(table_id, |_| ()).is_some();
+        if is_accesskit_enabled {
+            self.ctx.register_accesskit_parent(row_id, table_id);
+            self.ctx.register_accesskit_parent(cell_id, row_id);
+            self.ctx.accesskit_node_builder(row_id, |node| {
+                node.set_role(accesskit::Role::Row);
+                node.set_row_index(row);
+            });
+            let is_header = self.header_row && row == 0;
+            self.ctx.accesskit_node_builder(cell_id, |node| {
+                node.set_role(if is_header {
+                    accesskit::Role::ColumnHeader
+                } else {
+                    accesskit::Role::Cell
+                });
+                node.set_row_index(row);
+                node.set_column_index(col);
+            });
+        }
+
+        cell_id
+    }
+
+    /// Mark the [`Ui`] the grid is in as an AccessKit table, now that we know its size.
+    pub(crate) fn accesskit_table(&self, table_id: Id) {
+        let row_count = if self.col == 0 {
+            self.row
+        } else {
+            self.row + 1
+        };
+        let column_count = self.curr_state.col_widths.len();
+        self.ctx.accesskit_node_builder(table_id, |node| {
+            node.set_role(accesskit::Role::Table);
+            node.set_row_count(row_count);
+            node.set_column_count(column_count);
+        });
+    }
+
     pub(crate) fn save(&self) {
         // We need to always save state on the first frame, otherwise request_discard
         // would be called repeatedly (see #5132)
@@ -318,6 +382,7 @@ pub struct Grid {
     spacing: Option<Vec2>,
     start_row: usize,
     color_picker: Option<ColorPickerFn>,
+    header_row: bool,
 }
 
 impl Grid {
@@ -332,6 +397,7 @@ impl Grid {
             spacing: None,
             start_row: 0,
             color_picker: None,
+            header_row: false,
         }
     }
 
@@ -405,6 +471,16 @@ impl Grid {
         self.start_row = start_row;
         self
     }
+
+    /// If `true`, the first row holds the column headers.
+    ///
+    /// Its cells are then announced as column headers by screen readers.
+    /// Default: `false`.
+    #[inline]
+    pub fn header_row(mut self, header_row: bool) -> Self {
+        self.header_row = header_row;
+        self
+    }
 }
 
 impl Grid {
@@ -426,6 +502,7 @@ impl Grid {
             spacing,
             start_row,
             mut color_picker,
+            header_row,
         } = self;
         let min_col_width = min_col_width.unwrap_or_else(|| ui.spacing().interact_size.x);
         let min_row_height = min_row_height.unwrap_or_else(|| ui.spacing().interact_size.y);
@@ -462,6 +539,7 @@ impl Grid {
                 let grid = GridLayout {
                     num_columns,
                     color_picker,
+                    header_row,
                     min_cell_size: vec2(min_col_width, min_row_height),
                     max_cell_size,
                     spacing,
@@ -410,6 +410,7 @@ mod atomics;
 pub mod cache;
 pub mod containers;
 mod context;
+pub mod contrast;
 mod data;
 pub mod debug_text;
 mod drag_and_drop;
@@ -428,6 +429,7 @@ mod memory;
 pub mod menu;
 pub mod os;
 mod painter;
+mod palette;
 mod pass_state;
 pub(crate) mod placer;
 mod plugin;
@@ -435,6 +437,8 @@ pub mod response;
 mod sense;
 pub mod style;
 pub mod text_selection;
+#[cfg(feature = "theme_file")]
+pub mod theme_file;
 mod ui;
 mod ui_builder;
 mod ui_stack;
@@ -467,18 +471,26 @@ pub use emath::{
 pub use epaint::{
     ClippedPrimitive, ColorImage, CornerRadius, ImageData, Margin, Mesh, PaintCallback,
     PaintCallbackInfo, Shadow, Shape, Stroke, StrokeKind, TextureHandle, TextureId, mutex,
-    text::{FontData, FontDefinitions, FontFamily, FontId, FontTweak},
+    text::{
+        FontData, FontDefinitions, FontFamily, FontFeature, FontId, FontTweak, FontVariations,
+        Language,
+    },
     textures::{TextureFilter, TextureOptions, TextureWrapMode, TexturesDelta},
 };
 
 pub mod text {
     pub use crate::text_selection::CCursorRange;
     pub use epaint::text::{
-        FontData, FontDefinitions, FontFamily, Fonts, Galley, LayoutJob, LayoutSection, TAB_SIZE,
-        TextFormat, TextWrapping, cursor::CCursor,
+        DecorationStyle, Elision, FontData, FontDatabase, FontDefinitions, FontFaceInfo,
+        FontFaceKey, FontFamily, FontFeature, Fonts, Galley, GlyphRendering, Language, LayoutJob,
+        LayoutSection, Placeholder, TAB_SIZE, TabAlign, TabStop, TabStops, TextFormat,
+        TextWrapping, WritingMode, cursor::CCursor,
     };
 }
 
+#[cfg(feature = "theme_file")]
+pub use theme_file::{ThemeFile, ThemeFileError};
+
 pub use self::{
     atomics::*,
     containers::{menu::MenuBar, *},
@@ -487,8 +499,8 @@ pub use self::{
         Key, UserData,
         input::*,
         output::{
-            self, CursorIcon, FullOutput, OpenUrl, OutputCommand, PlatformOutput,
-            UserAttentionType, WidgetInfo,
+            self, CursorIcon, FullOutput, OpenUrl, OutputCommand, PlatformOutput, Politeness,
+            RichClipboardText, UserAttentionType, WidgetInfo,
         },
     },
     drag_and_drop::DragAndDrop,
@@ -501,6 +513,7 @@ pub use self::{
     load::SizeHint,
     memory::{FocusDirection, Memory, Options, Theme, ThemePreference},
     painter::Painter,
+    palette::{Palette, ThemedColor},
     plugin::Plugin,
     response::{InnerResponse, Response},
     sense::Sense,
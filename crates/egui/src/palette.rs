@@ -0,0 +1,393 @@
+//! Semantic colors that resolve differently in dark and light mode.
+
+use std::collections::BTreeMap;
+
+use crate::{Color32, Theme};
+
+/// A color with one value for dark mode and one for light mode.
+///
+/// Use this instead of a hard-coded [`Color32`] for colors that need to look
+/// right in both themes, and resolve it with [`crate::Visuals::resolve`]:
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// const ACCENT: egui::ThemedColor = egui::ThemedColor::new(
+///     egui::Color32::from_rgb(120, 200, 255),
+///     egui::Color32::from_rgb(0, 90, 160),
+/// );
+/// ui.colored_label(ui.visuals().resolve(ACCENT), "Accented text");
+/// # });
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ThemedColor {
+    /// The color used in [`Theme::Dark`].
+    pub dark: Color32,
+
+    /// The color used in [`Theme::Light`].
+    pub light: Color32,
+}
+
+impl ThemedColor {
+    #[inline]
+    pub const fn new(dark: Color32, light: Color32) -> Self {
+        Self { dark, light }
+    }
+
+    /// The same color in both themes.
+    #[inline]
+    pub const fn same(color: Color32) -> Self {
+        Self::new(color, color)
+    }
+
+    /// Gray in both themes, with the given levels.
+    #[inline]
+    pub const fn gray(dark: u8, light: u8) -> Self {
+        Self::new(Color32::from_gray(dark), Color32::from_gray(light))
+    }
+
+    /// The color to use for the given theme.
+    #[inline]
+    pub fn get(self, theme: Theme) -> Color32 {
+        match theme {
+            Theme::Dark => self.dark,
+            Theme::Light => self.light,
+        }
+    }
+}
+
+impl From<Color32> for ThemedColor {
+    #[inline]
+    fn from(color: Color32) -> Self {
+        Self::same(color)
+    }
+}
+
+/// The named colors all of egui's [`crate::Visuals`] are derived from.
+///
+/// Each field is a token that can be looked up by name with [`crate::Visuals::token`],
+/// e.g. `ui.visuals().token("success")`.
+/// Apps can add their own tokens with [`Self::set`].
+///
+/// To re-brand all widgets, change some of the tokens and use
+/// [`crate::Visuals::from_palette`] or [`crate::Context::set_palette`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Palette {
+    /// Background of panels, and of non-interactive widgets.
+    pub panel_fill: ThemedColor,
+
+    /// Background of windows.
+    pub window_fill: ThemedColor,
+
+    /// Window outlines, separators and indentation lines.
+    pub border: ThemedColor,
+
+    /// Background of text edits, scroll bars, and similar.
+    pub extreme_bg: ThemedColor,
+
+    /// Something just barely different from the background, e.g. for striped grids.
+    pub faint_bg: ThemedColor,
+
+    /// Background behind code-styled text.
+    pub code_bg: ThemedColor,
+
+    /// Normal, non-interactive text.
+    pub text: ThemedColor,
+
+    /// Background of buttons and other widgets that can be interacted with.
+    pub widget_bg: ThemedColor,
+
+    /// Text and icons of interactive widgets.
+    pub widget_fg: ThemedColor,
+
+    /// Background of a hovered widget.
+    pub hovered_bg: ThemedColor,
+
+    /// Outline of a hovered widget.
+    pub hovered_border: ThemedColor,
+
+    /// Text and icons of a hovered widget.
+    pub hovered_fg: ThemedColor,
+
+    /// Background of a widget that is being clicked or dragged.
+    pub active_bg: ThemedColor,
+
+    /// Outline, text and icons of a widget that is being clicked or dragged.
+    pub active_fg: ThemedColor,
+
+    /// Background of an open widget, e.g. a combo box with its popup showing.
+    pub open_bg: ThemedColor,
+
+    /// Weak background of an open widget.
+    pub open_weak_bg: ThemedColor,
+
+    /// Outline of an open widget.
+    pub open_border: ThemedColor,
+
+    /// Text and icons of an open widget.
+    pub open_fg: ThemedColor,
+
+    /// Background of selected text and selected widgets.
+    pub selection_bg: ThemedColor,
+
+    /// Foreground of selected widgets, and the text cursor.
+    pub selection_fg: ThemedColor,
+
+    /// Hyperlinks.
+    pub hyperlink: ThemedColor,
+
+    /// Something went well.
+    pub success: ThemedColor,
+
+    /// Warnings.
+    pub warn: ThemedColor,
+
+    /// Errors.
+    pub error: ThemedColor,
+
+    /// Window and popup shadows.
+    pub shadow: ThemedColor,
+
+    /// Tokens defined by the app, looked up after the built-in ones.
+    pub custom: BTreeMap<String, ThemedColor>,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl Palette {
+    /// The palette of egui's default dark and light themes.
+    pub const DEFAULT: Self = Self {
+        panel_fill: ThemedColor::gray(27, 248),
+        window_fill: ThemedColor::gray(27, 248),
+        border: ThemedColor::gray(60, 190),
+        extreme_bg: ThemedColor::gray(10, 255),
+        faint_bg: ThemedColor::same(Color32::from_additive_luminance(5)), // visible, but barely so
+        code_bg: ThemedColor::gray(64, 230),
+        text: ThemedColor::gray(140, 80),
+        widget_bg: ThemedColor::gray(60, 230),
+        widget_fg: ThemedColor::gray(180, 60),
+        hovered_bg: ThemedColor::gray(70, 220),
+        hovered_border: ThemedColor::gray(150, 105),
+        hovered_fg: ThemedColor::new(Color32::from_gray(240), Color32::BLACK),
+        active_bg: ThemedColor::gray(55, 165),
+        active_fg: ThemedColor::new(Color32::WHITE, Color32::BLACK),
+        open_bg: ThemedColor::gray(27, 220),
+        open_weak_bg: ThemedColor::gray(45, 220),
+        open_border: ThemedColor::gray(60, 160),
+        open_fg: ThemedColor::new(Color32::from_gray(210), Color32::BLACK),
+        selection_bg: ThemedColor::new(
+            Color32::from_rgb(0, 92, 128),
+            Color32::from_rgb(144, 209, 255),
+        ),
+        selection_fg: ThemedColor::new(
+            Color32::from_rgb(192, 222, 255),
+            Color32::from_rgb(0, 83, 125),
+        ),
+        hyperlink: ThemedColor::new(
+            Color32::from_rgb(90, 170, 255),
+            Color32::from_rgb(0, 155, 255),
+        ),
+        success: ThemedColor::new(
+            Color32::from_rgb(90, 200, 110),
+            Color32::from_rgb(0, 135, 40),
+        ),
+        // It's difficult to find a warning color that pops on bright background,
+        // so use a slightly orange red in light mode.
+        warn: ThemedColor::new(
+            Color32::from_rgb(255, 143, 0),
+            Color32::from_rgb(255, 100, 0),
+        ),
+        error: ThemedColor::same(Color32::from_rgb(255, 0, 0)),
+        shadow: ThemedColor::new(Color32::from_black_alpha(96), Color32::from_black_alpha(25)),
+        custom: BTreeMap::new(),
+    };
+
+    /// A palette where all text meets the WCAG AAA contrast requirement (7:1)
+    /// against the backgrounds it is normally painted on.
+    ///
+    /// Used by [`crate::Visuals::high_contrast_dark`] and [`crate::Visuals::high_contrast_light`].
+    pub const HIGH_CONTRAST: Self = Self {
+        panel_fill: ThemedColor::gray(0, 255),
+        window_fill: ThemedColor::gray(0, 255),
+        border: ThemedColor::gray(200, 60),
+        extreme_bg: ThemedColor::gray(0, 255),
+        faint_bg: ThemedColor::gray(20, 240),
+        code_bg: ThemedColor::gray(40, 225),
+        text: ThemedColor::gray(255, 0),
+        widget_bg: ThemedColor::gray(30, 225),
+        widget_fg: ThemedColor::gray(255, 0),
+        hovered_bg: ThemedColor::gray(60, 205),
+        hovered_border: ThemedColor::gray(255, 0),
+        hovered_fg: ThemedColor::gray(255, 0),
+        active_bg: ThemedColor::gray(80, 180),
+        active_fg: ThemedColor::gray(255, 0),
+        open_bg: ThemedColor::gray(30, 225),
+        open_weak_bg: ThemedColor::gray(30, 225),
+        open_border: ThemedColor::gray(200, 60),
+        open_fg: ThemedColor::gray(255, 0),
+        selection_bg: ThemedColor::new(
+            Color32::from_rgb(0, 70, 140),
+            Color32::from_rgb(170, 215, 255),
+        ),
+        selection_fg: ThemedColor::new(
+            Color32::from_rgb(255, 255, 0),
+            Color32::from_rgb(0, 40, 100),
+        ),
+        hyperlink: ThemedColor::new(
+            Color32::from_rgb(130, 200, 255),
+            Color32::from_rgb(0, 60, 170),
+        ),
+        success: ThemedColor::new(
+            Color32::from_rgb(100, 230, 120),
+            Color32::from_rgb(0, 100, 30),
+        ),
+        warn: ThemedColor::new(
+            Color32::from_rgb(255, 190, 0),
+            Color32::from_rgb(150, 60, 0),
+        ),
+        error: ThemedColor::new(
+            Color32::from_rgb(255, 120, 120),
+            Color32::from_rgb(170, 0, 0),
+        ),
+        shadow: ThemedColor::new(
+            Color32::from_black_alpha(160),
+            Color32::from_black_alpha(60),
+        ),
+        custom: BTreeMap::new(),
+    };
+
+    /// The built-in tokens, by name.
+    pub fn builtin_tokens(&self) -> [(&'static str, ThemedColor); 25] {
+        [
+            ("panel_fill", self.panel_fill),
+            ("window_fill", self.window_fill),
+            ("border", self.border),
+            ("extreme_bg", self.extreme_bg),
+            ("faint_bg", self.faint_bg),
+            ("code_bg", self.code_bg),
+            ("text", self.text),
+            ("widget_bg", self.widget_bg),
+            ("widget_fg", self.widget_fg),
+            ("hovered_bg", self.hovered_bg),
+            ("hovered_border", self.hovered_border),
+            ("hovered_fg", self.hovered_fg),
+            ("active_bg", self.active_bg),
+            ("active_fg", self.active_fg),
+            ("open_bg", self.open_bg),
+            ("open_weak_bg", self.open_weak_bg),
+            ("open_border", self.open_border),
+            ("open_fg", self.open_fg),
+            ("selection_bg", self.selection_bg),
+            ("selection_fg", self.selection_fg),
+            ("hyperlink", self.hyperlink),
+            ("success", self.success),
+            ("warn", self.warn),
+            ("error", self.error),
+            ("shadow", self.shadow),
+        ]
+    }
+
+    fn builtin_tokens_mut(&mut self) -> [(&'static str, &mut ThemedColor); 25] {
+        [
+            ("panel_fill", &mut self.panel_fill),
+            ("window_fill", &mut self.window_fill),
+            ("border", &mut self.border),
+            ("extreme_bg", &mut self.extreme_bg),
+            ("faint_bg", &mut self.faint_bg),
+            ("code_bg", &mut self.code_bg),
+            ("text", &mut self.text),
+            ("widget_bg", &mut self.widget_bg),
+            ("widget_fg", &mut self.widget_fg),
+            ("hovered_bg", &mut self.hovered_bg),
+            ("hovered_border", &mut self.hovered_border),
+            ("hovered_fg", &mut self.hovered_fg),
+            ("active_bg", &mut self.active_bg),
+            ("active_fg", &mut self.active_fg),
+            ("open_bg", &mut self.open_bg),
+            ("open_weak_bg", &mut self.open_weak_bg),
+            ("open_border", &mut self.open_border),
+            ("open_fg", &mut self.open_fg),
+            ("selection_bg", &mut self.selection_bg),
+            ("selection_fg", &mut self.selection_fg),
+            ("hyperlink", &mut self.hyperlink),
+            ("success", &mut self.success),
+            ("warn", &mut self.warn),
+            ("error", &mut self.error),
+            ("shadow", &mut self.shadow),
+        ]
+    }
+
+    /// Look up a token by name, first among the built-in ones, then among [`Self::custom`].
+    pub fn get(&self, name: &str) -> Option<ThemedColor> {
+        self.builtin_tokens()
+            .into_iter()
+            .find_map(|(token, color)| (token == name).then_some(color))
+            .or_else(|| self.custom.get(name).copied())
+    }
+
+    /// Set a token by name.
+    ///
+    /// If `name` is not one of the built-in tokens, it is added to [`Self::custom`].
+    pub fn set(&mut self, name: impl Into<String>, color: impl Into<ThemedColor>) {
+        let name = name.into();
+        let color = color.into();
+        if let Some((_, token)) = self
+            .builtin_tokens_mut()
+            .into_iter()
+            .find(|(token, _)| *token == name)
+        {
+            *token = color;
+        } else {
+            self.custom.insert(name, color);
+        }
+    }
+
+    /// Builder version of [`Self::set`].
+    #[inline]
+    pub fn with(mut self, name: impl Into<String>, color: impl Into<ThemedColor>) -> Self {
+        self.set(name, color);
+        self
+    }
+
+    /// Edit the tokens, showing the color for `theme`.
+    pub fn ui(&mut self, ui: &mut crate::Ui, theme: Theme) {
+        fn token_ui(ui: &mut crate::Ui, theme: Theme, name: &str, color: &mut ThemedColor) {
+            ui.label(name);
+            let color = match theme {
+                Theme::Dark => &mut color.dark,
+                Theme::Light => &mut color.light,
+            };
+            ui.color_edit_button_srgba(color);
+            ui.end_row();
+        }
+
+        crate::Grid::new("palette").num_columns(2).show(ui, |ui| {
+            for (name, color) in self.builtin_tokens_mut() {
+                token_ui(ui, theme, name, color);
+            }
+            for (name, color) in &mut self.custom {
+                token_ui(ui, theme, name, color);
+            }
+        });
+    }
+}
+
+#[test]
+fn palette_tokens() {
+    let mut palette = Palette::default();
+    assert_eq!(palette.get("success"), Some(palette.success));
+    assert_eq!(palette.get("brand"), None);
+
+    let brand = ThemedColor::new(Color32::RED, Color32::BLUE);
+    palette.set("brand", brand);
+    palette.set("hyperlink", brand);
+    assert_eq!(palette.get("brand"), Some(brand));
+    assert_eq!(palette.hyperlink, brand);
+    assert_eq!(palette.custom.len(), 1);
+}
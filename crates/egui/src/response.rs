@@ -484,6 +484,61 @@ impl Response {
         }
     }
 
+    /// Return the images that were pasted (e.g. with Cmd+V) into this widget this frame.
+    ///
+    /// Only returns something if [`Self::has_focus`] is true,
+    /// so this can be used to implement a drop zone that accepts pasted screenshots:
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// let response = ui.allocate_response(egui::vec2(200.0, 100.0), egui::Sense::click());
+    /// if response.clicked() {
+    ///     response.request_focus();
+    /// }
+    /// let pasted_images = response.pasted_images();
+    /// if let Some(image) = pasted_images.last() {
+    ///     ui.label(format!("Pasted a {}x{} image", image.width(), image.height()));
+    /// }
+    /// # });
+    /// ```
+    ///
+    /// See also [`Self::pasted_files`].
+    pub fn pasted_images(&self) -> Vec<crate::ColorImage> {
+        if !self.has_focus() {
+            return vec![];
+        }
+        self.ctx.input(|i| {
+            i.events
+                .iter()
+                .filter_map(|event| match event {
+                    crate::Event::PasteImage(image) => Some(image.clone()),
+                    _ => None,
+                })
+                .collect()
+        })
+    }
+
+    /// Return the files that were pasted (e.g. with Cmd+V) into this widget this frame.
+    ///
+    /// Only returns something if [`Self::has_focus`] is true.
+    ///
+    /// See also [`Self::pasted_images`].
+    pub fn pasted_files(&self) -> Vec<std::path::PathBuf> {
+        if !self.has_focus() {
+            return vec![];
+        }
+        self.ctx.input(|i| {
+            i.events
+                .iter()
+                .filter_map(|event| match event {
+                    crate::Event::PasteFiles(files) => Some(files.iter().cloned()),
+                    _ => None,
+                })
+                .flatten()
+                .collect()
+        })
+    }
+
     /// Where the pointer (mouse/touch) were when this widget was clicked or dragged.
     ///
     /// `None` if the widget is not being interacted with.
@@ -816,12 +871,13 @@ impl Response {
     }
 
     pub fn output_event(&self, event: crate::output::OutputEvent) {
-        self.ctx.accesskit_node_builder(self.id, |builder| {
-            self.fill_accesskit_node_from_widget_info(builder, event.widget_info().clone());
-        });
+        if let Some(info) = event.widget_info() {
+            self.ctx.accesskit_node_builder(self.id, |builder| {
+                self.fill_accesskit_node_from_widget_info(builder, info.clone());
+            });
 
-        self.ctx
-            .register_widget_info(self.id, || event.widget_info().clone());
+            self.ctx.register_widget_info(self.id, || info.clone());
+        }
 
         self.ctx.output_mut(|o| o.events.push(event));
     }
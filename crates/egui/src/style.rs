@@ -7,8 +7,8 @@ use epaint::{AlphaFromCoverage, CornerRadius, Shadow, Stroke, TextOptions, text:
 use std::{collections::BTreeMap, ops::RangeInclusive, sync::Arc};
 
 use crate::{
-    ComboBox, CursorIcon, FontFamily, FontId, Grid, Margin, Response, RichText, TextWrapMode,
-    WidgetText,
+    ComboBox, CursorIcon, FontFamily, FontId, Grid, Margin, Palette, Response, RichText,
+    TextWrapMode, Theme, ThemedColor, WidgetText,
     ecolor::Color32,
     emath::{Rangef, Rect, Vec2, pos2, vec2},
     reset_button_with,
@@ -314,6 +314,18 @@ pub struct Style {
     /// * `Some(mode)`: use the specified mode as default
     pub wrap_mode: Option<crate::TextWrapMode>,
 
+    /// How labels, buttons, etc. rasterize their text.
+    ///
+    /// Use [`crate::text::GlyphRendering::DistanceField`] for text that is zoomed in,
+    /// e.g. inside of a [`crate::Scene`], so that it stays sharp.
+    /// This only affects text that isn't already laid out (e.g. not a [`crate::WidgetText::LayoutJob`]).
+    ///
+    /// If the backend doesn't support it (see [`crate::Context::set_distance_field_supported`]),
+    /// [`crate::text::GlyphRendering::Bitmap`] is used instead.
+    ///
+    /// Default: [`crate::text::GlyphRendering::Bitmap`].
+    pub glyph_rendering: crate::text::GlyphRendering,
+
     /// Sizes and distances between widgets
     pub spacing: Spacing,
 
@@ -1072,6 +1084,15 @@ pub struct Visuals {
 
     /// How much to modify the alpha of a disabled widget.
     pub disabled_alpha: f32,
+
+    /// The semantic colors these visuals were derived from.
+    ///
+    /// Used by [`Self::token`].
+    ///
+    /// The other color fields (e.g. [`Self::panel_fill`]) are what egui paints with.
+    /// Changing this field directly does not change them, so they can drift apart.
+    /// Use [`Self::set_palette`] to change the palette and re-derive the colors from it.
+    pub palette: Palette,
 }
 
 impl Visuals {
@@ -1140,6 +1161,69 @@ impl Visuals {
     pub fn gray_out(&self, color: Color32) -> Color32 {
         crate::ecolor::tint_color_towards(color, self.widgets.noninteractive.weak_bg_fill)
     }
+
+    /// Is this a dark or a light theme?
+    #[inline]
+    pub fn theme(&self) -> Theme {
+        Theme::from_dark_mode(self.dark_mode)
+    }
+
+    /// Pick the dark or light version of the color, depending on [`Self::dark_mode`].
+    #[inline]
+    pub fn resolve(&self, color: impl Into<ThemedColor>) -> Color32 {
+        color.into().get(self.theme())
+    }
+
+    /// Look up a named color in the [`Palette`], e.g. `"success"` or `"hyperlink"`,
+    /// resolved for the current theme.
+    ///
+    /// Returns `None` if there is no such token.
+    pub fn token(&self, name: &str) -> Option<Color32> {
+        self.palette.get(name).map(|color| self.resolve(color))
+    }
+
+    /// Set the [`Self::palette`], and re-derive all colors from it,
+    /// the same way as [`Self::from_palette`] does.
+    ///
+    /// Unlike [`Self::from_palette`], this keeps all non-color settings
+    /// (corner radii, stroke widths, shadow sizes, …).
+    /// Any color you have changed by hand is overwritten by the one from the palette.
+    pub fn set_palette(&mut self, palette: Palette) {
+        let derived = Self::from_palette(self.theme(), palette);
+
+        for (widget, derived) in [
+            (
+                &mut self.widgets.noninteractive,
+                &derived.widgets.noninteractive,
+            ),
+            (&mut self.widgets.inactive, &derived.widgets.inactive),
+            (&mut self.widgets.hovered, &derived.widgets.hovered),
+            (&mut self.widgets.active, &derived.widgets.active),
+            (&mut self.widgets.open, &derived.widgets.open),
+        ] {
+            widget.bg_fill = derived.bg_fill;
+            widget.weak_bg_fill = derived.weak_bg_fill;
+            widget.bg_stroke.color = derived.bg_stroke.color;
+            widget.fg_stroke.color = derived.fg_stroke.color;
+        }
+        self.selection.bg_fill = derived.selection.bg_fill;
+        self.selection.stroke.color = derived.selection.stroke.color;
+
+        self.hyperlink_color = derived.hyperlink_color;
+        self.faint_bg_color = derived.faint_bg_color;
+        self.extreme_bg_color = derived.extreme_bg_color;
+        self.code_bg_color = derived.code_bg_color;
+        self.warn_fg_color = derived.warn_fg_color;
+        self.error_fg_color = derived.error_fg_color;
+        self.window_shadow.color = derived.window_shadow.color;
+        self.window_fill = derived.window_fill;
+        self.window_stroke.color = derived.window_stroke.color;
+        self.panel_fill = derived.panel_fill;
+        self.popup_shadow.color = derived.popup_shadow.color;
+        self.text_cursor.stroke.color = derived.text_cursor.stroke.color;
+
+        self.palette = derived.palette;
+    }
 }
 
 /// Selected text, selected elements etc
@@ -1300,6 +1384,11 @@ pub struct DebugOptions {
     ///
     /// See [`emath::GuiRounding`] for more.
     pub show_unaligned: bool,
+
+    /// If set, outline all text that doesn't meet this WCAG contrast level.
+    ///
+    /// See [`crate::contrast`] for more.
+    pub show_contrast_issues: Option<crate::contrast::WcagLevel>,
 }
 
 #[cfg(debug_assertions)]
@@ -1316,6 +1405,7 @@ impl Default for DebugOptions {
             show_interactive_widgets: false,
             show_widget_hits: false,
             show_unaligned: cfg!(debug_assertions),
+            show_contrast_issues: None,
         }
     }
 }
@@ -1348,6 +1438,7 @@ impl Default for Style {
             number_formatter: NumberFormatter(Arc::new(emath::format_with_decimals_in_range)),
             wrap: None,
             wrap_mode: None,
+            glyph_rendering: Default::default(),
             spacing: Spacing::default(),
             interaction: Interaction::default(),
             visuals: Visuals::default(),
@@ -1408,50 +1499,98 @@ impl Default for Interaction {
 impl Visuals {
     /// Default dark theme.
     pub fn dark() -> Self {
+        Self::from_palette(Theme::Dark, Palette::default())
+    }
+
+    /// Default light theme.
+    pub fn light() -> Self {
+        Self::from_palette(Theme::Light, Palette::default())
+    }
+
+    /// Dark theme where all text meets the WCAG AAA contrast requirement.
+    ///
+    /// See also [`crate::contrast`] for checking the contrast of your own colors.
+    pub fn high_contrast_dark() -> Self {
+        Self::high_contrast(Theme::Dark)
+    }
+
+    /// Light theme where all text meets the WCAG AAA contrast requirement.
+    ///
+    /// See also [`crate::contrast`] for checking the contrast of your own colors.
+    pub fn high_contrast_light() -> Self {
+        Self::high_contrast(Theme::Light)
+    }
+
+    fn high_contrast(theme: Theme) -> Self {
+        let palette = Palette::HIGH_CONTRAST;
+        let border = palette.border.get(theme);
+        let mut visuals = Self::from_palette(theme, palette);
+        visuals.weak_text_alpha = 0.8;
+        visuals.disabled_alpha = 0.7;
+        // Outline buttons too, so they can be told apart from the background
+        // without relying on the fill color.
+        visuals.widgets.inactive.bg_stroke = Stroke::new(1.0, border);
+        visuals.window_stroke = Stroke::new(2.0, border);
+        visuals.text_cursor.stroke.width = 3.0;
+        visuals
+    }
+
+    /// The default visuals for the given theme, with all colors taken from `palette`.
+    ///
+    /// Use this to re-brand egui by changing a few colors of [`Palette::default`].
+    pub fn from_palette(theme: Theme, palette: Palette) -> Self {
+        let color = |token: ThemedColor| token.get(theme);
+
         Self {
-            dark_mode: true,
+            dark_mode: theme == Theme::Dark,
             text_options: TextOptions {
-                alpha_from_coverage: AlphaFromCoverage::DARK_MODE_DEFAULT,
+                alpha_from_coverage: match theme {
+                    Theme::Dark => AlphaFromCoverage::DARK_MODE_DEFAULT,
+                    Theme::Light => AlphaFromCoverage::LIGHT_MODE_DEFAULT,
+                },
                 ..Default::default()
             },
             override_text_color: None,
             weak_text_alpha: 0.6,
             weak_text_color: None,
-            widgets: Widgets::default(),
-            selection: Selection::default(),
-            hyperlink_color: Color32::from_rgb(90, 170, 255),
-            faint_bg_color: Color32::from_additive_luminance(5), // visible, but barely so
-            extreme_bg_color: Color32::from_gray(10),            // e.g. TextEdit background
-            text_edit_bg_color: None, // use `extreme_bg_color` by default
-            code_bg_color: Color32::from_gray(64),
-            warn_fg_color: Color32::from_rgb(255, 143, 0), // orange
-            error_fg_color: Color32::from_rgb(255, 0, 0),  // red
+            widgets: Widgets::from_palette(theme, &palette),
+            selection: Selection::from_palette(theme, &palette),
+            hyperlink_color: color(palette.hyperlink),
+            faint_bg_color: color(palette.faint_bg),
+            extreme_bg_color: color(palette.extreme_bg), // e.g. TextEdit background
+            text_edit_bg_color: None,                    // use `extreme_bg_color` by default
+            code_bg_color: color(palette.code_bg),
+            warn_fg_color: color(palette.warn),
+            error_fg_color: color(palette.error),
 
             window_corner_radius: CornerRadius::same(6),
             window_shadow: Shadow {
                 offset: [10, 20],
                 blur: 15,
                 spread: 0,
-                color: Color32::from_black_alpha(96),
+                color: color(palette.shadow),
             },
-            window_fill: Color32::from_gray(27),
-            window_stroke: Stroke::new(1.0, Color32::from_gray(60)),
+            window_fill: color(palette.window_fill),
+            window_stroke: Stroke::new(1.0, color(palette.border)),
             window_highlight_topmost: true,
 
             menu_corner_radius: CornerRadius::same(6),
 
-            panel_fill: Color32::from_gray(27),
+            panel_fill: color(palette.panel_fill),
 
             popup_shadow: Shadow {
                 offset: [6, 10],
                 blur: 8,
                 spread: 0,
-                color: Color32::from_black_alpha(96),
+                color: color(palette.shadow),
             },
 
             resize_corner_size: 12.0,
 
-            text_cursor: Default::default(),
+            text_cursor: TextCursorStyle {
+                stroke: Stroke::new(2.0, color(palette.selection_fg)),
+                ..Default::default()
+            },
 
             clip_rect_margin: 3.0, // should be at least half the size of the widest frame stroke + max WidgetVisuals::expansion
             button_frame: true,
@@ -1469,50 +1608,8 @@ impl Visuals {
 
             numeric_color_space: NumericColorSpace::GammaByte,
             disabled_alpha: 0.5,
-        }
-    }
-
-    /// Default light theme.
-    pub fn light() -> Self {
-        Self {
-            dark_mode: false,
-            text_options: TextOptions {
-                alpha_from_coverage: AlphaFromCoverage::LIGHT_MODE_DEFAULT,
-                ..Default::default()
-            },
-            widgets: Widgets::light(),
-            selection: Selection::light(),
-            hyperlink_color: Color32::from_rgb(0, 155, 255),
-            faint_bg_color: Color32::from_additive_luminance(5), // visible, but barely so
-            extreme_bg_color: Color32::from_gray(255),           // e.g. TextEdit background
-            code_bg_color: Color32::from_gray(230),
-            warn_fg_color: Color32::from_rgb(255, 100, 0), // slightly orange red. it's difficult to find a warning color that pops on bright background.
-            error_fg_color: Color32::from_rgb(255, 0, 0),  // red
-
-            window_shadow: Shadow {
-                offset: [10, 20],
-                blur: 15,
-                spread: 0,
-                color: Color32::from_black_alpha(25),
-            },
-            window_fill: Color32::from_gray(248),
-            window_stroke: Stroke::new(1.0, Color32::from_gray(190)),
-
-            panel_fill: Color32::from_gray(248),
-
-            popup_shadow: Shadow {
-                offset: [6, 10],
-                blur: 8,
-                spread: 0,
-                color: Color32::from_black_alpha(25),
-            },
-
-            text_cursor: TextCursorStyle {
-                stroke: Stroke::new(2.0, Color32::from_rgb(0, 83, 125)),
-                ..Default::default()
-            },
 
-            ..Self::dark()
+            palette,
         }
     }
 }
@@ -1524,18 +1621,15 @@ impl Default for Visuals {
 }
 
 impl Selection {
-    fn dark() -> Self {
+    fn from_palette(theme: Theme, palette: &Palette) -> Self {
         Self {
-            bg_fill: Color32::from_rgb(0, 92, 128),
-            stroke: Stroke::new(1.0, Color32::from_rgb(192, 222, 255)),
+            bg_fill: palette.selection_bg.get(theme),
+            stroke: Stroke::new(1.0, palette.selection_fg.get(theme)),
         }
     }
 
-    fn light() -> Self {
-        Self {
-            bg_fill: Color32::from_rgb(144, 209, 255),
-            stroke: Stroke::new(1.0, Color32::from_rgb(0, 83, 125)),
-        }
+    fn dark() -> Self {
+        Self::from_palette(Theme::Dark, &Palette::DEFAULT)
     }
 }
 
@@ -1547,89 +1641,55 @@ impl Default for Selection {
 
 impl Widgets {
     pub fn dark() -> Self {
-        Self {
-            noninteractive: WidgetVisuals {
-                weak_bg_fill: Color32::from_gray(27),
-                bg_fill: Color32::from_gray(27),
-                bg_stroke: Stroke::new(1.0, Color32::from_gray(60)), // separators, indentation lines
-                fg_stroke: Stroke::new(1.0, Color32::from_gray(140)), // normal text color
-                corner_radius: CornerRadius::same(2),
-                expansion: 0.0,
-            },
-            inactive: WidgetVisuals {
-                weak_bg_fill: Color32::from_gray(60), // button background
-                bg_fill: Color32::from_gray(60),      // checkbox background
-                bg_stroke: Default::default(),
-                fg_stroke: Stroke::new(1.0, Color32::from_gray(180)), // button text
-                corner_radius: CornerRadius::same(2),
-                expansion: 0.0,
-            },
-            hovered: WidgetVisuals {
-                weak_bg_fill: Color32::from_gray(70),
-                bg_fill: Color32::from_gray(70),
-                bg_stroke: Stroke::new(1.0, Color32::from_gray(150)), // e.g. hover over window edge or button
-                fg_stroke: Stroke::new(1.5, Color32::from_gray(240)),
-                corner_radius: CornerRadius::same(3),
-                expansion: 1.0,
-            },
-            active: WidgetVisuals {
-                weak_bg_fill: Color32::from_gray(55),
-                bg_fill: Color32::from_gray(55),
-                bg_stroke: Stroke::new(1.0, Color32::WHITE),
-                fg_stroke: Stroke::new(2.0, Color32::WHITE),
-                corner_radius: CornerRadius::same(2),
-                expansion: 1.0,
-            },
-            open: WidgetVisuals {
-                weak_bg_fill: Color32::from_gray(45),
-                bg_fill: Color32::from_gray(27),
-                bg_stroke: Stroke::new(1.0, Color32::from_gray(60)),
-                fg_stroke: Stroke::new(1.0, Color32::from_gray(210)),
-                corner_radius: CornerRadius::same(2),
-                expansion: 0.0,
-            },
-        }
+        Self::from_palette(Theme::Dark, &Palette::DEFAULT)
     }
 
     pub fn light() -> Self {
+        Self::from_palette(Theme::Light, &Palette::DEFAULT)
+    }
+
+    /// The default widget visuals for the given theme, with all colors taken from `palette`.
+    pub fn from_palette(theme: Theme, palette: &Palette) -> Self {
+        let color = |token: ThemedColor| token.get(theme);
+
         Self {
             noninteractive: WidgetVisuals {
-                weak_bg_fill: Color32::from_gray(248),
-                bg_fill: Color32::from_gray(248),
-                bg_stroke: Stroke::new(1.0, Color32::from_gray(190)), // separators, indentation lines
-                fg_stroke: Stroke::new(1.0, Color32::from_gray(80)),  // normal text color
+                weak_bg_fill: color(palette.panel_fill),
+                bg_fill: color(palette.panel_fill),
+                bg_stroke: Stroke::new(1.0, color(palette.border)), // separators, indentation lines
+                fg_stroke: Stroke::new(1.0, color(palette.text)),   // normal text color
                 corner_radius: CornerRadius::same(2),
                 expansion: 0.0,
             },
             inactive: WidgetVisuals {
-                weak_bg_fill: Color32::from_gray(230), // button background
-                bg_fill: Color32::from_gray(230),      // checkbox background
+                weak_bg_fill: color(palette.widget_bg), // button background
+                bg_fill: color(palette.widget_bg),      // checkbox background
                 bg_stroke: Default::default(),
-                fg_stroke: Stroke::new(1.0, Color32::from_gray(60)), // button text
+                fg_stroke: Stroke::new(1.0, color(palette.widget_fg)), // button text
                 corner_radius: CornerRadius::same(2),
                 expansion: 0.0,
             },
             hovered: WidgetVisuals {
-                weak_bg_fill: Color32::from_gray(220),
-                bg_fill: Color32::from_gray(220),
-                bg_stroke: Stroke::new(1.0, Color32::from_gray(105)), // e.g. hover over window edge or button
-                fg_stroke: Stroke::new(1.5, Color32::BLACK),
+                weak_bg_fill: color(palette.hovered_bg),
+                bg_fill: color(palette.hovered_bg),
+                bg_stroke: Stroke::new(1.0, color(palette.hovered_border)), // e.g. hover over window edge or button
+                fg_stroke: Stroke::new(1.5, color(palette.hovered_fg)),
                 corner_radius: CornerRadius::same(3),
                 expansion: 1.0,
             },
             active: WidgetVisuals {
-                weak_bg_fill: Color32::from_gray(165),
-                bg_fill: Color32::from_gray(165),
-                bg_stroke: Stroke::new(1.0, Color32::BLACK),
-                fg_stroke: Stroke::new(2.0, Color32::BLACK),
+                weak_bg_fill: color(palette.active_bg),
+                bg_fill: color(palette.active_bg),
+                bg_stroke: Stroke::new(1.0, color(palette.active_fg)),
+                fg_stroke: Stroke::new(2.0, color(palette.active_fg)),
                 corner_radius: CornerRadius::same(2),
                 expansion: 1.0,
             },
             open: WidgetVisuals {
-                weak_bg_fill: Color32::from_gray(220),
-                bg_fill: Color32::from_gray(220),
-                bg_stroke: Stroke::new(1.0, Color32::from_gray(160)),
-                fg_stroke: Stroke::new(1.0, Color32::BLACK),
+                weak_bg_fill: color(palette.open_weak_bg),
+                bg_fill: color(palette.open_bg),
+                bg_stroke: Stroke::new(1.0, color(palette.open_border)),
+                fg_stroke: Stroke::new(1.0, color(palette.open_fg)),
                 corner_radius: CornerRadius::same(2),
                 expansion: 0.0,
             },
@@ -1662,6 +1722,7 @@ impl Style {
             number_formatter: _, // can't change callbacks in the UI
             wrap: _,
             wrap_mode,
+            glyph_rendering,
             spacing,
             interaction,
             visuals,
@@ -1759,6 +1820,19 @@ impl Style {
                 });
             ui.end_row();
 
+            ui.label("Glyph rendering");
+            ui.horizontal(|ui| {
+                use crate::text::GlyphRendering;
+                ui.radio_value(glyph_rendering, GlyphRendering::Bitmap, "Bitmap");
+                ui.radio_value(
+                    glyph_rendering,
+                    GlyphRendering::DistanceField,
+                    "Distance field",
+                )
+                .on_hover_text("Sharp at any zoom level, but requires support from the renderer");
+            });
+            ui.end_row();
+
             ui.label("Animation duration");
             ui.add(
                 DragValue::new(animation_time)
@@ -1792,6 +1866,21 @@ impl Style {
                 "If scrolling is enabled for only one direction, allow horizontal scrolling without pressing shift",
             );
 
+        #[cfg(feature = "theme_file")]
+        ui.vertical_centered(|ui| {
+            if ui
+                .button("📋 Export as theme")
+                .on_hover_text("Copy the changes from the built-in theme to the clipboard, as a RON theme file")
+                .clicked()
+            {
+                let base = crate::Theme::from_dark_mode(self.visuals.dark_mode);
+                match crate::ThemeFile::from_style(base, self) {
+                    Ok(theme) => ui.ctx().copy_text(theme.to_ron()),
+                    Err(err) => log::warn!("Failed to export theme: {err}"),
+                }
+            }
+        });
+
         ui.vertical_centered(|ui| reset_button(ui, self, "Reset style"));
     }
 }
@@ -2114,6 +2203,8 @@ impl WidgetVisuals {
 
 impl Visuals {
     pub fn ui(&mut self, ui: &mut crate::Ui) {
+        let palette_before = self.palette.clone();
+
         let Self {
             dark_mode,
             text_options,
@@ -2161,6 +2252,7 @@ impl Visuals {
 
             numeric_color_space,
             disabled_alpha,
+            palette,
         } = self;
 
         fn ui_optional_color(
@@ -2272,11 +2364,29 @@ impl Visuals {
                 max_texture_side: _,
                 alpha_from_coverage,
                 font_hinting,
+                language,
             } = text_options;
 
             text_alpha_from_coverage_ui(ui, alpha_from_coverage);
 
             ui.checkbox(font_hinting, "Enable font hinting");
+
+            ui.horizontal(|ui| {
+                ui.label("Language");
+                let mut tag = language
+                    .map(|language| language.to_string())
+                    .unwrap_or_default();
+                let response = ui.add(
+                    crate::TextEdit::singleline(&mut tag)
+                        .hint_text("e.g. ja or zh-Hant")
+                        .desired_width(80.0),
+                );
+                if response.changed() {
+                    *language = (!tag.is_empty()).then(|| tag.as_str().into());
+                }
+            })
+            .response
+            .on_hover_text("Decides which language-specific fallback fonts are used");
         });
 
         ui.collapsing("Text cursor", |ui| {
@@ -2328,6 +2438,15 @@ impl Visuals {
         ui.collapsing("Widgets", |ui| widgets.ui(ui));
         ui.collapsing("Selection", |ui| selection.ui(ui));
 
+        let mut rebuild_from_palette = false;
+        ui.collapsing("Palette", |ui| {
+            palette.ui(ui, Theme::from_dark_mode(*dark_mode));
+            rebuild_from_palette = ui
+                .button("Rebuild visuals from palette")
+                .on_hover_text("Reset all visuals to the defaults, with the colors of the palette")
+                .clicked();
+        });
+
         ui.collapsing("Misc", |ui| {
             ui.add(Slider::new(resize_corner_size, 0.0..=20.0).text("resize_corner_size"));
             ui.add(Slider::new(clip_rect_margin, 0.0..=20.0).text("clip_rect_margin"));
@@ -2372,6 +2491,12 @@ impl Visuals {
         });
 
         let dark_mode = *dark_mode;
+        if rebuild_from_palette {
+            *self = Self::from_palette(Theme::from_dark_mode(dark_mode), self.palette.clone());
+        } else if self.palette != palette_before {
+            self.set_palette(self.palette.clone());
+        }
+
         ui.vertical_centered(|ui| {
             reset_button_with(
                 ui,
@@ -2477,6 +2602,7 @@ impl DebugOptions {
             show_interactive_widgets,
             show_widget_hits,
             show_unaligned,
+            show_contrast_issues,
         } = self;
 
         {
@@ -2511,6 +2637,14 @@ impl DebugOptions {
             "Show rectangles not aligned to integer point coordinates",
         );
 
+        ui.horizontal(|ui| {
+            use crate::contrast::WcagLevel;
+            ui.label("Outline text with too little contrast:");
+            ui.selectable_value(show_contrast_issues, None, "Off");
+            ui.selectable_value(show_contrast_issues, Some(WcagLevel::AA), "AA");
+            ui.selectable_value(show_contrast_issues, Some(WcagLevel::AAA), "AAA");
+        });
+
         ui.vertical_centered(|ui| reset_button(ui, self, "Reset debug options"));
     }
 }
@@ -2820,7 +2954,7 @@ impl Widget for &mut crate::Frame {
 
 impl Widget for &mut FontTweak {
     fn ui(self, ui: &mut Ui) -> Response {
-        let original: FontTweak = *self;
+        let original: FontTweak = self.clone();
 
         let mut response = Grid::new("font_tweak")
             .num_columns(2)
@@ -2830,6 +2964,7 @@ impl Widget for &mut FontTweak {
                     y_offset_factor,
                     y_offset,
                     hinting_override,
+                    font_features,
                 } = self;
 
                 ui.label("Scale");
@@ -2857,6 +2992,16 @@ impl Widget for &mut FontTweak {
                         ui.selectable_value(hinting_override, Some(true), "Enable");
                         ui.selectable_value(hinting_override, Some(false), "Disable");
                     });
+                ui.end_row();
+
+                ui.label("font_features");
+                let font_features = font_features
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                ui.label(font_features);
+                ui.end_row();
 
                 if ui.button("Reset").clicked() {
                     *self = Default::default();
@@ -2871,3 +3016,31 @@ impl Widget for &mut FontTweak {
         response
     }
 }
+
+#[test]
+fn visuals_set_palette() {
+    let brand = crate::ThemedColor::new(Color32::RED, Color32::BLUE);
+    let palette = Palette::default()
+        .with("hyperlink", brand)
+        .with("panel_fill", brand);
+
+    let mut visuals = Visuals::dark();
+    visuals.window_stroke.width = 3.0;
+    visuals.set_palette(palette.clone());
+
+    assert_eq!(visuals.hyperlink_color, Color32::RED);
+    assert_eq!(visuals.panel_fill, Color32::RED);
+    assert_eq!(visuals.widgets.noninteractive.bg_fill, Color32::RED);
+    assert_eq!(
+        visuals.window_stroke.width, 3.0,
+        "non-color settings are kept"
+    );
+    assert_eq!(visuals.palette, palette);
+
+    // The derived colors are the same as those of `from_palette`:
+    let derived = Visuals::from_palette(Theme::Light, palette);
+    let mut light = Visuals::light();
+    light.set_palette(derived.palette.clone());
+    assert_eq!(light, derived);
+    assert_eq!(light.hyperlink_color, Color32::BLUE);
+}
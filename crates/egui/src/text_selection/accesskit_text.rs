@@ -47,14 +47,19 @@ pub fn update_accesskit_for_text_widget(
 
         ctx.accesskit_node_builder(row_id, |builder| {
             builder.set_role(accesskit::Role::TextRun);
-            let rect = global_from_galley * row.rect_without_leading_space();
+            let rect =
+                global_from_galley * galley.rect_from_row_space(row.rect_without_leading_space());
             builder.set_bounds(accesskit::Rect {
                 x0: rect.min.x.into(),
                 y0: rect.min.y.into(),
                 x1: rect.max.x.into(),
                 y1: rect.max.y.into(),
             });
-            builder.set_text_direction(accesskit::TextDirection::LeftToRight);
+            builder.set_text_direction(if galley.is_vertical() {
+                accesskit::TextDirection::TopToBottom
+            } else {
+                accesskit::TextDirection::LeftToRight
+            });
             // TODO(mwcampbell): Set more node fields for the row
             // once AccessKit adapters expose text formatting info.
 
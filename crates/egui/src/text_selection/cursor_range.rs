@@ -251,6 +251,19 @@ fn move_single_cursor(
     key: Key,
     modifiers: &Modifiers,
 ) {
+    let key = if galley.is_vertical() {
+        // The rows are columns, going from right to left:
+        match key {
+            Key::ArrowUp => Key::ArrowLeft,
+            Key::ArrowDown => Key::ArrowRight,
+            Key::ArrowLeft => Key::ArrowDown,
+            Key::ArrowRight => Key::ArrowUp,
+            key => key,
+        }
+    } else {
+        key
+    };
+
     let (new_cursor, new_h_pos) =
         if os == OperatingSystem::Mac && modifiers.ctrl && !modifiers.shift {
             match key {
@@ -258,8 +271,8 @@ fn move_single_cursor(
                 Key::E => (galley.cursor_end_of_row(cursor), None),
                 Key::P => galley.cursor_up_one_row(cursor, *h_pos),
                 Key::N => galley.cursor_down_one_row(cursor, *h_pos),
-                Key::B => (galley.cursor_left_one_character(cursor), None),
-                Key::F => (galley.cursor_right_one_character(cursor), None),
+                Key::B => (galley.cursor_previous_character(cursor), None),
+                Key::F => (galley.cursor_next_character(cursor), None),
                 _ => return,
             }
         } else {
@@ -3,13 +3,16 @@ use std::sync::Arc;
 use emath::TSTransform;
 
 use crate::{
-    Context, CursorIcon, Event, Galley, Id, LayerId, Plugin, Pos2, Rect, Response, Ui,
-    layers::ShapeIdx, text::CCursor, text_selection::CCursorRange,
+    Color32, Context, CursorIcon, Event, FontFamily, Galley, Id, LayerId, Plugin, Pos2, Rect,
+    Response, RichClipboardText, Ui,
+    layers::ShapeIdx,
+    text::{CCursor, TextFormat},
+    text_selection::CCursorRange,
 };
 
 use super::{
     TextCursorState,
-    text_cursor_state::cursor_rect,
+    text_cursor_state::{byte_index_from_char_index, cursor_rect},
     visuals::{RowVertexIndices, paint_text_selection},
 };
 
@@ -98,6 +101,9 @@ pub struct LabelSelectionState {
 
     /// Accumulated text to copy.
     text_to_copy: String,
+
+    /// Accumulated text to copy, as HTML.
+    html_to_copy: String,
     last_copied_galley_rect: Option<Rect>,
 
     /// Painted selections this frame.
@@ -117,6 +123,7 @@ impl Default for LabelSelectionState {
             has_reached_primary: Default::default(),
             has_reached_secondary: Default::default(),
             text_to_copy: Default::default(),
+            html_to_copy: Default::default(),
             last_copied_galley_rect: Default::default(),
             painted_selections: Default::default(),
         }
@@ -141,6 +148,7 @@ impl Plugin for LabelSelectionState {
         self.has_reached_primary = false;
         self.has_reached_secondary = false;
         self.text_to_copy.clear();
+        self.html_to_copy.clear();
         self.last_copied_galley_rect = None;
         self.painted_selections.clear();
     }
@@ -203,8 +211,12 @@ impl Plugin for LabelSelectionState {
         }
 
         let text_to_copy = std::mem::take(&mut self.text_to_copy);
+        let html_to_copy = std::mem::take(&mut self.html_to_copy);
         if !text_to_copy.is_empty() {
-            ctx.copy_text(text_to_copy);
+            ctx.copy_rich_text(RichClipboardText {
+                plain: text_to_copy,
+                html: Some(html_to_copy),
+            });
         }
     }
 }
@@ -223,15 +235,18 @@ impl LabelSelectionState {
         if new_text.is_empty() {
             return;
         }
+        let new_html = selected_html(galley, cursor_range);
 
         if self.text_to_copy.is_empty() {
             self.text_to_copy = new_text;
+            self.html_to_copy = new_html;
             self.last_copied_galley_rect = Some(new_galley_rect);
             return;
         }
 
         let Some(last_copied_galley_rect) = self.last_copied_galley_rect else {
             self.text_to_copy = new_text;
+            self.html_to_copy = new_html;
             self.last_copied_galley_rect = Some(new_galley_rect);
             return;
         };
@@ -241,9 +256,11 @@ impl LabelSelectionState {
 
         if last_copied_galley_rect.bottom() <= new_galley_rect.top() {
             self.text_to_copy.push('\n');
+            self.html_to_copy += "<br>";
             let vertical_distance = new_galley_rect.top() - last_copied_galley_rect.bottom();
             if estimate_row_height(galley) * 0.5 < vertical_distance {
                 self.text_to_copy.push('\n');
+                self.html_to_copy += "<br>";
             }
         } else {
             let existing_ends_with_space =
@@ -257,10 +274,12 @@ impl LabelSelectionState {
             if existing_ends_with_space == Some(false) && !new_text_starts_with_space_or_punctuation
             {
                 self.text_to_copy.push(' ');
+                self.html_to_copy.push(' ');
             }
         }
 
         self.text_to_copy.push_str(&new_text);
+        self.html_to_copy.push_str(&new_html);
         self.last_copied_galley_rect = Some(new_galley_rect);
     }
 
@@ -667,18 +686,136 @@ fn process_selection_key_events(
     changed
 }
 
-fn selected_text(galley: &Galley, cursor_range: &CCursorRange) -> String {
+/// The byte range of the selected text.
+fn selected_bytes(galley: &Galley, cursor_range: &CCursorRange) -> std::ops::Range<usize> {
+    let text = galley.text();
+
     // This logic means we can select everything in an elided label (including the `…`)
     // and still copy the entire un-elided text!
     let everything_is_selected = cursor_range.contains(CCursorRange::select_all(galley));
 
-    let copy_everything = cursor_range.is_empty() || everything_is_selected;
+    if cursor_range.is_empty() || everything_is_selected {
+        0..text.len()
+    } else {
+        let [min, max] = cursor_range.sorted_cursors();
+        let byte_index = |cursor| byte_index_from_char_index(text, galley.text_char_index(cursor));
+        byte_index(min)..byte_index(max)
+    }
+}
+
+/// The selected text, without the [`crate::text::Placeholder::CHAR`]s of the placeholders.
+fn selected_text(galley: &Galley, cursor_range: &CCursorRange) -> String {
+    let text = galley.text();
+    let selected_bytes = selected_bytes(galley, cursor_range);
+
+    let mut selected_text = String::with_capacity(selected_bytes.len());
+    let mut start = selected_bytes.start;
+    for section in &galley.job.sections {
+        if section.placeholder.is_some() {
+            let skip_start = section.byte_range.start.clamp(start, selected_bytes.end);
+            let skip_end = section.byte_range.end.clamp(skip_start, selected_bytes.end);
+            selected_text += &text[start..skip_start];
+            start = skip_end;
+        }
+    }
+    selected_text += &text[start..selected_bytes.end];
+    selected_text
+}
+
+/// Like [`selected_text`], but as HTML that keeps the colors, fonts and links of the text.
+fn selected_html(galley: &Galley, cursor_range: &CCursorRange) -> String {
+    let text = galley.text();
+    let selected_bytes = selected_bytes(galley, cursor_range);
+
+    let mut html = String::new();
+    for section in &galley.job.sections {
+        let start = section.byte_range.start.max(selected_bytes.start);
+        let end = section.byte_range.end.min(selected_bytes.end);
+        if end <= start || section.placeholder.is_some() {
+            continue;
+        }
+
+        let span = format!(
+            "<span style=\"{}\">{}</span>",
+            css_from_format(&section.format),
+            escape_html(&text[start..end]).replace('\n', "<br>")
+        );
+        if let Some(link) = &section.link {
+            html += &format!("<a href=\"{}\">{span}</a>", escape_html(link));
+        } else {
+            html += &span;
+        }
+    }
+    html
+}
+
+fn css_from_format(format: &TextFormat) -> String {
+    let mut css = format!(
+        "color: {}; font-size: {}px",
+        css_color(format.color),
+        format.font_id.size
+    );
+
+    match &format.font_id.family {
+        FontFamily::Monospace => css += "; font-family: monospace",
+        FontFamily::Proportional => {}
+        FontFamily::Name(name) => {
+            if name.to_lowercase().contains("bold") && format.font_id.variations.weight.is_none() {
+                css += "; font-weight: bold";
+            }
+        }
+    }
+
+    if let Some(weight) = format.font_id.variations.weight {
+        css += &format!("; font-weight: {weight}");
+    }
+
+    if format.italics {
+        css += "; font-style: italic";
+    }
+
+    let decorations: Vec<&str> = [
+        (format.underline, "underline"),
+        (format.strikethrough, "line-through"),
+        (format.overline, "overline"),
+    ]
+    .into_iter()
+    .filter(|(stroke, _)| !stroke.is_empty())
+    .map(|(_, decoration)| decoration)
+    .collect();
+    if !decorations.is_empty() {
+        css += &format!("; text-decoration: {}", decorations.join(" "));
+    }
+
+    if format.background != Color32::TRANSPARENT {
+        css += &format!("; background-color: {}", css_color(format.background));
+    }
+
+    css
+}
 
-    if copy_everything {
-        galley.text().to_owned()
+fn css_color(color: Color32) -> String {
+    let [r, g, b, a] = color.to_srgba_unmultiplied();
+    if a == 255 {
+        format!("#{r:02x}{g:02x}{b:02x}")
     } else {
-        cursor_range.slice_str(galley).to_owned()
+        format!("rgba({r}, {g}, {b}, {})", f32::from(a) / 255.0)
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped += "&amp;",
+            '<' => escaped += "&lt;",
+            '>' => escaped += "&gt;",
+            '"' => escaped += "&quot;",
+            '\'' => escaped += "&#39;",
+            _ => escaped.push(c),
+        }
     }
+    escaped
 }
 
 fn estimate_row_height(galley: &Galley) -> f32 {
@@ -688,3 +825,129 @@ fn estimate_row_height(galley: &Galley) -> f32 {
         galley.size().y
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_css_from_format() {
+        let format = TextFormat {
+            font_id: crate::FontId::monospace(12.0),
+            color: Color32::RED,
+            italics: true,
+            underline: crate::Stroke::new(1.0, Color32::RED),
+            ..Default::default()
+        };
+        assert_eq!(
+            css_from_format(&format),
+            "color: #ff0000; font-size: 12px; font-family: monospace; font-style: italic; text-decoration: underline"
+        );
+    }
+
+    #[test]
+    fn test_selected_html() {
+        let format = TextFormat {
+            font_id: crate::FontId::proportional(14.0),
+            color: Color32::WHITE,
+            ..Default::default()
+        };
+        let mut job = crate::text::LayoutJob::default();
+        job.append("Hello ", 0.0, format.clone());
+        job.append_link(
+            "<world>",
+            TextFormat {
+                font_id: crate::FontId::proportional(14.0).bold(),
+                ..format
+            },
+            "https://egui.rs/?a&b",
+        );
+
+        let ctx = Context::default();
+        let mut galley = None;
+        let _ = ctx.run(Default::default(), |ctx| {
+            galley = Some(ctx.fonts_mut(|fonts| fonts.layout_job(job.clone())));
+        });
+        let galley = galley.unwrap();
+
+        // "lo <wor", backwards:
+        let cursor_range = CCursorRange::two(CCursor::new(10), CCursor::new(3));
+        assert_eq!(
+            selected_html(&galley, &cursor_range),
+            "<span style=\"color: #ffffff; font-size: 14px\">lo </span>\
+             <a href=\"https://egui.rs/?a&amp;b\">\
+             <span style=\"color: #ffffff; font-size: 14px; font-weight: 700\">&lt;wor</span>\
+             </a>"
+        );
+    }
+
+    #[test]
+    fn test_selected_text_without_placeholders() {
+        let mut job = crate::text::LayoutJob::default();
+        job.append("Press ", 0.0, TextFormat::default());
+        job.append_placeholder(
+            crate::text::Placeholder::new([10.0, 10.0]),
+            TextFormat::default(),
+        );
+        job.append(" to save", 0.0, TextFormat::default());
+
+        let ctx = Context::default();
+        let mut galley = None;
+        let _ = ctx.run(Default::default(), |ctx| {
+            galley = Some(ctx.fonts_mut(|fonts| fonts.layout_job(job.clone())));
+        });
+        let galley = galley.unwrap();
+
+        let everything = CCursorRange::select_all(&galley);
+        assert_eq!(selected_text(&galley, &everything), "Press  to save");
+        let cursor_range = CCursorRange::two(CCursor::new(2), CCursor::new(9));
+        assert_eq!(selected_text(&galley, &cursor_range), "ess  t");
+    }
+
+    #[test]
+    fn test_selected_text_of_elided_galley() {
+        let text = "/home/user/projects/egui/main.rs";
+        let mut job = crate::text::LayoutJob::single_section(
+            text.to_owned(),
+            TextFormat::simple(crate::FontId::monospace(12.0), Color32::WHITE),
+        );
+        job.wrap = crate::text::TextWrapping::truncate_at_width(100.0);
+        job.wrap.elision = crate::text::Elision::Middle;
+
+        let ctx = Context::default();
+        let mut galley = None;
+        let _ = ctx.run(Default::default(), |ctx| {
+            galley = Some(ctx.fonts_mut(|fonts| fonts.layout_job(job.clone())));
+        });
+        let galley = galley.unwrap();
+        let row_text = galley.rows[0].text();
+        let ellipsis = row_text.chars().position(|c| c == '…').unwrap();
+        let elided = galley.elided.clone().unwrap();
+
+        // From the second char up to the ellipsis, then up to the second to last char:
+        let cursor_range = CCursorRange::two(CCursor::new(1), CCursor::new(ellipsis));
+        assert_eq!(
+            selected_text(&galley, &cursor_range),
+            &text[1..elided.start]
+        );
+        let end = galley.end().index - 1;
+        let cursor_range = CCursorRange::two(CCursor::new(1), CCursor::new(end));
+        assert_eq!(
+            selected_text(&galley, &cursor_range),
+            &text[1..text.len() - 1]
+        );
+        let cursor_range = CCursorRange::two(CCursor::new(ellipsis + 1), CCursor::new(end));
+        assert_eq!(
+            selected_text(&galley, &cursor_range),
+            &text[elided.end..text.len() - 1]
+        );
+    }
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq!(
+            escape_html("<a href=\"x\">Tom & Jerry's</a>"),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#39;s&lt;/a&gt;"
+        );
+    }
+}
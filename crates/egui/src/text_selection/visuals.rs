@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use crate::{Galley, Painter, Rect, Ui, Visuals, pos2, vec2};
+use crate::{Color32, Galley, Painter, Rect, Ui, Visuals, epaint::text::Row, pos2, vec2};
 
 use super::CCursorRange;
 
@@ -36,28 +36,69 @@ pub fn paint_text_selection(
         let placed_row = &mut galley.rows[ri];
         let row = Arc::make_mut(&mut placed_row.row);
 
-        let left = if ri == min.row {
-            row.x_offset(min.column)
+        let newline_size = if ri != max.row && placed_row.ends_with_newline {
+            row.height() / 2.0 // visualize that we select the newline
         } else {
             0.0
         };
-        let right = if ri == max.row {
-            row.x_offset(max.column)
+
+        let first_glyph_index = if ri == min.row { min.column } else { 0 };
+        let end_glyph_index = if ri == max.row {
+            max.column
+        } else {
+            row.glyphs.len()
+        };
+
+        let rects = if row.has_rtl() {
+            // The selected glyphs are not necessarily next to each other,
+            // so we paint a rectangle for each contiguous span of them,
+            // starting with one span per shaping cluster:
+            let mut spans: Vec<(f32, f32)> = vec![];
+            let selected_glyphs = row
+                .glyphs
+                .get(first_glyph_index..end_glyph_index)
+                .unwrap_or_default();
+            for (i, glyph) in selected_glyphs.iter().enumerate() {
+                let span = (glyph.pos.x, glyph.max_x());
+                match spans.last_mut() {
+                    Some(last) if 0 < i && glyph.continues_cluster => {
+                        *last = (last.0.min(span.0), last.1.max(span.1));
+                    }
+                    _ => spans.push(span),
+                }
+            }
+            if 0.0 < newline_size {
+                spans.push((row.size.x, row.size.x + newline_size));
+            }
+            spans.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            let mut merged: Vec<(f32, f32)> = vec![];
+            for (left, right) in spans {
+                match merged.last_mut() {
+                    Some(last) if left <= last.1 + 0.5 => last.1 = last.1.max(right),
+                    _ => merged.push((left, right)),
+                }
+            }
+            merged
+                .into_iter()
+                .map(|(left, right)| Rect::from_min_max(pos2(left, 0.0), pos2(right, row.size.y)))
+                .collect()
         } else {
-            let newline_size = if placed_row.ends_with_newline {
-                row.height() / 2.0 // visualize that we select the newline
+            let left = if ri == min.row {
+                row.x_offset(min.column)
             } else {
                 0.0
             };
-            row.size.x + newline_size
+            let right = if ri == max.row {
+                row.x_offset(max.column)
+            } else {
+                row.size.x + newline_size
+            };
+            vec![Rect::from_min_max(pos2(left, 0.0), pos2(right, row.size.y))]
         };
 
-        let rect = Rect::from_min_max(pos2(left, 0.0), pos2(right, row.size.y));
-        let mesh = &mut row.visuals.mesh;
-
         if !row.glyphs.is_empty() {
             // Change color of the selected text:
-            let first_glyph_index = if ri == min.row { min.column } else { 0 };
             let last_glyph_index = if ri == max.row {
                 max.column
             } else {
@@ -76,51 +117,67 @@ pub fn paint_text_selection(
                 .map_or(row.visuals.glyph_vertex_range.end, |g| g.first_vertex as _);
 
             for vi in first_vertex_index..last_vertex_index {
-                mesh.vertices[vi].color = text_color;
+                if !row.visuals.is_color_glyph_vertex(vi) {
+                    row.visuals.mesh.vertices[vi].color = text_color;
+                }
             }
         }
 
-        // Time to insert the selection rectangle into the row mesh.
-        // It should be on top (after) of any background in the galley,
-        // but behind (before) any glyphs. The row visuals has this information:
-        let glyph_index_start = row.visuals.glyph_index_start;
-
-        // Start by appending the selection rectangle to end of the mesh, as two triangles (= 6 indices):
-        let num_indices_before = mesh.indices.len();
-        mesh.add_colored_rect(rect, background_color);
-        assert_eq!(
-            num_indices_before + 6,
-            mesh.indices.len(),
-            "We expect exactly 6 new indices"
-        );
+        for rect in rects {
+            let selection_triangles = insert_selection_rect(row, rect, background_color);
 
-        // Copy out the new triangles:
-        let selection_triangles = [
-            mesh.indices[num_indices_before],
-            mesh.indices[num_indices_before + 1],
-            mesh.indices[num_indices_before + 2],
-            mesh.indices[num_indices_before + 3],
-            mesh.indices[num_indices_before + 4],
-            mesh.indices[num_indices_before + 5],
-        ];
-
-        // Move every old triangle forwards by 6 indices to make room for the new triangle:
-        for i in (glyph_index_start..num_indices_before).rev() {
-            mesh.indices.swap(i, i + 6);
+            if let Some(new_vertex_indices) = &mut new_vertex_indices {
+                new_vertex_indices.push(RowVertexIndices {
+                    row: ri,
+                    vertex_indices: selection_triangles,
+                });
+            }
         }
-        // Put the new triangle in place:
-        mesh.indices[glyph_index_start..glyph_index_start + 6]
-            .clone_from_slice(&selection_triangles);
 
-        row.visuals.mesh_bounds = mesh.calc_bounds();
+        row.visuals.mesh_bounds = row.visuals.mesh.calc_bounds();
+    }
+}
 
-        if let Some(new_vertex_indices) = &mut new_vertex_indices {
-            new_vertex_indices.push(RowVertexIndices {
-                row: ri,
-                vertex_indices: selection_triangles,
-            });
-        }
+/// Returns the vertex indices of the two new triangles.
+fn insert_selection_rect(row: &mut Row, rect: Rect, background_color: Color32) -> [u32; 6] {
+    let mesh = &mut row.visuals.mesh;
+
+    // Time to insert the selection rectangle into the row mesh.
+    // It should be on top (after) of any background in the galley,
+    // but behind (before) any glyphs. The row visuals has this information:
+    let glyph_index_start = row.visuals.glyph_index_start;
+
+    // Start by appending the selection rectangle to end of the mesh, as two triangles (= 6 indices):
+    let num_indices_before = mesh.indices.len();
+    mesh.add_colored_rect(rect, background_color);
+    assert_eq!(
+        num_indices_before + 6,
+        mesh.indices.len(),
+        "We expect exactly 6 new indices"
+    );
+
+    // Copy out the new triangles:
+    let selection_triangles = [
+        mesh.indices[num_indices_before],
+        mesh.indices[num_indices_before + 1],
+        mesh.indices[num_indices_before + 2],
+        mesh.indices[num_indices_before + 3],
+        mesh.indices[num_indices_before + 4],
+        mesh.indices[num_indices_before + 5],
+    ];
+
+    // Move every old triangle forwards by 6 indices to make room for the new triangle:
+    for i in (glyph_index_start..num_indices_before).rev() {
+        mesh.indices.swap(i, i + 6);
     }
+    // Put the new triangle in place:
+    mesh.indices[glyph_index_start..glyph_index_start + 6].clone_from_slice(&selection_triangles);
+
+    // The glyphs now start after the new triangles,
+    // so that the next selection rectangle goes after this one:
+    row.visuals.glyph_index_start += 6;
+
+    selection_triangles
 }
 
 /// Paint one end of the selection, e.g. the primary cursor.
@@ -129,8 +186,12 @@ pub fn paint_text_selection(
 pub fn paint_cursor_end(painter: &Painter, visuals: &Visuals, cursor_rect: Rect) {
     let stroke = visuals.text_cursor.stroke;
 
-    let top = cursor_rect.center_top();
-    let bottom = cursor_rect.center_bottom();
+    let (top, bottom) = if cursor_rect.height() < cursor_rect.width() {
+        // A cursor in vertical text goes across the column:
+        (cursor_rect.left_center(), cursor_rect.right_center())
+    } else {
+        (cursor_rect.center_top(), cursor_rect.center_bottom())
+    };
 
     painter.line_segment([top, bottom], (stroke.width, stroke.color));
 
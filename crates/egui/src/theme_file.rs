@@ -0,0 +1,472 @@
+//! Theme files: a [`Style`] expressed as a diff on top of one of the built-in themes.
+//!
+//! A theme file names a `base` theme (`"Dark"` or `"Light"`) and then lists only
+//! the fields of [`Style`] and [`Visuals`](crate::Visuals) that differ from it:
+//!
+//! ```json
+//! {
+//!     "base": "Dark",
+//!     "visuals": {
+//!         "hyperlink_color": [255, 128, 0, 255],
+//!         "widgets": { "inactive": { "corner_radius": { "nw": 8, "ne": 8, "sw": 8, "se": 8 } } }
+//!     },
+//!     "style": {
+//!         "spacing": { "item_spacing": { "x": 10.0, "y": 4.0 } }
+//!     }
+//! }
+//! ```
+//!
+//! The same structure can be written in RON. Since RON identifiers are not
+//! preserved in a schema-less parse, enum values have to be written as strings
+//! there too (e.g. `base: "Light"`).
+//!
+//! Keys that do not exist in [`Style`] are rejected with a suggestion of what
+//! was probably meant, so typos do not silently get ignored.
+//!
+//! Use [`ThemeFile::from_style`] to go the other way and export the current style.
+
+use crate::{Context, Style, Theme};
+
+type Map = serde_json::Map<String, serde_json::Value>;
+type Value = serde_json::Value;
+
+/// Something went wrong when parsing or applying a [`ThemeFile`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ThemeFileError {
+    /// The text could not be parsed in the given format.
+    Parse(String),
+
+    /// A key that does not exist in [`Style`].
+    UnknownKey {
+        /// Dot-separated path to the key, e.g. `visuals.widgets.inactive.bg_fil`.
+        path: String,
+
+        /// The closest existing key, if any is close enough.
+        suggestion: Option<String>,
+    },
+
+    /// A value has the wrong shape for the field it overrides.
+    InvalidValue {
+        /// Dot-separated path to the value.
+        path: String,
+
+        /// What went wrong.
+        message: String,
+    },
+}
+
+impl std::fmt::Display for ThemeFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parse(err) => write!(f, "Failed to parse theme: {err}"),
+            Self::UnknownKey { path, suggestion } => {
+                write!(f, "Unknown theme key `{path}`")?;
+                if let Some(suggestion) = suggestion {
+                    write!(f, " - did you mean `{suggestion}`?")?;
+                }
+                Ok(())
+            }
+            Self::InvalidValue { path, message } => {
+                write!(f, "Invalid value for `{path}`: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ThemeFileError {}
+
+/// A set of overrides on top of one of the built-in themes.
+///
+/// See the [module docs](crate::theme_file) for the format.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ThemeFile {
+    /// The built-in style the overrides are applied to.
+    pub base: Theme,
+
+    /// Overridden fields of [`Style`], keyed by field name.
+    ///
+    /// Nested structs are nested maps, so only the changed leaves need to be listed.
+    pub overrides: Map,
+}
+
+impl ThemeFile {
+    /// A theme without any overrides.
+    pub fn new(base: Theme) -> Self {
+        Self {
+            base,
+            overrides: Map::new(),
+        }
+    }
+
+    /// Parse a theme file written in JSON.
+    ///
+    /// # Errors
+    /// If the JSON is malformed, or the theme does not match [`Style`].
+    pub fn from_json(json: &str) -> Result<Self, ThemeFileError> {
+        let value: Value =
+            serde_json::from_str(json).map_err(|err| ThemeFileError::Parse(err.to_string()))?;
+        Self::from_value(value)
+    }
+
+    /// Parse a theme file written in RON.
+    ///
+    /// Enum values must be written as strings, e.g. `base: "Light"`.
+    ///
+    /// # Errors
+    /// If the RON is malformed, or the theme does not match [`Style`].
+    pub fn from_ron(ron: &str) -> Result<Self, ThemeFileError> {
+        let value: Value =
+            ron::from_str(ron).map_err(|err| ThemeFileError::Parse(err.to_string()))?;
+        Self::from_value(value)
+    }
+
+    /// Build a theme from an already parsed document.
+    ///
+    /// Use this to support other self-describing formats (e.g. TOML):
+    /// deserialize them into a [`serde_json::Value`] and pass it here.
+    ///
+    /// # Errors
+    /// If there are unknown keys, or values of the wrong type.
+    pub fn from_value(value: Value) -> Result<Self, ThemeFileError> {
+        let Value::Object(mut root) = value else {
+            return Err(ThemeFileError::InvalidValue {
+                path: String::new(),
+                message: "expected a map at the top level".to_owned(),
+            });
+        };
+
+        let base = match root.remove("base") {
+            None => Theme::Dark,
+            Some(base) => {
+                serde_json::from_value(base).map_err(|err| ThemeFileError::InvalidValue {
+                    path: "base".to_owned(),
+                    message: err.to_string(),
+                })?
+            }
+        };
+
+        let mut overrides = Map::new();
+        for (key, value) in root {
+            match key.as_str() {
+                "style" => merge_into(&mut overrides, expect_map(value, "style")?),
+                "visuals" => {
+                    let visuals = overrides
+                        .entry("visuals")
+                        .or_insert_with(|| Value::Object(Map::new()));
+                    if let Value::Object(visuals) = visuals {
+                        merge_into(visuals, expect_map(value, "visuals")?);
+                    }
+                }
+                _ => {
+                    return Err(ThemeFileError::UnknownKey {
+                        suggestion: closest_key(&key, ["base", "style", "visuals"].into_iter())
+                            .map(ToOwned::to_owned),
+                        path: key,
+                    });
+                }
+            }
+        }
+
+        let theme = Self { base, overrides };
+        theme.style()?;
+        Ok(theme)
+    }
+
+    /// Describe `style` as the minimal set of overrides on top of `base`.
+    ///
+    /// # Errors
+    /// If `style` can't be serialized, e.g. because [`Style::text_styles`]
+    /// contains a [`crate::TextStyle::Name`].
+    pub fn from_style(base: Theme, style: &Style) -> Result<Self, ThemeFileError> {
+        let base_value = style_to_value(&base.default_style())?;
+        let style_value = style_to_value(style)?;
+
+        let mut overrides = match diff(&base_value, &style_value) {
+            Some(Value::Object(overrides)) => overrides,
+            _ => Map::new(),
+        };
+        // Debug options are not part of the look, and don't exist in release builds.
+        overrides.remove("debug");
+        Ok(Self { base, overrides })
+    }
+
+    /// The resulting [`Style`]: the base style with all overrides applied.
+    ///
+    /// # Errors
+    /// If there are unknown keys, or values of the wrong type.
+    pub fn style(&self) -> Result<Style, ThemeFileError> {
+        let mut value = style_to_value(&self.base.default_style())?;
+        apply_overrides(&mut value, &self.overrides, "")?;
+        serde_json::from_value(value).map_err(|err| ThemeFileError::InvalidValue {
+            path: String::new(),
+            message: err.to_string(),
+        })
+    }
+
+    /// Set the style of [`Self::base`] in the given context to this theme.
+    ///
+    /// Note that this has no visible effect unless [`Self::base`] is the current [`Context::theme`].
+    ///
+    /// # Errors
+    /// See [`Self::style`].
+    pub fn apply(&self, ctx: &Context) -> Result<(), ThemeFileError> {
+        ctx.set_style_of(self.base, self.style()?);
+        Ok(())
+    }
+
+    fn to_value(&self) -> Value {
+        let mut root = Map::new();
+        root.insert(
+            "base".to_owned(),
+            serde_json::to_value(self.base).unwrap_or_default(),
+        );
+
+        let mut style = self.overrides.clone();
+        if let Some(visuals) = style.remove("visuals") {
+            root.insert("visuals".to_owned(), visuals);
+        }
+        if !style.is_empty() {
+            root.insert("style".to_owned(), Value::Object(style));
+        }
+        Value::Object(root)
+    }
+
+    /// Write the theme as pretty-printed JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.to_value()).unwrap_or_default()
+    }
+
+    /// Write the theme as pretty-printed RON.
+    pub fn to_ron(&self) -> String {
+        ron::ser::to_string_pretty(&self.to_value(), ron::ser::PrettyConfig::default())
+            .unwrap_or_default()
+    }
+}
+
+fn style_to_value(style: &Style) -> Result<Value, ThemeFileError> {
+    serde_json::to_value(style).map_err(|err| ThemeFileError::InvalidValue {
+        path: String::new(),
+        message: err.to_string(),
+    })
+}
+
+fn expect_map(value: Value, path: &str) -> Result<Map, ThemeFileError> {
+    match value {
+        Value::Object(map) => Ok(map),
+        other => Err(ThemeFileError::InvalidValue {
+            path: path.to_owned(),
+            message: format!("expected a map, found {}", kind_name(&other)),
+        }),
+    }
+}
+
+/// Recursively merge `src` into `dst`, with `src` taking precedence.
+fn merge_into(dst: &mut Map, src: Map) {
+    for (key, value) in src {
+        match (dst.get_mut(&key), value) {
+            (Some(Value::Object(dst)), Value::Object(src)) => merge_into(dst, src),
+            (_, value) => {
+                dst.insert(key, value);
+            }
+        }
+    }
+}
+
+fn join_path(parent: &str, key: &str) -> String {
+    if parent.is_empty() {
+        key.to_owned()
+    } else {
+        format!("{parent}.{key}")
+    }
+}
+
+/// Apply `overrides` to `target`, rejecting keys that are not already in `target`.
+fn apply_overrides(target: &mut Value, overrides: &Map, path: &str) -> Result<(), ThemeFileError> {
+    let Value::Object(target) = target else {
+        return Err(ThemeFileError::InvalidValue {
+            path: path.to_owned(),
+            message: format!("expected {}, found a map", kind_name(target)),
+        });
+    };
+
+    for (key, value) in overrides {
+        let key_path = join_path(path, key);
+        let Some(existing) = target.get_mut(key) else {
+            return Err(ThemeFileError::UnknownKey {
+                suggestion: closest_key(key, target.keys().map(String::as_str))
+                    .map(|suggestion| join_path(path, suggestion)),
+                path: key_path,
+            });
+        };
+
+        match (existing, value) {
+            // A struct field: recurse so only the given leaves are replaced.
+            // Maps with non-identifier keys (e.g. `text_styles`) are replaced one entry at a time as well.
+            (existing @ Value::Object(_), Value::Object(value)) => {
+                apply_overrides(existing, value, &key_path)?;
+            }
+            // `None` in the base: anything goes, the final deserialization checks it.
+            (existing @ Value::Null, value) => *existing = value.clone(),
+            (existing, value) => {
+                if !value.is_null() && !same_kind(existing, value) {
+                    return Err(ThemeFileError::InvalidValue {
+                        path: key_path,
+                        message: format!(
+                            "expected {}, found {}",
+                            kind_name(existing),
+                            kind_name(value)
+                        ),
+                    });
+                }
+                *existing = value.clone();
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The parts of `new` that differ from `old`, or `None` if they are equal.
+fn diff(old: &Value, new: &Value) -> Option<Value> {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            let mut changed = Map::new();
+            for (key, new_value) in new {
+                let value = match old.get(key) {
+                    Some(old_value) => diff(old_value, new_value),
+                    None => Some(new_value.clone()),
+                };
+                if let Some(value) = value {
+                    changed.insert(key.clone(), value);
+                }
+            }
+            (!changed.is_empty()).then_some(Value::Object(changed))
+        }
+        (old, new) => (old != new).then(|| new.clone()),
+    }
+}
+
+fn same_kind(a: &Value, b: &Value) -> bool {
+    std::mem::discriminant(a) == std::mem::discriminant(b)
+}
+
+fn kind_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "nothing",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "a list",
+        Value::Object(_) => "a map",
+    }
+}
+
+/// The candidate closest to `key`, if it is close enough to likely be a typo.
+fn closest_key<'a>(key: &str, candidates: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    let max_distance = (key.chars().count() / 3).max(1);
+    candidates
+        .map(|candidate| (edit_distance(key, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// [`Style::number_formatter`] is not serialized, and only compares equal to itself.
+    fn assert_same_style(actual: &Style, expected: &Style) {
+        let actual = Style {
+            number_formatter: expected.number_formatter.clone(),
+            ..actual.clone()
+        };
+        assert_eq!(&actual, expected);
+    }
+
+    #[test]
+    fn empty_theme_is_base_style() {
+        let theme = ThemeFile::from_json(r#"{ "base": "Light" }"#).unwrap();
+        assert_eq!(theme.base, Theme::Light);
+        assert_same_style(&theme.style().unwrap(), &Theme::Light.default_style());
+    }
+
+    #[test]
+    fn overrides_only_touch_given_fields() {
+        let theme = ThemeFile::from_json(
+            r#"{
+                "base": "Dark",
+                "visuals": { "hyperlink_color": [1, 2, 3, 255] },
+                "style": { "spacing": { "item_spacing": { "x": 10.0, "y": 4.0 } } }
+            }"#,
+        )
+        .unwrap();
+        let mut expected = Theme::Dark.default_style();
+        expected.visuals.hyperlink_color = crate::Color32::from_rgb(1, 2, 3);
+        expected.spacing.item_spacing = crate::vec2(10.0, 4.0);
+        assert_same_style(&theme.style().unwrap(), &expected);
+    }
+
+    #[test]
+    fn unknown_keys_are_reported() {
+        let err = ThemeFile::from_json(r#"{ "visuals": { "hyperlink_colour": [0, 0, 0, 255] } }"#)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ThemeFileError::UnknownKey {
+                path: "visuals.hyperlink_colour".to_owned(),
+                suggestion: Some("visuals.hyperlink_color".to_owned()),
+            }
+        );
+    }
+
+    #[test]
+    fn wrong_kind_is_reported() {
+        let err = ThemeFile::from_json(r#"{ "visuals": { "striped": 3 } }"#).unwrap_err();
+        assert!(
+            matches!(err, ThemeFileError::InvalidValue { path, .. } if path == "visuals.striped")
+        );
+    }
+
+    #[test]
+    fn export_roundtrip() {
+        let mut style = Theme::Light.default_style();
+        style.visuals.window_fill = crate::Color32::from_rgb(200, 100, 50);
+        style.spacing.indent = 30.0;
+
+        let theme = ThemeFile::from_style(Theme::Light, &style).unwrap();
+        assert_eq!(
+            theme.overrides.len(),
+            2,
+            "Only the changed fields: {theme:?}"
+        );
+
+        assert_eq!(ThemeFile::from_json(&theme.to_json()).unwrap(), theme);
+        assert_same_style(
+            &ThemeFile::from_ron(&theme.to_ron())
+                .unwrap()
+                .style()
+                .unwrap(),
+            &style,
+        );
+    }
+}
@@ -328,10 +328,16 @@ impl Ui {
             child_ui.disable();
         }
 
-        child_ui.ctx().register_accesskit_parent(
-            child_ui.unique_id,
-            accessibility_parent.unwrap_or(self.unique_id),
-        );
+        let accessibility_parent = accessibility_parent.unwrap_or_else(|| {
+            if let Some(grid) = self.placer.grid() {
+                grid.accesskit_cell(self.unique_id, grid.next_cell_index())
+            } else {
+                self.unique_id
+            }
+        });
+        child_ui
+            .ctx()
+            .register_accesskit_parent(child_ui.unique_id, accessibility_parent);
 
         // Register in the widget stack early, to ensure we are behind all widgets we contain:
         let start_rect = Rect::NOTHING; // This will be overwritten when `remember_min_rect` is called
@@ -1025,7 +1031,17 @@ impl Ui {
 impl Ui {
     /// Check for clicks, drags and/or hover on a specific region of this [`Ui`].
     pub fn interact(&self, rect: Rect, id: Id, sense: Sense) -> Response {
-        self.ctx().register_accesskit_parent(id, self.unique_id);
+        let accessibility_parent = match self.placer.grid() {
+            // Widgets in a grid belong to the cell they were just placed in:
+            Some(grid) if id != self.unique_id => {
+                grid.last_cell_index().map_or(self.unique_id, |cell| {
+                    grid.accesskit_cell(self.unique_id, cell)
+                })
+            }
+            _ => self.unique_id,
+        };
+        self.ctx()
+            .register_accesskit_parent(id, accessibility_parent);
 
         self.ctx().create_widget(
             WidgetRect {
@@ -2710,6 +2726,9 @@ impl Ui {
     }
 
     pub(crate) fn save_grid(&mut self) {
+        if let Some(grid) = self.placer.grid() {
+            grid.accesskit_table(self.unique_id);
+        }
         self.placer.save_grid();
     }
 
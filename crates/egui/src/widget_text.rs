@@ -4,8 +4,9 @@ use std::fmt::Formatter;
 use std::{borrow::Cow, sync::Arc};
 
 use crate::{
-    Align, Color32, FontFamily, FontSelection, Galley, Style, TextStyle, TextWrapMode, Ui, Visuals,
-    text::{LayoutJob, TextWrapping},
+    Align, Color32, FontFamily, FontFeature, FontSelection, FontVariations, Galley, Language,
+    Shadow, Stroke, Style, TextStyle, TextWrapMode, Ui, Visuals,
+    text::{DecorationStyle, LayoutJob, TextWrapping},
 };
 
 /// Text and optional style choices for it.
@@ -30,6 +31,7 @@ pub struct RichText {
     extra_letter_spacing: f32,
     line_height: Option<f32>,
     family: Option<FontFamily>,
+    variations: FontVariations,
     text_style: Option<TextStyle>,
     background_color: Color32,
     expand_bg: f32,
@@ -39,8 +41,14 @@ pub struct RichText {
     weak: bool,
     strikethrough: bool,
     underline: bool,
+    overline: bool,
+    decoration_style: DecorationStyle,
+    outline: Stroke,
+    shadow: Shadow,
     italics: bool,
     raised: bool,
+    font_features: Vec<FontFeature>,
+    language: Option<Language>,
 }
 
 impl Default for RichText {
@@ -51,6 +59,7 @@ impl Default for RichText {
             extra_letter_spacing: Default::default(),
             line_height: Default::default(),
             family: Default::default(),
+            variations: Default::default(),
             text_style: Default::default(),
             background_color: Default::default(),
             expand_bg: 1.0,
@@ -60,8 +69,14 @@ impl Default for RichText {
             weak: Default::default(),
             strikethrough: Default::default(),
             underline: Default::default(),
+            overline: Default::default(),
+            decoration_style: Default::default(),
+            outline: Stroke::NONE,
+            shadow: Shadow::NONE,
             italics: Default::default(),
             raised: Default::default(),
+            font_features: Default::default(),
+            language: Default::default(),
         }
     }
 }
@@ -190,9 +205,23 @@ impl RichText {
     /// This overrides the value from [`Self::text_style`].
     #[inline]
     pub fn font(mut self, font_id: crate::FontId) -> Self {
-        let crate::FontId { size, family } = font_id;
+        let crate::FontId {
+            size,
+            family,
+            variations,
+        } = font_id;
         self.size = Some(size);
         self.family = Some(family);
+        self.variations = variations;
+        self
+    }
+
+    /// Set the weight of a variable font, e.g. `600.0` for semi-bold.
+    ///
+    /// See [`FontVariations::weight`].
+    #[inline]
+    pub fn weight(mut self, weight: f32) -> Self {
+        self.variations.weight = Some(weight);
         self
     }
 
@@ -229,7 +258,10 @@ impl RichText {
         self.text_style(TextStyle::Monospace)
     }
 
-    /// Extra strong text (stronger color).
+    /// Extra strong text: bold (if the font is a variable font with a weight axis),
+    /// and in a stronger color.
+    ///
+    /// The built-in fonts are not variable fonts, so for them only the color changes.
     #[inline]
     pub fn strong(mut self) -> Self {
         self.strong = true;
@@ -261,6 +293,53 @@ impl RichText {
         self
     }
 
+    /// Draw a line above the text.
+    ///
+    /// If you want to control the line color, use [`LayoutJob`] instead.
+    #[inline]
+    pub fn overline(mut self) -> Self {
+        self.overline = true;
+        self
+    }
+
+    /// How to draw the [`Self::underline`], [`Self::strikethrough`] and [`Self::overline`],
+    /// e.g. [`DecorationStyle::Wavy`] to mark a spelling mistake.
+    #[inline]
+    pub fn decoration_style(mut self, decoration_style: DecorationStyle) -> Self {
+        self.decoration_style = decoration_style;
+        self
+    }
+
+    /// Draw an outline around the glyphs,
+    /// e.g. to keep the text readable on top of an image.
+    ///
+    /// ```
+    /// # use egui::{Color32, RichText};
+    /// RichText::new("Over the map").outline((2.0, Color32::BLACK));
+    /// ```
+    #[inline]
+    pub fn outline(mut self, outline: impl Into<Stroke>) -> Self {
+        self.outline = outline.into();
+        self
+    }
+
+    /// Draw a (blurred) drop shadow under the glyphs.
+    ///
+    /// ```
+    /// # use egui::{Color32, RichText, Shadow};
+    /// RichText::new("Over the video").shadow(Shadow {
+    ///     offset: [1, 1],
+    ///     blur: 4,
+    ///     spread: 0,
+    ///     color: Color32::from_black_alpha(200),
+    /// });
+    /// ```
+    #[inline]
+    pub fn shadow(mut self, shadow: Shadow) -> Self {
+        self.shadow = shadow;
+        self
+    }
+
     /// Tilt the characters to the right.
     #[inline]
     pub fn italics(mut self) -> Self {
@@ -287,6 +366,31 @@ impl RichText {
         self
     }
 
+    /// Turn an OpenType feature on or off, e.g. [`FontFeature::TABULAR_NUMBERS`]
+    /// to make numbers in a table column line up.
+    #[inline]
+    pub fn font_feature(mut self, font_feature: FontFeature) -> Self {
+        self.font_features.push(font_feature);
+        self
+    }
+
+    /// Turn several OpenType features on or off, e.g. [`FontFeature::NO_LIGATURES`].
+    #[inline]
+    pub fn font_features(mut self, font_features: impl IntoIterator<Item = FontFeature>) -> Self {
+        self.font_features.extend(font_features);
+        self
+    }
+
+    /// The language of the text, e.g. `"ja"` or `"zh-Hant"`.
+    ///
+    /// This decides which language-specific fallback fonts are used,
+    /// see [`crate::FontDefinitions::language_fallbacks`].
+    #[inline]
+    pub fn language(mut self, language: impl Into<Language>) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+
     /// Fill-color behind the text.
     #[inline]
     pub fn background_color(mut self, background_color: impl Into<Color32>) -> Self {
@@ -370,7 +474,10 @@ impl RichText {
         default_valign: Align,
     ) -> LayoutJob {
         let (text, text_format) = self.into_text_and_format(style, fallback_font, default_valign);
-        LayoutJob::single_section(text, text_format)
+        LayoutJob {
+            glyph_rendering: style.glyph_rendering,
+            ..LayoutJob::single_section(text, text_format)
+        }
     }
 
     fn into_text_and_format(
@@ -387,17 +494,24 @@ impl RichText {
             extra_letter_spacing,
             line_height,
             family,
+            variations,
             text_style,
             background_color,
             expand_bg,
             text_color: _, // already used by `get_text_color`
             code,
-            strong: _, // already used by `get_text_color`
-            weak: _,   // already used by `get_text_color`
+            strong,
+            weak: _, // already used by `get_text_color`
             strikethrough,
             underline,
+            overline,
+            decoration_style,
+            outline,
+            shadow,
             italics,
             raised,
+            font_features,
+            language,
         } = self;
 
         let line_color = text_color.unwrap_or_else(|| style.visuals.text_color());
@@ -415,6 +529,20 @@ impl RichText {
             if let Some(family) = family {
                 font_id.family = family;
             }
+            let FontVariations {
+                weight,
+                width,
+                slant,
+                optical_size,
+            } = variations;
+            let font_variations = &mut font_id.variations;
+            font_variations.weight = weight.or(font_variations.weight);
+            font_variations.width = width.or(font_variations.width);
+            font_variations.slant = slant.or(font_variations.slant);
+            font_variations.optical_size = optical_size.or(font_variations.optical_size);
+            if strong && weight.is_none() {
+                font_variations.weight = Some(FontVariations::BOLD);
+            }
             font_id
         };
 
@@ -434,6 +562,11 @@ impl RichText {
         } else {
             crate::Stroke::NONE
         };
+        let overline = if overline {
+            crate::Stroke::new(1.0, line_color)
+        } else {
+            crate::Stroke::NONE
+        };
 
         let valign = if raised {
             crate::Align::TOP
@@ -452,8 +585,15 @@ impl RichText {
                 italics,
                 underline,
                 strikethrough,
+                overline,
+                decoration_style,
+                decoration_thickness_from_font: false,
+                outline,
+                shadow,
                 valign,
                 expand_bg,
+                font_features,
+                language,
             },
         )
     }
@@ -641,6 +781,30 @@ impl WidgetText {
         self.map_rich_text(|text| text.strikethrough())
     }
 
+    /// Prefer using [`RichText`] directly!
+    #[inline]
+    pub fn overline(self) -> Self {
+        self.map_rich_text(|text| text.overline())
+    }
+
+    /// Prefer using [`RichText`] directly!
+    #[inline]
+    pub fn decoration_style(self, decoration_style: DecorationStyle) -> Self {
+        self.map_rich_text(|text| text.decoration_style(decoration_style))
+    }
+
+    /// Prefer using [`RichText`] directly!
+    #[inline]
+    pub fn outline(self, outline: impl Into<Stroke>) -> Self {
+        self.map_rich_text(|text| text.outline(outline))
+    }
+
+    /// Prefer using [`RichText`] directly!
+    #[inline]
+    pub fn shadow(self, shadow: Shadow) -> Self {
+        self.map_rich_text(|text| text.shadow(shadow))
+    }
+
     /// Prefer using [`RichText`] directly!
     #[inline]
     pub fn italics(self) -> Self {
@@ -694,15 +858,18 @@ impl WidgetText {
         default_valign: Align,
     ) -> Arc<LayoutJob> {
         match self {
-            Self::Text(text) => Arc::new(LayoutJob::simple_format(
-                text,
-                TextFormat {
-                    font_id: FontSelection::Default.resolve(style),
-                    color: crate::Color32::PLACEHOLDER,
-                    valign: default_valign,
-                    ..Default::default()
-                },
-            )),
+            Self::Text(text) => Arc::new(LayoutJob {
+                glyph_rendering: style.glyph_rendering,
+                ..LayoutJob::simple_format(
+                    text,
+                    TextFormat {
+                        font_id: FontSelection::Default.resolve(style),
+                        color: crate::Color32::PLACEHOLDER,
+                        valign: default_valign,
+                        ..Default::default()
+                    },
+                )
+            }),
             Self::RichText(text) => Arc::new(Arc::unwrap_or_clone(text).into_layout_job(
                 style,
                 fallback_font,
@@ -758,6 +925,7 @@ impl WidgetText {
                     },
                 );
                 layout_job.wrap = text_wrapping;
+                layout_job.glyph_rendering = style.glyph_rendering;
                 ctx.fonts_mut(|f| f.layout_job(layout_job))
             }
             Self::RichText(text) => {
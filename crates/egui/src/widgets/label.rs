@@ -1,8 +1,12 @@
 use std::sync::Arc;
 
 use crate::{
-    Align, Direction, FontSelection, Galley, Pos2, Response, Sense, Stroke, TextWrapMode, Ui,
-    Widget, WidgetInfo, WidgetText, WidgetType, epaint, pos2, text_selection::LabelSelectionState,
+    Align, CursorIcon, Direction, FontSelection, Galley, Layout, Pos2, Rect, Response, Sense,
+    Stroke, TextWrapMode, Ui, UiBuilder, Vec2, Widget, WidgetInfo, WidgetText, WidgetType, epaint,
+    epaint::text::cursor::CCursor,
+    pos2,
+    text::{Elision, WritingMode},
+    text_selection::LabelSelectionState,
 };
 
 /// Static text.
@@ -21,10 +25,15 @@ use crate::{
 ///
 /// For full control of the text you can use [`crate::text::LayoutJob`]
 /// as argument to [`Self::new`].
+///
+/// Sections of a [`crate::text::LayoutJob`] can be made clickable with
+/// [`crate::text::LayoutJob::append_link`].
+/// Use [`Self::show`] to find out which of them was hovered or clicked.
 #[must_use = "You should put this widget in a ui with `ui.add(widget);`"]
 pub struct Label {
     text: WidgetText,
     wrap_mode: Option<TextWrapMode>,
+    elision: Option<Elision>,
     sense: Option<Sense>,
     selectable: Option<bool>,
     halign: Option<Align>,
@@ -36,6 +45,7 @@ impl Label {
         Self {
             text: text.into(),
             wrap_mode: None,
+            elision: None,
             sense: None,
             selectable: None,
             halign: None,
@@ -73,6 +83,24 @@ impl Label {
         self
     }
 
+    /// Set [`Self::wrap_mode`] to [`TextWrapMode::Truncate`],
+    /// and choose which part of the text to leave out.
+    ///
+    /// For instance, [`Elision::Path`] keeps the file name of a long path visible.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui::text::Elision;
+    /// ui.add(egui::Label::new("/home/user/projects/egui/main.rs").elide(Elision::Path));
+    /// # });
+    /// ```
+    #[inline]
+    pub fn elide(mut self, elision: Elision) -> Self {
+        self.wrap_mode = Some(TextWrapMode::Truncate);
+        self.elision = Some(elision);
+        self
+    }
+
     /// Set [`Self::wrap_mode`] to [`TextWrapMode::Extend`],
     /// disabling wrapping and truncating, and instead expanding the parent [`Ui`].
     #[inline]
@@ -186,10 +214,12 @@ impl Label {
             valign,
         ));
 
+        let is_vertical = layout_job.writing_mode == WritingMode::VerticalRightToLeft;
         let available_width = ui.available_width();
 
         let wrap_mode = self.wrap_mode.unwrap_or_else(|| ui.wrap_mode());
         if wrap_mode == TextWrapMode::Wrap
+            && !is_vertical
             && ui.layout().main_dir() == Direction::LeftToRight
             && ui.layout().main_wrap()
             && available_width.is_finite()
@@ -227,6 +257,13 @@ impl Label {
             }
             (pos, galley, response)
         } else {
+            // Vertical text wraps into new columns at the bottom:
+            let available_width = if is_vertical {
+                ui.available_height()
+            } else {
+                available_width
+            };
+
             // Apply wrap_mode, but don't overwrite anything important
             // the user may have set manually on the layout_job:
             match wrap_mode {
@@ -240,10 +277,13 @@ impl Label {
                     layout_job.wrap.max_width = available_width;
                     layout_job.wrap.max_rows = 1;
                     layout_job.wrap.break_anywhere = true;
+                    if let Some(elision) = self.elision {
+                        layout_job.wrap.elision = elision;
+                    }
                 }
             }
 
-            if ui.is_grid() {
+            if ui.is_grid() || is_vertical {
                 // TODO(emilk): remove special Grid hacks like these
                 layout_job.halign = Align::LEFT;
                 layout_job.justify = false;
@@ -267,8 +307,69 @@ impl Label {
     }
 }
 
-impl Widget for Label {
-    fn ui(self, ui: &mut Ui) -> Response {
+impl Label {
+    /// Show the label, and find out which section of its text was hovered or clicked.
+    ///
+    /// The pointer shows a hand over sections with a [`crate::text::LayoutSection::link`].
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui::text::{LayoutJob, TextFormat};
+    ///
+    /// let mut job = LayoutJob::default();
+    /// job.append("Read the ", 0.0, TextFormat::default());
+    /// let link = job.append_link("manual", TextFormat::default(), "https://docs.rs/egui");
+    /// job.append(" before starting.", 0.0, TextFormat::default());
+    ///
+    /// let output = egui::Label::new(job).show(ui);
+    /// if let Some(url) = output.clicked_link() {
+    ///     ui.ctx().open_url(egui::OpenUrl::new_tab(url));
+    /// }
+    /// if output.hovered_section == Some(link) {
+    ///     output.response.on_hover_text("Opens in your browser");
+    /// }
+    /// # });
+    /// ```
+    pub fn show(self, ui: &mut Ui) -> LabelOutput {
+        self.show_impl(ui, &mut |_, _| {})
+    }
+
+    /// Show the label, and put something into each placeholder of its text
+    /// (see [`crate::text::LayoutJob::append_placeholder`]).
+    ///
+    /// `add_contents` is called with a [`Ui`] covering each placeholder,
+    /// and the section index of the placeholder.
+    /// Use it to show inline images or widgets that flow with the text.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui::text::{LayoutJob, Placeholder, TextFormat};
+    ///
+    /// let mut job = LayoutJob::default();
+    /// job.append("Press ", 0.0, TextFormat::default());
+    /// let button = job.append_placeholder(Placeholder::new([40.0, 18.0]), TextFormat::default());
+    /// job.append(" to continue.", 0.0, TextFormat::default());
+    ///
+    /// egui::Label::new(job).show_with_placeholders(ui, |ui, section_index| {
+    ///     if section_index == button && ui.small_button("here").clicked() {
+    ///         // …
+    ///     }
+    /// });
+    /// # });
+    /// ```
+    pub fn show_with_placeholders(
+        self,
+        ui: &mut Ui,
+        mut add_contents: impl FnMut(&mut Ui, usize),
+    ) -> Response {
+        self.show_impl(ui, &mut add_contents).response
+    }
+
+    fn show_impl(
+        self,
+        ui: &mut Ui,
+        add_placeholder_contents: &mut dyn FnMut(&mut Ui, usize),
+    ) -> LabelOutput {
         // Interactive = the uses asked to sense interaction.
         // We DON'T want to have the color respond just because the text is selectable;
         // the cursor is enough to communicate that.
@@ -280,9 +381,67 @@ impl Widget for Label {
         let (galley_pos, galley, mut response) = self.layout_in_ui(ui);
         response
             .widget_info(|| WidgetInfo::labeled(WidgetType::Label, ui.is_enabled(), galley.text()));
+        let placeholder_rects = galley.placeholder_rects();
+
+        // Only the links are clickable, not the rest of the label.
+        // They sit on top of the label, and can be reached with the TAB key, like a `Hyperlink`:
+        let mut hovered_link = None;
+        let mut clicked_link = None;
+        let mut focused_link_rects = vec![];
+        let section_at = |pos: Pos2| section_at(&galley, pos - galley_pos);
+        for (link_sections, rects) in galley.link_rects() {
+            let link_id = response.id.with(("link", link_sections.start));
+            // The section of the link under the pointer, e.g. a bold word in it:
+            let section_in_link = |pos: Option<Pos2>| {
+                pos.and_then(section_at)
+                    .filter(|section_index| link_sections.contains(section_index))
+                    .unwrap_or(link_sections.start)
+            };
+            let rects: Vec<Rect> = rects
+                .into_iter()
+                .map(|rect| rect.translate(galley_pos.to_vec2()))
+                .collect();
+            for (row, &rect) in rects.iter().enumerate() {
+                // A link that is wrapped over several rows is only focused once,
+                // as the AccessKit node of the link:
+                let (id, sense) = if row == 0 {
+                    (link_id, Sense::click())
+                } else {
+                    (link_id.with(row), Sense::click() - Sense::FOCUSABLE)
+                };
+                let link_response = ui.interact(rect, id, sense);
+                if link_response.hovered() {
+                    hovered_link = Some(section_in_link(link_response.hover_pos()));
+                }
+                if link_response.clicked() {
+                    clicked_link = Some(section_in_link(link_response.interact_pointer_pos()));
+                }
+                if link_response.has_focus() {
+                    focused_link_rects.clone_from(&rects);
+                }
+            }
+            if ui.input(|i| i.has_accesskit_action_request(link_id, accesskit::Action::Click)) {
+                clicked_link = Some(link_sections.start);
+            }
+            accesskit_link(ui, &response, link_id, &galley, link_sections, &rects);
+        }
+
+        if hovered_link.is_some() {
+            ui.set_cursor_icon(CursorIcon::PointingHand);
+        }
+
+        let hovered_section = hovered_link.or_else(|| response.hover_pos().and_then(section_at));
+        let clicked_section = clicked_link.or_else(|| {
+            if response.clicked() {
+                response.interact_pointer_pos().and_then(section_at)
+            } else {
+                None
+            }
+        });
+        let output_galley = Arc::clone(&galley);
 
         if ui.is_rect_visible(response.rect) {
-            if show_tooltip_when_elided && galley.elided {
+            if show_tooltip_when_elided && galley.elided.is_some() {
                 // Keep the sections and text, but reset everything else (especially wrapping):
                 let job = crate::text::LayoutJob {
                     sections: galley.job.sections.clone(),
@@ -321,8 +480,140 @@ impl Widget for Label {
                         .with_underline(underline),
                 );
             }
+
+            // Show which link has the keyboard focus:
+            for rect in focused_link_rects {
+                ui.painter().rect_stroke(
+                    rect,
+                    0.0,
+                    ui.visuals().selection.stroke,
+                    epaint::StrokeKind::Outside,
+                );
+            }
         }
 
-        response
+        for (section_index, rect) in placeholder_rects {
+            let mut child_ui = ui.new_child(
+                UiBuilder::new()
+                    .id_salt((response.id, section_index))
+                    .max_rect(rect.translate(galley_pos.to_vec2()))
+                    .layout(Layout::centered_and_justified(Direction::TopDown)),
+            );
+            add_placeholder_contents(&mut child_ui, section_index);
+        }
+
+        LabelOutput {
+            response,
+            galley_pos,
+            galley: output_galley,
+            hovered_section,
+            clicked_section,
+        }
     }
 }
+
+impl Widget for Label {
+    fn ui(self, ui: &mut Ui) -> Response {
+        self.show(ui).response
+    }
+}
+
+/// The result of [`Label::show`].
+pub struct LabelOutput {
+    /// The interaction response for the whole label.
+    pub response: Response,
+
+    /// Where the text in [`Self::galley`] ended up on the screen.
+    pub galley_pos: Pos2,
+
+    /// The laid out text.
+    pub galley: Arc<Galley>,
+
+    /// The index of the [`crate::text::LayoutSection`] under the mouse pointer, if any.
+    pub hovered_section: Option<usize>,
+
+    /// The index of the [`crate::text::LayoutSection`] that was clicked this frame, if any.
+    pub clicked_section: Option<usize>,
+}
+
+impl LabelOutput {
+    /// The [`crate::text::LayoutSection::link`] of the section that was clicked this frame, if any.
+    pub fn clicked_link(&self) -> Option<&str> {
+        let section = self.galley.job.sections.get(self.clicked_section?)?;
+        section.link.as_deref()
+    }
+
+    /// The [`crate::text::LayoutSection::link`] of the section under the mouse pointer, if any.
+    pub fn hovered_link(&self) -> Option<&str> {
+        let section = self.galley.job.sections.get(self.hovered_section?)?;
+        section.link.as_deref()
+    }
+}
+
+/// Which section of the job is at the given position, relative to the galley?
+fn section_at(galley: &Galley, pos: Vec2) -> Option<usize> {
+    // `cursor_from_pos` gives us the closest gap between two chars.
+    // Find out which of the chars on either side of it we are actually over:
+    let cursor = galley.cursor_from_pos(pos);
+    let char_index = [cursor.index.checked_sub(1), Some(cursor.index)]
+        .into_iter()
+        .flatten()
+        .find(|&index| {
+            let start = galley.pos_from_cursor(CCursor::new(index));
+            let end = galley.pos_from_cursor(CCursor {
+                index: index + 1,
+                prefer_next_row: false,
+            });
+            // Both sides must be on the same row, apart along it:
+            let same_row = if galley.is_vertical() {
+                start.x_range() == end.x_range() && start.min.y != end.min.y
+            } else {
+                start.y_range() == end.y_range() && start.min.x != end.min.x
+            };
+            same_row && start.union(end).contains(pos.to_pos2())
+        })?;
+
+    let (byte_index, _) = galley.text().char_indices().nth(char_index)?;
+    galley
+        .job
+        .sections
+        .iter()
+        .position(|section| section.byte_range.contains(&byte_index))
+}
+
+/// Give each link in the label its own AccessKit node, so screen readers can find them.
+///
+/// `link_sections` are the sections of the link, and `rects` its screen rects, one for each row.
+fn accesskit_link(
+    ui: &Ui,
+    response: &Response,
+    link_id: crate::Id,
+    galley: &Galley,
+    link_sections: std::ops::Range<usize>,
+    rects: &[Rect],
+) {
+    let sections = &galley.job.sections[link_sections];
+    let (Some(first), Some(last)) = (sections.first(), sections.last()) else {
+        return;
+    };
+    let Some(link) = &first.link else {
+        return;
+    };
+    let Some(rect) = rects.iter().copied().reduce(|a, b| a.union(b)) else {
+        return; // Elided
+    };
+
+    ui.ctx().register_accesskit_parent(link_id, response.id);
+    ui.ctx().accesskit_node_builder(link_id, |builder| {
+        builder.set_role(accesskit::Role::Link);
+        builder.set_label(&galley.job.text[first.byte_range.start..last.byte_range.end]);
+        builder.set_url(link.as_str());
+        builder.set_bounds(accesskit::Rect {
+            x0: rect.min.x.into(),
+            y0: rect.min.y.into(),
+            x1: rect.max.x.into(),
+            y1: rect.max.y.into(),
+        });
+        builder.add_action(accesskit::Action::Click);
+    });
+}
@@ -35,7 +35,7 @@ pub use self::{
         decode_animated_image_uri, has_gif_magic_header, has_webp_header, paint_texture_at,
     },
     image_button::ImageButton,
-    label::Label,
+    label::{Label, LabelOutput},
     progress_bar::ProgressBar,
     radio_button::RadioButton,
     separator::Separator,
@@ -3,7 +3,7 @@ use std::sync::Arc;
 use emath::{Rect, TSTransform};
 use epaint::{
     StrokeKind,
-    text::{Galley, LayoutJob, cursor::CCursor},
+    text::{Galley, LayoutJob, TabStops, cursor::CCursor},
 };
 
 use crate::{
@@ -89,6 +89,7 @@ pub struct TextEdit<'t> {
     char_limit: usize,
     return_key: Option<KeyboardShortcut>,
     background_color: Option<Color32>,
+    tab_stops: TabStops,
 }
 
 impl WidgetWithState for TextEdit<'_> {
@@ -148,6 +149,7 @@ impl<'t> TextEdit<'t> {
             char_limit: usize::MAX,
             return_key: Some(KeyboardShortcut::new(Modifiers::NONE, Key::Enter)),
             background_color: None,
+            tab_stops: TabStops::default(),
         }
     }
 
@@ -332,6 +334,19 @@ impl<'t> TextEdit<'t> {
         self
     }
 
+    /// Where the `'\t'` characters move the text, e.g. to edit tab-separated columns.
+    ///
+    /// By default a `'\t'` is as wide as [`crate::text::TAB_SIZE`] spaces.
+    /// Use together with [`Self::lock_focus`] to let the user type tabs.
+    ///
+    /// This is ignored if you use a custom [`Self::layouter`]:
+    /// set [`LayoutJob::tab_stops`] there instead.
+    #[inline]
+    pub fn tab_stops(mut self, tab_stops: TabStops) -> Self {
+        self.tab_stops = tab_stops;
+        self
+    }
+
     /// When `true` (default), the cursor will initially be placed at the end of the text.
     ///
     /// When `false`, the cursor will initially be placed at the beginning of the text.
@@ -493,6 +508,7 @@ impl TextEdit<'_> {
             char_limit,
             return_key,
             background_color: _,
+            tab_stops,
         } = self;
 
         let text_color = text_color
@@ -517,11 +533,12 @@ impl TextEdit<'_> {
         let font_id_clone = font_id.clone();
         let mut default_layouter = move |ui: &Ui, text: &dyn TextBuffer, wrap_width: f32| {
             let text = mask_if_password(password, text.as_str());
-            let layout_job = if multiline {
+            let mut layout_job = if multiline {
                 LayoutJob::simple(text, font_id_clone.clone(), text_color, wrap_width)
             } else {
                 LayoutJob::simple_singleline(text, font_id_clone.clone(), text_color)
             };
+            layout_job.tab_stops = tab_stops.clone();
             ui.fonts_mut(|f| f.layout_job(layout_job))
         };
 
@@ -76,10 +76,11 @@ impl crate::View for CodeEditor {
             });
         });
 
+        let highlighting_id = ui.id().with("syntax_highlighting");
         let mut layouter = |ui: &egui::Ui, buf: &dyn egui::TextBuffer, wrap_width: f32| {
-            let mut layout_job = egui_extras::syntax_highlighting::highlight(
+            let mut layout_job = egui_extras::syntax_highlighting::highlight_incremental(
                 ui.ctx(),
-                ui.style(),
+                highlighting_id,
                 &theme,
                 buf.as_str(),
                 language,
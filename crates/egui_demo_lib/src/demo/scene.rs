@@ -6,6 +6,7 @@ use super::widget_gallery;
 pub struct SceneDemo {
     widget_gallery: widget_gallery::WidgetGallery,
     scene_rect: Rect,
+    distance_field_text: bool,
 }
 
 impl Default for SceneDemo {
@@ -13,6 +14,7 @@ impl Default for SceneDemo {
         Self {
             widget_gallery: widget_gallery::WidgetGallery::default().with_date_button(false), // disable date button so that we don't fail the snapshot test
             scene_rect: Rect::ZERO, // `egui::Scene` will initialize this to something valid
+            distance_field_text: false,
         }
     }
 }
@@ -45,6 +47,8 @@ impl crate::View for SceneDemo {
         ui.separator();
 
         ui.label(format!("Scene rect: {:#?}", &mut self.scene_rect));
+        ui.checkbox(&mut self.distance_field_text, "Sharp text when zoomed in")
+            .on_hover_text("Render the text with signed distance fields");
 
         ui.separator();
 
@@ -59,6 +63,11 @@ impl crate::View for SceneDemo {
                 let mut inner_rect = Rect::NAN;
                 let response = scene
                     .show(ui, &mut self.scene_rect, |ui| {
+                        if self.distance_field_text {
+                            ui.style_mut().glyph_rendering =
+                                egui::text::GlyphRendering::DistanceField;
+                        }
+
                         reset_view = ui.button("Reset view").clicked();
 
                         ui.add_space(16.0);
@@ -37,6 +37,22 @@ pub(crate) struct StripLayoutFlags {
 
     /// Used when we want to accurately measure the size of this cell.
     pub(crate) sizing_pass: bool,
+
+    /// The cell can receive keyboard focus.
+    pub(crate) focusable: bool,
+}
+
+/// Where a table cell goes in the AccessKit tree.
+#[derive(Clone, Copy)]
+pub(crate) struct CellAccessibility {
+    /// The AccessKit node of the row the cell is in.
+    pub(crate) row_id: Id,
+
+    /// [`egui::accesskit::Role::Cell`] or [`egui::accesskit::Role::ColumnHeader`].
+    pub(crate) role: egui::accesskit::Role,
+
+    pub(crate) row_index: usize,
+    pub(crate) col_index: usize,
 }
 
 /// Positions cells in [`CellDirection`] and starts a new line on [`StripLayout::end_line`]
@@ -118,6 +134,7 @@ impl<'l> StripLayout<'l> {
         width: CellSize,
         height: CellSize,
         child_ui_id_salt: Id,
+        accessibility: Option<CellAccessibility>,
         add_cell_contents: impl FnOnce(&mut Ui),
     ) -> (Rect, Response) {
         let max_rect = self.cell_rect(&width, &height);
@@ -150,7 +167,13 @@ impl<'l> StripLayout<'l> {
             );
         }
 
-        let mut child_ui = self.cell(flags, max_rect, child_ui_id_salt, add_cell_contents);
+        let mut child_ui = self.cell(
+            flags,
+            max_rect,
+            child_ui_id_salt,
+            accessibility,
+            add_cell_contents,
+        );
 
         let used_rect = child_ui.min_rect();
 
@@ -202,20 +225,45 @@ impl<'l> StripLayout<'l> {
         flags: StripLayoutFlags,
         max_rect: Rect,
         child_ui_id_salt: egui::Id,
+        accessibility: Option<CellAccessibility>,
         add_cell_contents: impl FnOnce(&mut Ui),
     ) -> Ui {
-        let mut ui_builder = UiBuilder::new()
-            .id_salt(child_ui_id_salt)
+        let sense = if flags.focusable {
+            self.sense | Sense::FOCUSABLE
+        } else {
+            self.sense
+        };
+        let ui_builder = if flags.focusable {
+            // The caller gave us a globally unique id, which stays the same even if
+            // the cells before this one change (e.g. when scrolling a table):
+            UiBuilder::new().id(child_ui_id_salt)
+        } else {
+            UiBuilder::new().id_salt(child_ui_id_salt)
+        };
+        let mut ui_builder = ui_builder
             .ui_stack_info(egui::UiStackInfo::new(egui::UiKind::TableCell))
             .max_rect(max_rect)
             .layout(self.cell_layout)
-            .sense(self.sense);
+            .sense(sense);
         if flags.sizing_pass {
             ui_builder = ui_builder.sizing_pass();
         }
+        if let Some(accessibility) = accessibility {
+            ui_builder = ui_builder.accessibility_parent(accessibility.row_id);
+        }
 
         let mut child_ui = self.ui.new_child(ui_builder);
 
+        if let Some(accessibility) = accessibility {
+            child_ui
+                .ctx()
+                .accesskit_node_builder(child_ui.unique_id(), |node| {
+                    node.set_role(accessibility.role);
+                    node.set_row_index(accessibility.row_index);
+                    node.set_column_index(accessibility.col_index);
+                });
+        }
+
         if flags.clip {
             let margin = egui::Vec2::splat(self.ui.visuals().clip_rect_margin);
             let margin = margin.min(0.5 * self.ui.spacing().item_spacing);
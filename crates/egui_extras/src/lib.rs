@@ -18,9 +18,13 @@ pub mod syntax_highlighting;
 pub mod image;
 mod layout;
 pub mod loaders;
+#[cfg(feature = "markdown")]
+pub mod markdown;
 mod sizing;
 mod strip;
 mod table;
+#[cfg(feature = "theme")]
+pub mod theme;
 
 #[cfg(feature = "chrono")]
 pub use crate::datepicker::DatePickerButton;
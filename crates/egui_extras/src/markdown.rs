@@ -0,0 +1,1183 @@
+//! Show [CommonMark](https://commonmark.org) markdown, with GitHub-flavored tables and task lists.
+//!
+//! Turn on the `markdown` feature to use this module.
+//!
+//! ```
+//! # egui::__run_test_ui(|ui| {
+//! use egui_extras::markdown::MarkdownViewer;
+//!
+//! let source = "# Hello\n\nSome *emphasized* text, and a [link](https://www.egui.rs).";
+//!
+//! let viewer = MarkdownViewer::new("readme");
+//! viewer.show_table_of_contents(ui, source);
+//! egui::ScrollArea::vertical().show(ui, |ui| {
+//!     viewer.show(ui, source);
+//! });
+//! # });
+//! ```
+//!
+//! Code blocks are highlighted with [`crate::syntax_highlighting`],
+//! and images are loaded with whatever loaders are installed
+//! (see [`crate::install_image_loaders`]).
+//! All text is selectable, and a selection can span the whole document.
+//! Raw HTML is left out, except for the text between inline tags, e.g. `<kbd>Ctrl</kbd>`.
+
+use std::sync::Arc;
+
+use egui::{
+    Align, FontFamily, FontId, Id, Layout, Margin, OpenUrl, Stroke, TextStyle, TextWrapMode, Ui,
+    Vec2,
+    text::{LayoutJob, TextFormat},
+};
+use pulldown_cmark::{Alignment, CodeBlockKind, Event, Options, Parser, Tag, TagEnd};
+
+use crate::syntax_highlighting::{CodeTheme, code_view_ui};
+
+/// A heading in a markdown document, as listed by [`headings`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Heading {
+    /// 1 for `#`, up to 6 for `######`.
+    pub level: u8,
+
+    /// The text of the heading, without any formatting.
+    pub text: String,
+
+    /// The anchor of the heading, which `[links](#anchor)` in the document can point to.
+    ///
+    /// Either the id given with `# Heading {#id}`, or a GitHub-style slug of the text,
+    /// e.g. `getting-started` for `## Getting Started`.
+    pub anchor: String,
+}
+
+/// List the headings of a markdown document, e.g. to build your own table of contents.
+pub fn headings(source: &str) -> Vec<Heading> {
+    Document::parse(source).headings
+}
+
+/// Shows markdown in a [`Ui`].
+///
+/// The viewer has no state of its own, so you can create it anew each frame.
+pub struct MarkdownViewer {
+    id: Id,
+    base_uri: Option<String>,
+    code_theme: Option<CodeTheme>,
+}
+
+impl MarkdownViewer {
+    /// The id is global (not relative to the [`Ui`]), so that the table of contents
+    /// can be shown in a different part of the UI than the document.
+    pub fn new(id_salt: impl std::hash::Hash) -> Self {
+        Self {
+            id: Id::new(id_salt),
+            base_uri: None,
+            code_theme: None,
+        }
+    }
+
+    /// Relative image paths and links are resolved against this, e.g. `file://docs` or `https://example.com/docs`.
+    #[inline]
+    pub fn base_uri(mut self, base_uri: impl Into<String>) -> Self {
+        self.base_uri = Some(base_uri.into());
+        self
+    }
+
+    /// The theme for code blocks.
+    ///
+    /// Defaults to [`CodeTheme::from_style`].
+    #[inline]
+    pub fn code_theme(mut self, code_theme: CodeTheme) -> Self {
+        self.code_theme = Some(code_theme);
+        self
+    }
+
+    /// Show the document.
+    ///
+    /// The parsed document is memoized, so you can call this every frame without performance penalty.
+    /// Put it in a [`egui::ScrollArea`] to be able to jump to headings.
+    pub fn show(&self, ui: &mut Ui, source: &str) -> egui::Response {
+        let document = parse_cached(ui.ctx(), source);
+        let scroll_to = ui
+            .data(|data| data.get_temp::<State>(self.id))
+            .unwrap_or_default()
+            .scroll_to;
+
+        let code_theme = self
+            .code_theme
+            .clone()
+            .unwrap_or_else(|| CodeTheme::from_style(ui.style()));
+        let mut renderer = Renderer {
+            viewer: self,
+            document: &document,
+            code_theme,
+            scroll_to,
+            list_depth: 0,
+            table_count: 0,
+        };
+        ui.vertical(|ui| renderer.blocks(ui, &document.blocks))
+            .response
+    }
+
+    /// Show links to all headings of the document, indented by level.
+    ///
+    /// Clicking one scrolls the document shown with [`Self::show`] to that heading.
+    pub fn show_table_of_contents(&self, ui: &mut Ui, source: &str) -> egui::Response {
+        let document = parse_cached(ui.ctx(), source);
+        let top_level = document.headings.iter().map(|h| h.level).min().unwrap_or(1);
+        ui.vertical(|ui| {
+            for heading in &document.headings {
+                ui.horizontal(|ui| {
+                    ui.add_space(f32::from(heading.level - top_level) * ui.spacing().indent);
+                    if ui.link(&heading.text).clicked() {
+                        self.scroll_to_heading(ui.ctx(), &heading.anchor);
+                    }
+                });
+            }
+        })
+        .response
+    }
+
+    /// Scroll the document to the heading with the given [`Heading::anchor`] the next time it is shown.
+    ///
+    /// Footnotes can be scrolled to as well, with the anchor `fn-label` for `[^label]`.
+    pub fn scroll_to_heading(&self, ctx: &egui::Context, anchor: &str) {
+        ctx.data_mut(|data| {
+            data.insert_temp(
+                self.id,
+                State {
+                    scroll_to: Some(anchor.to_owned()),
+                },
+            );
+        });
+        ctx.request_repaint();
+    }
+
+    fn resolve_uri(&self, uri: &str) -> String {
+        match &self.base_uri {
+            Some(base) if !has_scheme(uri) => {
+                format!(
+                    "{}/{}",
+                    base.trim_end_matches('/'),
+                    uri.trim_start_matches("./")
+                )
+            }
+            _ => uri.to_owned(),
+        }
+    }
+
+    /// Like [`Self::resolve_uri`], but `#anchor` links are kept as they are.
+    fn resolve_link(&self, link: &str) -> String {
+        if link.starts_with('#') {
+            link.to_owned()
+        } else {
+            self.resolve_uri(link)
+        }
+    }
+}
+
+/// Does the URI start with a scheme, like `https:`, `mailto:` or `data:`?
+fn has_scheme(uri: &str) -> bool {
+    uri.split_once(':').is_some_and(|(scheme, _)| {
+        scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+    })
+}
+
+/// The anchor of the footnote `[^label]`, see [`MarkdownViewer::scroll_to_heading`].
+fn footnote_anchor(label: &str) -> String {
+    format!("fn-{label}")
+}
+
+#[derive(Clone, Default)]
+struct State {
+    /// The anchor of the heading to scroll to.
+    scroll_to: Option<String>,
+}
+
+fn parse_cached(ctx: &egui::Context, source: &str) -> Arc<Document> {
+    #[derive(Default)]
+    struct DocumentParser {}
+
+    impl egui::cache::ComputerMut<&str, Arc<Document>> for DocumentParser {
+        fn compute(&mut self, source: &str) -> Arc<Document> {
+            Arc::new(Document::parse(source))
+        }
+    }
+
+    type DocumentCache = egui::cache::FrameCache<Arc<Document>, DocumentParser>;
+
+    ctx.memory_mut(|mem| mem.caches.cache::<DocumentCache>().get(source))
+}
+
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, PartialEq)]
+struct Document {
+    blocks: Vec<Block>,
+    headings: Vec<Heading>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Block {
+    Paragraph(Vec<Inline>),
+    Heading {
+        /// Index into [`Document::headings`].
+        index: usize,
+        content: Vec<Inline>,
+    },
+    Quote(Vec<Block>),
+    Code {
+        language: String,
+        code: String,
+    },
+    List {
+        /// The number of the first item of an ordered list.
+        start: Option<u64>,
+        items: Vec<ListItem>,
+    },
+    Table {
+        alignments: Vec<Alignment>,
+        head: Vec<Vec<Inline>>,
+        rows: Vec<Vec<Vec<Inline>>>,
+    },
+    Rule,
+    Footnote {
+        label: String,
+        blocks: Vec<Block>,
+    },
+}
+
+#[derive(Debug, PartialEq)]
+struct ListItem {
+    /// `Some` for task list items, `true` if checked.
+    checked: Option<bool>,
+    blocks: Vec<Block>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Inline {
+    Text {
+        text: String,
+        style: SpanStyle,
+        link: Option<String>,
+    },
+    Image {
+        url: String,
+        title: String,
+        alt: String,
+
+        /// Set for images inside a link, e.g. `[![alt](image.png)](url)`.
+        link: Option<String>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct SpanStyle {
+    strong: bool,
+    emphasis: bool,
+    strikethrough: bool,
+    code: bool,
+}
+
+impl Document {
+    fn parse(source: &str) -> Self {
+        profiling::function_scope!();
+        let options = Options::ENABLE_TABLES
+            | Options::ENABLE_TASKLISTS
+            | Options::ENABLE_STRIKETHROUGH
+            | Options::ENABLE_HEADING_ATTRIBUTES
+            | Options::ENABLE_FOOTNOTES;
+        let mut builder = Builder {
+            events: Parser::new_ext(source, options),
+            headings: Vec::new(),
+            depth: 0,
+            strong: 0,
+            emphasis: 0,
+            strikethrough: 0,
+            link: None,
+            image: None,
+            task_marker: None,
+        };
+        let blocks = builder.blocks();
+        Self {
+            blocks,
+            headings: builder.headings,
+        }
+    }
+}
+
+/// Turns the flat stream of events from [`Parser`] into a tree of [`Block`]s.
+struct Builder<'a> {
+    events: Parser<'a>,
+    headings: Vec<Heading>,
+
+    /// Nesting depth of the blocks, e.g. quotes in quotes.
+    depth: usize,
+
+    // Nesting depth of the inline styles:
+    strong: usize,
+    emphasis: usize,
+    strikethrough: usize,
+
+    link: Option<String>,
+
+    /// The image we are reading the alt text of.
+    image: Option<Inline>,
+
+    /// The task list marker of the innermost list item.
+    task_marker: Option<bool>,
+}
+
+impl Builder<'_> {
+    /// Blocks nested deeper than this are shown as plain text,
+    /// so that a malicious document can't make us overflow the stack.
+    const MAX_DEPTH: usize = 32;
+
+    fn style(&self) -> SpanStyle {
+        SpanStyle {
+            strong: self.strong > 0,
+            emphasis: self.emphasis > 0,
+            strikethrough: self.strikethrough > 0,
+            code: false,
+        }
+    }
+
+    /// Read blocks until the end of the enclosing block (or document).
+    fn blocks(&mut self) -> Vec<Block> {
+        if Self::MAX_DEPTH <= self.depth {
+            let text = self.flat_text();
+            return vec![Block::Paragraph(vec![Inline::Text {
+                text,
+                style: SpanStyle::default(),
+                link: None,
+            }])];
+        }
+
+        self.depth += 1;
+        let blocks = self.blocks_impl();
+        self.depth -= 1;
+        blocks
+    }
+
+    fn blocks_impl(&mut self) -> Vec<Block> {
+        let mut blocks = Vec::new();
+
+        // Tight list items have their text directly in the item, without a paragraph:
+        let mut loose_inlines = Vec::new();
+
+        while let Some(event) = self.events.next() {
+            if is_inline(&event) {
+                self.inline(event, &mut loose_inlines);
+                continue;
+            }
+            if !loose_inlines.is_empty() {
+                blocks.push(Block::Paragraph(std::mem::take(&mut loose_inlines)));
+            }
+
+            match event {
+                Event::End(_) => break,
+                Event::Start(Tag::Paragraph) => blocks.push(Block::Paragraph(self.inlines())),
+                Event::Start(Tag::Heading { level, id, .. }) => {
+                    let content = self.inlines();
+                    let text = plain_text(&content);
+                    let anchor = match id {
+                        Some(id) => id.to_string(),
+                        None => self.unique_slug(&text),
+                    };
+                    self.headings.push(Heading {
+                        level: level as u8,
+                        text,
+                        anchor,
+                    });
+                    blocks.push(Block::Heading {
+                        index: self.headings.len() - 1,
+                        content,
+                    });
+                }
+                Event::Start(Tag::BlockQuote(_)) => blocks.push(Block::Quote(self.blocks())),
+                Event::Start(Tag::CodeBlock(kind)) => {
+                    let language = match kind {
+                        CodeBlockKind::Fenced(info) => info
+                            .split_whitespace()
+                            .next()
+                            .unwrap_or_default()
+                            .to_owned(),
+                        CodeBlockKind::Indented => String::new(),
+                    };
+                    let mut code = self.raw_text();
+                    if code.ends_with('\n') {
+                        code.pop();
+                    }
+                    blocks.push(Block::Code { language, code });
+                }
+                Event::Start(Tag::HtmlBlock) => {
+                    self.raw_text(); // Raw HTML is not shown
+                }
+                Event::Start(Tag::List(start)) => {
+                    let mut items = Vec::new();
+                    while let Some(event) = self.events.next() {
+                        match event {
+                            Event::Start(Tag::Item) => items.push(self.list_item()),
+                            Event::End(_) => break,
+                            _ => {}
+                        }
+                    }
+                    blocks.push(Block::List { start, items });
+                }
+                Event::Start(Tag::Table(alignments)) => blocks.push(self.table(alignments)),
+                Event::Start(Tag::FootnoteDefinition(label)) => blocks.push(Block::Footnote {
+                    label: label.to_string(),
+                    blocks: self.blocks(),
+                }),
+                Event::Start(_) => {
+                    // Something we don't support: show its contents.
+                    blocks.extend(self.blocks());
+                }
+                Event::Rule => blocks.push(Block::Rule),
+                _ => {}
+            }
+        }
+
+        if !loose_inlines.is_empty() {
+            blocks.push(Block::Paragraph(loose_inlines));
+        }
+        blocks
+    }
+
+    fn list_item(&mut self) -> ListItem {
+        // Nested items must not steal the marker of their parent:
+        let parent_marker = self.task_marker.take();
+        let blocks = self.blocks();
+        let checked = std::mem::replace(&mut self.task_marker, parent_marker);
+        ListItem { checked, blocks }
+    }
+
+    fn table(&mut self, alignments: Vec<Alignment>) -> Block {
+        let mut head = Vec::new();
+        let mut rows = Vec::new();
+        while let Some(event) = self.events.next() {
+            match event {
+                Event::Start(Tag::TableHead) => head = self.table_row(),
+                Event::Start(Tag::TableRow) => rows.push(self.table_row()),
+                Event::End(_) => break,
+                _ => {}
+            }
+        }
+        Block::Table {
+            alignments,
+            head,
+            rows,
+        }
+    }
+
+    fn table_row(&mut self) -> Vec<Vec<Inline>> {
+        let mut cells = Vec::new();
+        while let Some(event) = self.events.next() {
+            match event {
+                Event::Start(Tag::TableCell) => cells.push(self.inlines()),
+                Event::End(_) => break,
+                _ => {}
+            }
+        }
+        cells
+    }
+
+    /// Read inline content until the end of the enclosing block.
+    fn inlines(&mut self) -> Vec<Inline> {
+        let mut inlines = Vec::new();
+        while let Some(event) = self.events.next() {
+            if is_inline(&event) {
+                self.inline(event, &mut inlines);
+            } else if matches!(event, Event::End(_)) {
+                break;
+            }
+        }
+        inlines
+    }
+
+    /// Read the text of a code or HTML block.
+    fn raw_text(&mut self) -> String {
+        let mut text = String::new();
+        for event in self.events.by_ref() {
+            match event {
+                Event::Text(part) | Event::Html(part) => text += &part,
+                Event::End(_) => break,
+                _ => {}
+            }
+        }
+        text
+    }
+
+    /// Read the text until the end of the enclosing block, ignoring any structure.
+    fn flat_text(&mut self) -> String {
+        let mut text = String::new();
+        let mut depth = 0_usize;
+        for event in self.events.by_ref() {
+            match event {
+                Event::Start(_) => depth += 1,
+                Event::End(_) => {
+                    if depth == 0 {
+                        break;
+                    }
+                    depth -= 1;
+                }
+                Event::Text(part) | Event::Code(part) => text += &part,
+                Event::SoftBreak | Event::HardBreak => text.push(' '),
+                _ => {}
+            }
+        }
+        text
+    }
+
+    fn inline(&mut self, event: Event<'_>, out: &mut Vec<Inline>) {
+        let text = match event {
+            Event::Start(Tag::Strong) => {
+                self.strong += 1;
+                return;
+            }
+            Event::End(TagEnd::Strong) => {
+                self.strong = self.strong.saturating_sub(1);
+                return;
+            }
+            Event::Start(Tag::Emphasis) => {
+                self.emphasis += 1;
+                return;
+            }
+            Event::End(TagEnd::Emphasis) => {
+                self.emphasis = self.emphasis.saturating_sub(1);
+                return;
+            }
+            Event::Start(Tag::Strikethrough) => {
+                self.strikethrough += 1;
+                return;
+            }
+            Event::End(TagEnd::Strikethrough) => {
+                self.strikethrough = self.strikethrough.saturating_sub(1);
+                return;
+            }
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                self.link = Some(dest_url.to_string());
+                return;
+            }
+            Event::End(TagEnd::Link) => {
+                self.link = None;
+                return;
+            }
+            Event::Start(Tag::Image {
+                dest_url, title, ..
+            }) => {
+                self.image = Some(Inline::Image {
+                    url: dest_url.to_string(),
+                    title: title.to_string(),
+                    alt: String::new(),
+                    link: self.link.clone(),
+                });
+                return;
+            }
+            Event::End(TagEnd::Image) => {
+                out.extend(self.image.take());
+                return;
+            }
+            Event::TaskListMarker(checked) => {
+                self.task_marker = Some(checked);
+                return;
+            }
+            Event::FootnoteReference(label) => {
+                out.push(Inline::Text {
+                    text: format!("[{label}]"),
+                    style: self.style(),
+                    link: Some(format!("#{}", footnote_anchor(&label))),
+                });
+                return;
+            }
+            Event::Text(text) => text.to_string(),
+            Event::Code(text) => {
+                out.push(Inline::Text {
+                    text: text.to_string(),
+                    style: SpanStyle {
+                        code: true,
+                        ..self.style()
+                    },
+                    link: self.link.clone(),
+                });
+                return;
+            }
+            Event::SoftBreak => " ".to_owned(),
+            Event::HardBreak => "\n".to_owned(),
+            _ => return,
+        };
+
+        if let Some(Inline::Image { alt, .. }) = &mut self.image {
+            *alt += &text;
+            return;
+        }
+
+        // Merge with the previous span if it looks the same:
+        let style = self.style();
+        if let Some(Inline::Text {
+            text: previous,
+            style: previous_style,
+            link: previous_link,
+        }) = out.last_mut()
+        {
+            if *previous_style == style && *previous_link == self.link {
+                *previous += &text;
+                return;
+            }
+        }
+        out.push(Inline::Text {
+            text,
+            style,
+            link: self.link.clone(),
+        });
+    }
+
+    /// A GitHub-style anchor for a heading, unique within the document.
+    fn unique_slug(&self, text: &str) -> String {
+        let slug: String = text
+            .trim()
+            .chars()
+            .filter_map(|c| {
+                if c.is_alphanumeric() || c == '-' || c == '_' {
+                    Some(c.to_lowercase().next().unwrap_or(c))
+                } else if c == ' ' {
+                    Some('-')
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        let is_taken = |anchor: &str| self.headings.iter().any(|h| h.anchor == anchor);
+        if !is_taken(&slug) {
+            return slug;
+        }
+        (1..)
+            .map(|i| format!("{slug}-{i}"))
+            .find(|anchor| !is_taken(anchor))
+            .unwrap_or(slug)
+    }
+}
+
+fn is_inline(event: &Event<'_>) -> bool {
+    match event {
+        Event::Start(tag) => matches!(
+            tag,
+            Tag::Emphasis
+                | Tag::Strong
+                | Tag::Strikethrough
+                | Tag::Superscript
+                | Tag::Subscript
+                | Tag::Link { .. }
+                | Tag::Image { .. }
+        ),
+        Event::End(tag) => matches!(
+            tag,
+            TagEnd::Emphasis
+                | TagEnd::Strong
+                | TagEnd::Strikethrough
+                | TagEnd::Superscript
+                | TagEnd::Subscript
+                | TagEnd::Link
+                | TagEnd::Image
+        ),
+        Event::Text(_)
+        | Event::Code(_)
+        | Event::InlineMath(_)
+        | Event::InlineHtml(_)
+        | Event::FootnoteReference(_)
+        | Event::SoftBreak
+        | Event::HardBreak
+        | Event::TaskListMarker(_) => true,
+        Event::DisplayMath(_) | Event::Html(_) | Event::Rule => false,
+    }
+}
+
+fn plain_text(inlines: &[Inline]) -> String {
+    inlines
+        .iter()
+        .map(|inline| match inline {
+            Inline::Text { text, .. } => text.as_str(),
+            Inline::Image { alt, .. } => alt.as_str(),
+        })
+        .collect()
+}
+
+// ----------------------------------------------------------------------------
+
+struct Renderer<'a> {
+    viewer: &'a MarkdownViewer,
+    document: &'a Document,
+    code_theme: CodeTheme,
+
+    /// The anchor of the heading to scroll to, if any.
+    scroll_to: Option<String>,
+
+    list_depth: usize,
+
+    /// Used to give each table a unique id.
+    table_count: usize,
+}
+
+impl Renderer<'_> {
+    fn blocks(&mut self, ui: &mut Ui, blocks: &[Block]) {
+        for (i, block) in blocks.iter().enumerate() {
+            if i > 0 {
+                ui.add_space(0.5 * ui.text_style_height(&TextStyle::Body));
+            }
+            self.block(ui, block);
+        }
+    }
+
+    fn block(&mut self, ui: &mut Ui, block: &Block) {
+        match block {
+            Block::Paragraph(inlines) => {
+                let format = body_format(ui);
+                self.paragraph(ui, inlines, &format);
+            }
+            Block::Heading { index, content } => self.heading(ui, *index, content),
+            Block::Quote(blocks) => {
+                let response = egui::Frame::new()
+                    .inner_margin(Margin {
+                        left: 12,
+                        ..Margin::ZERO
+                    })
+                    .show(ui, |ui| self.blocks(ui, blocks))
+                    .response;
+                let rect = response.rect;
+                ui.painter().vline(
+                    rect.left() + 3.0,
+                    rect.y_range(),
+                    Stroke::new(3.0, ui.visuals().widgets.noninteractive.bg_stroke.color),
+                );
+            }
+            Block::Code { language, code } => self.code_block(ui, code, language),
+            Block::List { start, items } => self.list(ui, *start, items),
+            Block::Table {
+                alignments,
+                head,
+                rows,
+            } => self.table(ui, alignments, head, rows),
+            Block::Rule => {
+                ui.separator();
+            }
+            Block::Footnote { label, blocks } => self.footnote(ui, label, blocks),
+        }
+    }
+
+    fn footnote(&mut self, ui: &mut Ui, label: &str, blocks: &[Block]) {
+        let response = ui
+            .with_layout(Layout::left_to_right(Align::TOP), |ui| {
+                ui.label(format!("[{label}]"));
+                ui.vertical(|ui| self.blocks(ui, blocks));
+            })
+            .response;
+        self.scroll_to_if_requested(ui, &response, &footnote_anchor(label));
+    }
+
+    /// Scroll to the response if it is what [`MarkdownViewer::scroll_to_heading`] asked for.
+    fn scroll_to_if_requested(&mut self, ui: &Ui, response: &egui::Response, anchor: &str) {
+        if self.scroll_to.as_deref() == Some(anchor) {
+            response.scroll_to_me(Some(Align::TOP));
+            self.scroll_to = None;
+            ui.data_mut(|data| data.remove::<State>(self.viewer.id));
+        }
+    }
+
+    fn heading(&mut self, ui: &mut Ui, index: usize, content: &[Inline]) {
+        let heading = &self.document.headings[index];
+        let body_size = TextStyle::Body.resolve(ui.style()).size;
+        let scale = match heading.level {
+            1 => 2.0,
+            2 => 1.6,
+            3 => 1.3,
+            4 => 1.15,
+            5 => 1.0,
+            _ => 0.9,
+        };
+        let format = TextFormat {
+            font_id: FontId::proportional(body_size * scale),
+            color: ui.visuals().strong_text_color(),
+            ..Default::default()
+        };
+
+        let response = ui
+            .scope(|ui| {
+                self.paragraph(ui, content, &format);
+                if heading.level <= 2 {
+                    ui.separator();
+                }
+            })
+            .response;
+
+        self.scroll_to_if_requested(ui, &response, &heading.anchor);
+    }
+
+    /// Show text, with any images on their own lines.
+    fn paragraph(&self, ui: &mut Ui, inlines: &[Inline], format: &TextFormat) {
+        let mut job = LayoutJob::default();
+        for inline in inlines {
+            match inline {
+                Inline::Text { text, style, link } => {
+                    let format = span_format(ui, format, *style, link.is_some());
+                    match link {
+                        Some(link) => {
+                            job.append_link(text, format, self.viewer.resolve_link(link));
+                        }
+                        None => job.append(text, 0.0, format),
+                    }
+                }
+                Inline::Image {
+                    url,
+                    title,
+                    alt,
+                    link,
+                } => {
+                    self.text(ui, std::mem::take(&mut job));
+                    let mut image = egui::Image::new(self.viewer.resolve_uri(url))
+                        .max_width(ui.available_width())
+                        .fit_to_original_size(1.0)
+                        .alt_text(alt.clone());
+                    if link.is_some() {
+                        image = image.sense(egui::Sense::click());
+                    }
+                    let mut response = ui.add(image);
+                    let link = link.as_deref().map(|link| self.viewer.resolve_link(link));
+                    if let Some(link) = &link {
+                        response = response.on_hover_cursor(egui::CursorIcon::PointingHand);
+                        if response.clicked() {
+                            self.open_link(ui, link, response.clicked_with_open_in_background());
+                        }
+                    }
+                    if !title.is_empty() {
+                        response.on_hover_text(title);
+                    } else if let Some(link) = &link
+                        && ui.style().url_in_tooltip
+                    {
+                        response.on_hover_text(link);
+                    }
+                }
+            }
+        }
+        self.text(ui, job);
+    }
+
+    fn text(&self, ui: &mut Ui, job: LayoutJob) {
+        if job.is_empty() {
+            return;
+        }
+        let output = egui::Label::new(job).selectable(true).wrap().show(ui);
+        if let Some(link) = output.clicked_link() {
+            self.open_link(ui, link, output.response.clicked_with_open_in_background());
+        }
+        if ui.style().url_in_tooltip {
+            if let Some(link) = output.hovered_link().map(ToOwned::to_owned) {
+                output.response.on_hover_text(link);
+            }
+        }
+    }
+
+    /// Scroll to the heading of an `#anchor` link, or open any other link.
+    fn open_link(&self, ui: &Ui, link: &str, new_tab: bool) {
+        if let Some(anchor) = link.strip_prefix('#') {
+            self.viewer.scroll_to_heading(ui.ctx(), anchor);
+        } else {
+            ui.ctx().open_url(OpenUrl {
+                url: link.to_owned(),
+                new_tab,
+            });
+        }
+    }
+
+    fn code_block(&self, ui: &mut Ui, code: &str, language: &str) {
+        egui::Frame::new()
+            .fill(ui.visuals().code_bg_color)
+            .corner_radius(ui.visuals().widgets.noninteractive.corner_radius)
+            .inner_margin(6)
+            .show(ui, |ui| {
+                ui.set_min_width(ui.available_width());
+                code_view_ui(ui, &self.code_theme, code, language);
+            });
+    }
+
+    fn list(&mut self, ui: &mut Ui, start: Option<u64>, items: &[ListItem]) {
+        let marker_width = 1.5 * ui.text_style_height(&TextStyle::Body);
+        self.list_depth += 1;
+        for (i, item) in items.iter().enumerate() {
+            ui.with_layout(Layout::left_to_right(Align::TOP), |ui| {
+                ui.allocate_ui_with_layout(
+                    Vec2::new(marker_width, 0.0),
+                    Layout::right_to_left(Align::TOP),
+                    |ui| {
+                        ui.set_min_width(marker_width);
+                        if let Some(mut checked) = item.checked {
+                            ui.add_enabled(false, egui::Checkbox::without_text(&mut checked));
+                        } else if let Some(start) = start {
+                            ui.label(format!("{}.", start + i as u64));
+                        } else {
+                            ui.label(match self.list_depth {
+                                1 => "•",
+                                2 => "◦",
+                                _ => "▪",
+                            });
+                        }
+                    },
+                );
+                ui.vertical(|ui| self.blocks(ui, &item.blocks));
+            });
+        }
+        self.list_depth -= 1;
+    }
+
+    fn table(
+        &mut self,
+        ui: &mut Ui,
+        alignments: &[Alignment],
+        head: &[Vec<Inline>],
+        rows: &[Vec<Vec<Inline>>],
+    ) {
+        self.table_count += 1;
+        let body = body_format(ui);
+        let strong = TextFormat {
+            color: ui.visuals().strong_text_color(),
+            ..body.clone()
+        };
+
+        // Measure the columns up front, so that we can align the cells within them:
+        let num_columns = alignments.len();
+        let max_width = ui.available_width() / num_columns.max(1) as f32;
+        let mut widths = vec![0.0_f32; num_columns];
+        let all_rows = std::iter::once((head, &strong)).chain(rows.iter().map(|r| (&r[..], &body)));
+        for (row, format) in all_rows.clone() {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                let galley = egui::WidgetText::from(cell_job(ui, cell, format)).into_galley(
+                    ui,
+                    Some(TextWrapMode::Extend),
+                    f32::INFINITY,
+                    TextStyle::Body,
+                );
+                *width = width.max(galley.size().x.min(max_width));
+            }
+        }
+
+        egui::Grid::new(self.viewer.id.with(("table", self.table_count)))
+            .striped(true)
+            .show(ui, |ui| {
+                for (row, format) in all_rows {
+                    for ((cell, width), alignment) in row.iter().zip(&widths).zip(alignments) {
+                        let layout = match alignment {
+                            Alignment::Center => Layout::top_down(Align::Center),
+                            Alignment::Right => Layout::top_down(Align::Max),
+                            Alignment::None | Alignment::Left => Layout::top_down(Align::Min),
+                        };
+                        ui.allocate_ui_with_layout(Vec2::new(*width, 0.0), layout, |ui| {
+                            ui.set_width(*width);
+                            self.paragraph(ui, cell, format);
+                        });
+                    }
+                    ui.end_row();
+                }
+            });
+    }
+}
+
+fn body_format(ui: &Ui) -> TextFormat {
+    TextFormat {
+        font_id: TextStyle::Body.resolve(ui.style()),
+        color: ui.visuals().text_color(),
+        ..Default::default()
+    }
+}
+
+fn cell_job(ui: &Ui, cell: &[Inline], format: &TextFormat) -> LayoutJob {
+    let mut job = LayoutJob::default();
+    for inline in cell {
+        if let Inline::Text { text, style, link } = inline {
+            job.append(text, 0.0, span_format(ui, format, *style, link.is_some()));
+        }
+    }
+    job
+}
+
+fn span_format(ui: &Ui, base: &TextFormat, style: SpanStyle, is_link: bool) -> TextFormat {
+    let visuals = ui.visuals();
+    let mut format = base.clone();
+    if style.strong {
+        format.color = visuals.strong_text_color();
+        format.font_id.variations.weight = Some(egui::FontVariations::BOLD);
+    }
+    if style.emphasis {
+        format.italics = true;
+    }
+    if style.code {
+        format.font_id.family = FontFamily::Monospace;
+        format.background = visuals.code_bg_color;
+    }
+    if is_link {
+        format.color = visuals.hyperlink_color;
+    }
+    if style.strikethrough {
+        format.strikethrough = Stroke::new(1.0, format.color);
+    }
+    format
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(text: &str) -> Inline {
+        Inline::Text {
+            text: text.to_owned(),
+            style: SpanStyle::default(),
+            link: None,
+        }
+    }
+
+    #[test]
+    fn heading_anchors() {
+        let headings =
+            headings("# Getting Started\n\n## Usage\n\n## Usage\n\n### Custom {#my-id}\n");
+        let anchors: Vec<_> = headings.iter().map(|h| h.anchor.as_str()).collect();
+        assert_eq!(anchors, ["getting-started", "usage", "usage-1", "my-id"]);
+        assert_eq!(headings[3].level, 3);
+        assert_eq!(headings[3].text, "Custom");
+    }
+
+    #[test]
+    fn task_list() {
+        let document = Document::parse("- [x] done\n- [ ] todo\n  - nested\n- plain\n");
+        let [Block::List { start: None, items }] = &document.blocks[..] else {
+            panic!("Expected a single list, got {:?}", document.blocks);
+        };
+        let checked: Vec<_> = items.iter().map(|item| item.checked).collect();
+        assert_eq!(checked, [Some(true), Some(false), None]);
+        assert_eq!(items[0].blocks, [Block::Paragraph(vec![text("done")])]);
+
+        let Block::List { items: nested, .. } = &items[1].blocks[1] else {
+            panic!("Expected a nested list, got {:?}", items[1].blocks);
+        };
+        assert_eq!(nested[0].checked, None);
+    }
+
+    #[test]
+    fn table() {
+        let document = Document::parse("| a | b |\n|:--|--:|\n| 1 | **2** |\n");
+        assert_eq!(
+            document.blocks,
+            [Block::Table {
+                alignments: vec![Alignment::Left, Alignment::Right],
+                head: vec![vec![text("a")], vec![text("b")]],
+                rows: vec![vec![
+                    vec![text("1")],
+                    vec![Inline::Text {
+                        text: "2".to_owned(),
+                        style: SpanStyle {
+                            strong: true,
+                            ..Default::default()
+                        },
+                        link: None,
+                    }],
+                ]],
+            }]
+        );
+    }
+
+    #[test]
+    fn links_and_images() {
+        let document = Document::parse(
+            "See [the *docs*](https://docs.rs).\n\n![A cat](cat.png \"Meow\")\n\n[![Logo](logo.png)](https://egui.rs)\n",
+        );
+        assert_eq!(
+            document.blocks,
+            [
+                Block::Paragraph(vec![
+                    text("See "),
+                    Inline::Text {
+                        text: "the ".to_owned(),
+                        style: SpanStyle::default(),
+                        link: Some("https://docs.rs".to_owned()),
+                    },
+                    Inline::Text {
+                        text: "docs".to_owned(),
+                        style: SpanStyle {
+                            emphasis: true,
+                            ..Default::default()
+                        },
+                        link: Some("https://docs.rs".to_owned()),
+                    },
+                    text("."),
+                ]),
+                Block::Paragraph(vec![Inline::Image {
+                    url: "cat.png".to_owned(),
+                    title: "Meow".to_owned(),
+                    alt: "A cat".to_owned(),
+                    link: None,
+                }]),
+                Block::Paragraph(vec![Inline::Image {
+                    url: "logo.png".to_owned(),
+                    title: String::new(),
+                    alt: "Logo".to_owned(),
+                    link: Some("https://egui.rs".to_owned()),
+                }]),
+            ]
+        );
+
+        let viewer = MarkdownViewer::new("test").base_uri("file://docs/");
+        assert_eq!(viewer.resolve_uri("./cat.png"), "file://docs/cat.png");
+        assert_eq!(
+            viewer.resolve_uri("https://x.com/a.png"),
+            "https://x.com/a.png"
+        );
+        assert_eq!(viewer.resolve_link("guide.md"), "file://docs/guide.md");
+        assert_eq!(viewer.resolve_link("#usage"), "#usage");
+        assert_eq!(viewer.resolve_link("mailto:a@b.c"), "mailto:a@b.c");
+    }
+
+    #[test]
+    fn html_is_left_out() {
+        let document = Document::parse(
+            "<div align=\"center\">\n<img src=\"logo.png\">\n</div>\n\nPress <kbd>Q</kbd>\n",
+        );
+        assert_eq!(document.blocks, [Block::Paragraph(vec![text("Press Q")])]);
+    }
+
+    #[test]
+    fn footnotes() {
+        let document = Document::parse("Egui[^1] is fast.\n\n[^1]: Pronounced e-gooey.\n");
+        assert_eq!(
+            document.blocks,
+            [
+                Block::Paragraph(vec![
+                    text("Egui"),
+                    Inline::Text {
+                        text: "[1]".to_owned(),
+                        style: SpanStyle::default(),
+                        link: Some("#fn-1".to_owned()),
+                    },
+                    text(" is fast."),
+                ]),
+                Block::Footnote {
+                    label: "1".to_owned(),
+                    blocks: vec![Block::Paragraph(vec![text("Pronounced e-gooey.")])],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn deeply_nested_quotes() {
+        let source = format!("{} deep", ">".repeat(10_000));
+        let document = Document::parse(&source);
+
+        let mut depth = 0;
+        let mut blocks = &document.blocks;
+        while let [Block::Quote(inner)] = &blocks[..] {
+            depth += 1;
+            blocks = inner;
+        }
+        assert_eq!(depth, Builder::MAX_DEPTH);
+        assert_eq!(blocks, &[Block::Paragraph(vec![text("deep")])]);
+    }
+}
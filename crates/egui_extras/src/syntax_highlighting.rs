@@ -5,8 +5,12 @@
 
 #![allow(clippy::mem_forget)] // False positive from enum_map macro
 
+use std::ops::Range;
+use std::sync::Arc;
+
 use egui::TextStyle;
-use egui::text::LayoutJob;
+use egui::mutex::Mutex;
+use egui::text::{LayoutJob, LayoutSection, TextFormat};
 
 /// View some code with syntax highlighting and selection.
 pub fn code_view_ui(
@@ -57,6 +61,37 @@ pub fn highlight_with(
     )
 }
 
+/// Like [`highlight`], but when the code changes, only the lines from the first edit onward are highlighted again.
+///
+/// Use this in code editors, to stay responsive when editing large files.
+/// The highlighting is remembered in egui memory under `id`, so use a different id for each editor.
+pub fn highlight_incremental(
+    ctx: &egui::Context,
+    id: egui::Id,
+    theme: &CodeTheme,
+    code: &str,
+    language: &str,
+) -> LayoutJob {
+    type Cached = Arc<Mutex<(u64, IncrementalHighlighter<DefaultHighlighter>)>>;
+
+    // The default settings can be replaced, e.g. with `set_syntect_settings`:
+    let settings_address = ctx.memory_mut(|mem| {
+        Arc::as_ptr(&PrivateSettings::get(&mut mem.data).0).cast::<()>() as usize
+    });
+    let key = egui::util::hash((theme, language, settings_address));
+    let cached = match ctx.data(|d| d.get_temp::<Cached>(id)) {
+        Some(cached) if cached.lock().0 == key => cached,
+        _ => {
+            let highlighter = DefaultHighlighter::new(ctx, theme, language);
+            let cached: Cached =
+                Arc::new(Mutex::new((key, IncrementalHighlighter::new(highlighter))));
+            ctx.data_mut(|d| d.insert_temp(id, cached.clone()));
+            cached
+        }
+    };
+    cached.lock().1.highlight(code)
+}
+
 fn highlight_inner(
     ctx: &egui::Context,
     style: &egui::Style,
@@ -74,7 +109,7 @@ fn highlight_inner(
         egui::cache::ComputerMut<
             (&egui::FontId, &CodeTheme, &str, &str, HighlightSettings<'_>),
             LayoutJob,
-        > for Highlighter
+        > for CachedHighlighter
     {
         fn compute(
             &mut self,
@@ -90,31 +125,16 @@ fn highlight_inner(
         }
     }
 
-    type HighlightCache = egui::cache::FrameCache<LayoutJob, Highlighter>;
+    type HighlightCache = egui::cache::FrameCache<LayoutJob, CachedHighlighter>;
 
     let font_id = style
         .override_font_id
         .clone()
         .unwrap_or_else(|| TextStyle::Monospace.resolve(style));
 
-    // Private type, so that users can't interfere with it in the `IdTypeMap`
-    #[cfg(feature = "syntect")]
-    #[derive(Clone, Default)]
-    struct PrivateSettings(std::sync::Arc<SyntectSettings>);
-
-    // Dummy private settings, to minimize code changes without `syntect`
-    #[cfg(not(feature = "syntect"))]
-    #[derive(Clone, Default)]
-    struct PrivateSettings(std::sync::Arc<()>);
-
     ctx.memory_mut(|mem| {
-        let settings = settings.unwrap_or_else(|| {
-            HighlightSettings(
-                &mem.data
-                    .get_temp_mut_or_default::<PrivateSettings>(egui::Id::NULL)
-                    .0,
-            )
-        });
+        let settings =
+            settings.unwrap_or_else(|| HighlightSettings(&PrivateSettings::get(&mut mem.data).0));
         mem.caches
             .cache::<HighlightCache>()
             .get((&font_id, theme, code, language, settings))
@@ -125,6 +145,338 @@ fn monospace_font_size(style: &egui::Style) -> f32 {
     TextStyle::Monospace.resolve(style).size
 }
 
+fn plain_text_color(theme: &CodeTheme) -> egui::Color32 {
+    if theme.dark_mode {
+        egui::Color32::LIGHT_GRAY
+    } else {
+        egui::Color32::DARK_GRAY
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Splits code into highlighted sections, one line at a time.
+///
+/// Implement this to plug in your own lexer (e.g. tree-sitter, or semantic tokens from a language server),
+/// and use it with an [`IncrementalHighlighter`].
+///
+/// ```
+/// use egui::{Color32, FontId, text::TextFormat};
+/// use egui_extras::syntax_highlighting::{Highlighter, IncrementalHighlighter};
+///
+/// /// Highlights `#` comments, which continue on the next line after a trailing `\`.
+/// struct Comments;
+///
+/// impl Highlighter for Comments {
+///     type State = bool; // Inside a continued comment?
+///
+///     fn start_state(&self) -> bool {
+///         false
+///     }
+///
+///     fn highlight_line(
+///         &self,
+///         in_comment: &mut bool,
+///         line: &str,
+///     ) -> Vec<(std::ops::Range<usize>, TextFormat)> {
+///         let start = if *in_comment { 0 } else { line.find('#').unwrap_or(line.len()) };
+///         *in_comment = start < line.len() && line.trim_end().ends_with('\\');
+///         let font_id = FontId::monospace(12.0);
+///         vec![
+///             (0..start, TextFormat::simple(font_id.clone(), Color32::WHITE)),
+///             (start..line.len(), TextFormat::simple(font_id, Color32::GRAY)),
+///         ]
+///     }
+/// }
+///
+/// let mut highlighter = IncrementalHighlighter::new(Comments);
+/// let job = highlighter.highlight("x = 1 # one \\\n  continued\ny = 2\n");
+/// assert_eq!(job.sections.len(), 4);
+/// ```
+pub trait Highlighter {
+    /// What needs to be remembered from one line to the next, e.g. "inside a block comment".
+    ///
+    /// Lines that start in equal states must be highlighted the same,
+    /// as this is used to decide when the lines after an edit need not be highlighted again.
+    type State: Clone + PartialEq;
+
+    /// The state at the start of the code.
+    fn start_state(&self) -> Self::State;
+
+    /// Highlight one line, including its trailing newline (if any), and update the state.
+    ///
+    /// Returns the byte ranges of the line (in order, covering the whole line) and how to format them.
+    fn highlight_line(
+        &self,
+        state: &mut Self::State,
+        line: &str,
+    ) -> Vec<(Range<usize>, TextFormat)>;
+}
+
+/// Remembers how each line was highlighted by a [`Highlighter`],
+/// so that only the lines from the first edit onward are highlighted again when the code changes.
+///
+/// Once a line after the edit is reached that is unchanged and starts in the same
+/// [`Highlighter::State`] as before, the rest of the old highlighting is reused.
+pub struct IncrementalHighlighter<H: Highlighter> {
+    highlighter: H,
+    lines: Vec<HighlightedLine<H::State>>,
+
+    /// The state after the last line.
+    end_state: H::State,
+
+    /// The hash of the code we highlighted last, and the result.
+    last_job: Option<(u64, LayoutJob)>,
+}
+
+struct HighlightedLine<State> {
+    /// Including the trailing newline, if any.
+    text: String,
+
+    /// The state after a line is the start state of the next one, or [`IncrementalHighlighter::end_state`].
+    start_state: State,
+
+    sections: Vec<(Range<usize>, TextFormat)>,
+}
+
+impl<H: Highlighter> IncrementalHighlighter<H> {
+    /// Nothing is highlighted until the first call to [`Self::highlight`].
+    pub fn new(highlighter: H) -> Self {
+        let end_state = highlighter.start_state();
+        Self {
+            highlighter,
+            lines: Vec::new(),
+            end_state,
+            last_job: None,
+        }
+    }
+
+    /// The [`Highlighter`] that highlights the lines.
+    pub fn highlighter(&self) -> &H {
+        &self.highlighter
+    }
+
+    /// Use a different highlighter, e.g. because the theme changed.
+    ///
+    /// This forgets all previous highlighting.
+    pub fn set_highlighter(&mut self, highlighter: H) {
+        *self = Self::new(highlighter);
+    }
+
+    /// Highlight the code, reusing as much as possible from the previous call.
+    pub fn highlight(&mut self, code: &str) -> LayoutJob {
+        profiling::function_scope!();
+
+        let code_hash = egui::util::hash(code);
+        if let Some((last_hash, job)) = &self.last_job
+            && *last_hash == code_hash
+        {
+            return job.clone();
+        }
+
+        let new_lines: Vec<&str> = code.split_inclusive('\n').collect();
+        let old_lines = std::mem::take(&mut self.lines);
+
+        // Find the unchanged lines at the start and end:
+        let num_prefix = new_lines
+            .iter()
+            .zip(&old_lines)
+            .take_while(|(new, old)| **new == old.text)
+            .count();
+        let max_suffix = new_lines.len().min(old_lines.len()) - num_prefix;
+        let num_suffix = new_lines
+            .iter()
+            .rev()
+            .zip(old_lines.iter().rev())
+            .take(max_suffix)
+            .take_while(|(new, old)| **new == old.text)
+            .count();
+
+        // The state after the prefix:
+        let mut state = old_lines
+            .get(num_prefix)
+            .map_or_else(|| self.end_state.clone(), |line| line.start_state.clone());
+
+        let num_old_lines = old_lines.len();
+        let mut old_lines = old_lines.into_iter();
+        let mut lines: Vec<_> = old_lines.by_ref().take(num_prefix).collect();
+        let mut old_suffix: Vec<_> = old_lines
+            .skip(num_old_lines - num_prefix - num_suffix)
+            .collect();
+
+        let suffix_start = new_lines.len() - num_suffix;
+        let mut reused_suffix = false;
+
+        for (i, text) in new_lines.iter().enumerate().skip(num_prefix) {
+            if suffix_start <= i {
+                let old_index = i - suffix_start;
+                if old_suffix[old_index].start_state == state {
+                    lines.extend(old_suffix.drain(old_index..));
+                    reused_suffix = true;
+                    break;
+                }
+            }
+
+            let start_state = state.clone();
+            let sections = self.highlighter.highlight_line(&mut state, text);
+            lines.push(HighlightedLine {
+                text: (*text).to_owned(),
+                start_state,
+                sections,
+            });
+        }
+
+        self.lines = lines;
+        if !reused_suffix {
+            // Otherwise the old end state still holds, since the code ends the same way.
+            self.end_state = state;
+        }
+
+        let mut job = LayoutJob {
+            text: code.to_owned(),
+            ..Default::default()
+        };
+        let mut offset = 0;
+        for line in &self.lines {
+            for (range, format) in &line.sections {
+                if !range.is_empty() {
+                    job.sections.push(LayoutSection::new(
+                        offset + range.start..offset + range.end,
+                        format.clone(),
+                    ));
+                }
+            }
+            offset += line.text.len();
+        }
+        self.last_job = Some((code_hash, job.clone()));
+        job
+    }
+}
+
+/// The [`Highlighter`] used by [`highlight`]:
+/// `syntect` with the `syntect` feature, otherwise a very simple tokenizer for a few languages.
+///
+/// Languages it doesn't know are shown as plain text.
+pub struct DefaultHighlighter {
+    theme: CodeTheme,
+
+    #[cfg(feature = "syntect")]
+    settings: Arc<SyntectSettings>,
+    #[cfg(feature = "syntect")]
+    syntax: Option<syntect::parsing::SyntaxReference>,
+
+    #[cfg(not(feature = "syntect"))]
+    language: Option<Language>,
+}
+
+/// The [`Highlighter::State`] of [`DefaultHighlighter`].
+#[derive(Clone, PartialEq, Eq)]
+pub struct DefaultHighlighterState {
+    #[cfg(feature = "syntect")]
+    syntect: Option<SyntectState>,
+
+    /// Inside a string literal that continues from the previous line?
+    #[cfg(not(feature = "syntect"))]
+    in_string: bool,
+}
+
+impl DefaultHighlighter {
+    /// With the `syntect` feature, this uses the same [`SyntectSettings`] as [`highlight`].
+    pub fn new(ctx: &egui::Context, theme: &CodeTheme, language: &str) -> Self {
+        #[cfg(feature = "syntect")]
+        {
+            Self::with_settings(theme, language, syntect_settings(ctx))
+        }
+
+        #[cfg(not(feature = "syntect"))]
+        {
+            _ = ctx;
+            Self {
+                theme: theme.clone(),
+                language: Language::new(language),
+            }
+        }
+    }
+
+    /// Use custom `syntect` settings.
+    #[cfg(feature = "syntect")]
+    pub fn with_settings(
+        theme: &CodeTheme,
+        language: &str,
+        settings: Arc<SyntectSettings>,
+    ) -> Self {
+        let syntax = settings.find_syntax(language).cloned();
+        Self {
+            theme: theme.clone(),
+            settings,
+            syntax,
+        }
+    }
+
+    fn plain_text(&self, line: &str) -> Vec<(Range<usize>, TextFormat)> {
+        #[cfg(feature = "syntect")]
+        let font_id = self.theme.font_id.clone();
+        #[cfg(not(feature = "syntect"))]
+        let font_id = self.theme.formats[TokenType::Punctuation].font_id.clone();
+
+        vec![(
+            0..line.len(),
+            TextFormat::simple(font_id, plain_text_color(&self.theme)),
+        )]
+    }
+
+    #[cfg(feature = "syntect")]
+    fn syntect_theme(&self) -> Option<&syntect::highlighting::Theme> {
+        self.theme.syntect_theme(&self.settings)
+    }
+}
+
+impl Highlighter for DefaultHighlighter {
+    type State = DefaultHighlighterState;
+
+    fn start_state(&self) -> Self::State {
+        DefaultHighlighterState {
+            #[cfg(feature = "syntect")]
+            syntect: self
+                .syntax
+                .as_ref()
+                .zip(self.syntect_theme())
+                .map(|(syntax, syn_theme)| SyntectState::new(syntax, syn_theme)),
+
+            #[cfg(not(feature = "syntect"))]
+            in_string: false,
+        }
+    }
+
+    #[cfg(feature = "syntect")]
+    fn highlight_line(
+        &self,
+        state: &mut Self::State,
+        line: &str,
+    ) -> Vec<(Range<usize>, TextFormat)> {
+        state
+            .syntect
+            .as_mut()
+            .zip(self.syntect_theme())
+            .and_then(|(syntect, syn_theme)| {
+                syntect.highlight_line(&self.settings.ps, syn_theme, &self.theme, line)
+            })
+            .unwrap_or_else(|| self.plain_text(line))
+    }
+
+    #[cfg(not(feature = "syntect"))]
+    fn highlight_line(
+        &self,
+        state: &mut Self::State,
+        line: &str,
+    ) -> Vec<(Range<usize>, TextFormat)> {
+        match &self.language {
+            Some(language) => language.highlight_line(&self.theme, &mut state.in_string, line),
+            None => self.plain_text(line),
+        }
+    }
+}
+
 // ----------------------------------------------------------------------------
 
 #[cfg(not(feature = "syntect"))]
@@ -140,7 +492,7 @@ enum TokenType {
 }
 
 #[cfg(feature = "syntect")]
-#[derive(Clone, Copy, Hash, PartialEq)]
+#[derive(Clone, Hash, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 enum SyntectTheme {
     Base16EightiesDark,
@@ -150,6 +502,9 @@ enum SyntectTheme {
     InspiredGitHub,
     SolarizedDark,
     SolarizedLight,
+
+    /// A theme added to the [`SyntectSettings`], by its key in [`syntect::highlighting::ThemeSet::themes`].
+    Custom(String),
 }
 
 #[cfg(feature = "syntect")]
@@ -164,11 +519,16 @@ impl SyntectTheme {
             Self::SolarizedDark,
             Self::SolarizedLight,
         ]
-        .iter()
-        .copied()
+        .into_iter()
     }
 
-    fn name(&self) -> &'static str {
+    fn from_key_name(name: &str) -> Self {
+        Self::all()
+            .find(|theme| theme.syntect_key_name() == name)
+            .unwrap_or_else(|| Self::Custom(name.to_owned()))
+    }
+
+    fn name(&self) -> &str {
         match self {
             Self::Base16EightiesDark => "Base16 Eighties (dark)",
             Self::Base16MochaDark => "Base16 Mocha (dark)",
@@ -177,10 +537,11 @@ impl SyntectTheme {
             Self::InspiredGitHub => "InspiredGitHub (light)",
             Self::SolarizedDark => "Solarized (dark)",
             Self::SolarizedLight => "Solarized (light)",
+            Self::Custom(name) => name,
         }
     }
 
-    fn syntect_key_name(&self) -> &'static str {
+    fn syntect_key_name(&self) -> &str {
         match self {
             Self::Base16EightiesDark => "base16-eighties.dark",
             Self::Base16MochaDark => "base16-mocha.dark",
@@ -189,10 +550,12 @@ impl SyntectTheme {
             Self::InspiredGitHub => "InspiredGitHub",
             Self::SolarizedDark => "Solarized (dark)",
             Self::SolarizedLight => "Solarized (light)",
+            Self::Custom(name) => name,
         }
     }
 
-    pub fn is_dark(&self) -> bool {
+    /// Custom themes are dark if their background is.
+    pub fn is_dark(&self, settings: &SyntectSettings) -> bool {
         match self {
             Self::Base16EightiesDark
             | Self::Base16MochaDark
@@ -200,6 +563,15 @@ impl SyntectTheme {
             | Self::SolarizedDark => true,
 
             Self::Base16OceanLight | Self::InspiredGitHub | Self::SolarizedLight => false,
+
+            Self::Custom(name) => settings
+                .ts
+                .themes
+                .get(name)
+                .and_then(|theme| theme.settings.background)
+                .is_none_or(|bg| {
+                    egui::Rgba::from_srgba_unmultiplied(bg.r, bg.g, bg.b, bg.a).intensity() < 0.5
+                }),
         }
     }
 }
@@ -300,6 +672,10 @@ impl CodeTheme {
     /// Store theme to egui memory.
     ///
     /// There is one dark and one light theme stored at any one time.
+    ///
+    /// With the `syntect` feature, only the name of the `syntect` theme is stored.
+    /// A custom theme is available after a restart if it was loaded from a file,
+    /// see `set_syntect_settings`.
     pub fn store_in_memory(self, ctx: &egui::Context) {
         let id = if ctx.global_style().visuals.dark_mode {
             egui::Id::new("dark")
@@ -337,7 +713,41 @@ impl CodeTheme {
         self.dark_mode
     }
 
+    /// The key of the `syntect` theme in [`syntect::highlighting::ThemeSet::themes`].
+    pub fn syntect_theme_name(&self) -> &str {
+        self.syntect_theme.syntect_key_name()
+    }
+
+    /// Use the `syntect` theme with this key in [`syntect::highlighting::ThemeSet::themes`],
+    /// e.g. one added with [`SyntectSettings::add_theme_from_file`].
+    ///
+    /// If the [`SyntectSettings`] used for highlighting have no such theme, a built-in one is used instead.
+    pub fn set_syntect_theme(&mut self, name: &str) {
+        self.syntect_theme = SyntectTheme::from_key_name(name);
+    }
+
+    /// The `syntect` theme to use, falling back to a built-in one if it is missing from the settings.
+    fn syntect_theme<'a>(
+        &self,
+        settings: &'a SyntectSettings,
+    ) -> Option<&'a syntect::highlighting::Theme> {
+        let themes = &settings.ts.themes;
+        themes
+            .get(self.syntect_theme.syntect_key_name())
+            .or_else(|| {
+                let fallback = if self.dark_mode {
+                    Self::dark(12.0)
+                } else {
+                    Self::light(12.0)
+                };
+                themes.get(fallback.syntect_theme.syntect_key_name())
+            })
+            .or_else(|| themes.values().next())
+    }
+
     /// Show UI for changing the color theme.
+    ///
+    /// This lists the built-in `syntect` themes, and any custom ones added with [`set_syntect_settings`].
     pub fn ui(&mut self, ui: &mut egui::Ui) {
         ui.horizontal(|ui| {
             ui.selectable_value(&mut self.dark_mode, true, "🌙 Dark theme")
@@ -347,9 +757,17 @@ impl CodeTheme {
                 .on_hover_text("Use the light mode theme");
         });
 
-        for theme in SyntectTheme::all() {
-            if theme.is_dark() == self.dark_mode {
-                ui.radio_value(&mut self.syntect_theme, theme, theme.name());
+        let settings = syntect_settings(ui.ctx());
+        let custom_themes = settings
+            .ts
+            .themes
+            .keys()
+            .map(|name| SyntectTheme::from_key_name(name))
+            .filter(|theme| matches!(theme, SyntectTheme::Custom(_)));
+
+        for theme in SyntectTheme::all().chain(custom_themes) {
+            if theme.is_dark(&settings) == self.dark_mode {
+                ui.radio_value(&mut self.syntect_theme, theme.clone(), theme.name());
             }
         }
     }
@@ -469,11 +887,42 @@ impl CodeTheme {
 
 // ----------------------------------------------------------------------------
 
+/// The grammars and themes used for highlighting with `syntect`.
+///
+/// Add your own with [`Self::add_syntax_from_file`] and [`Self::add_theme_from_file`],
+/// and then either use them with [`highlight_with`], or everywhere with [`set_syntect_settings`].
 #[cfg(feature = "syntect")]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SyntectSettings {
     pub ps: syntect::parsing::SyntaxSet,
     pub ts: syntect::highlighting::ThemeSet,
+
+    /// The files loaded with [`Self::add_syntax_from_file`].
+    ///
+    /// These are persisted by [`set_syntect_settings`] with the `serde` feature.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub syntax_files: Vec<std::path::PathBuf>,
+
+    /// The files loaded with [`Self::add_theme_from_file`].
+    ///
+    /// These are persisted by [`set_syntect_settings`] with the `serde` feature.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub theme_files: Vec<std::path::PathBuf>,
+}
+
+// `ThemeSet` doesn't implement `Clone`, but its themes do.
+#[cfg(feature = "syntect")]
+impl Clone for SyntectSettings {
+    fn clone(&self) -> Self {
+        Self {
+            ps: self.ps.clone(),
+            ts: syntect::highlighting::ThemeSet {
+                themes: self.ts.themes.clone(),
+            },
+            syntax_files: self.syntax_files.clone(),
+            theme_files: self.theme_files.clone(),
+        }
+    }
 }
 
 #[cfg(feature = "syntect")]
@@ -483,8 +932,174 @@ impl Default for SyntectSettings {
         Self {
             ps: syntect::parsing::SyntaxSet::load_defaults_newlines(),
             ts: syntect::highlighting::ThemeSet::load_defaults(),
+            syntax_files: Vec::new(),
+            theme_files: Vec::new(),
+        }
+    }
+}
+
+#[cfg(feature = "syntect")]
+impl SyntectSettings {
+    /// Add a grammar, e.g. loaded with [`syntect::parsing::SyntaxDefinition::load_from_str`].
+    ///
+    /// It is found by its name or file extensions, like the built-in ones.
+    pub fn add_syntax(&mut self, syntax: syntect::parsing::SyntaxDefinition) {
+        let mut builder = std::mem::take(&mut self.ps).into_builder();
+        builder.add(syntax);
+        self.ps = builder.build();
+    }
+
+    /// Load a grammar from a `.sublime-syntax` file.
+    ///
+    /// # Errors
+    /// If the file can't be read, or isn't a valid grammar.
+    pub fn add_syntax_from_file(
+        &mut self,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<(), syntect::LoadingError> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path)?;
+        let fallback_name = path.file_stem().and_then(|stem| stem.to_str());
+        let syntax =
+            syntect::parsing::SyntaxDefinition::load_from_str(&source, true, fallback_name)
+                .map_err(|err| {
+                    syntect::LoadingError::ParseSyntax(err, path.display().to_string())
+                })?;
+        self.add_syntax(syntax);
+        self.syntax_files.push(path.to_owned());
+        Ok(())
+    }
+
+    /// Add a theme, which can then be selected with [`CodeTheme::set_syntect_theme`].
+    pub fn add_theme(&mut self, name: impl Into<String>, theme: syntect::highlighting::Theme) {
+        self.ts.themes.insert(name.into(), theme);
+    }
+
+    /// Load a theme from a `.tmTheme` file.
+    ///
+    /// Returns the name to select it by with [`CodeTheme::set_syntect_theme`]:
+    /// the name given in the file, or else the file name.
+    ///
+    /// # Errors
+    /// If the file can't be read, or isn't a valid theme.
+    pub fn add_theme_from_file(
+        &mut self,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<String, syntect::LoadingError> {
+        let path = path.as_ref();
+        let theme = syntect::highlighting::ThemeSet::get_theme(path)?;
+        let name = theme
+            .name
+            .clone()
+            .or_else(|| Some(path.file_stem()?.to_str()?.to_owned()))
+            .unwrap_or_else(|| path.display().to_string());
+        self.add_theme(name.clone(), theme);
+        self.theme_files.push(path.to_owned());
+        Ok(name)
+    }
+
+    fn find_syntax(&self, language: &str) -> Option<&syntect::parsing::SyntaxReference> {
+        self.ps
+            .find_syntax_by_name(language)
+            .or_else(|| self.ps.find_syntax_by_extension(language))
+    }
+}
+
+/// The [`SyntectSettings`] used by [`highlight`], [`code_view_ui`], [`DefaultHighlighter::new`] and [`CodeTheme::ui`].
+#[cfg(feature = "syntect")]
+pub fn syntect_settings(ctx: &egui::Context) -> Arc<SyntectSettings> {
+    ctx.memory_mut(|mem| PrivateSettings::get(&mut mem.data).0.clone())
+}
+
+/// Replace the [`SyntectSettings`] used by [`highlight`], [`code_view_ui`], [`DefaultHighlighter::new`] and [`CodeTheme::ui`],
+/// e.g. to add your own grammars and themes:
+///
+/// ```no_run
+/// # egui::__run_test_ctx(|ctx| {
+/// use egui_extras::syntax_highlighting::{self, CodeTheme, SyntectSettings};
+///
+/// let mut settings = SyntectSettings::clone(&syntax_highlighting::syntect_settings(ctx));
+/// settings.add_syntax_from_file("my_language.sublime-syntax").unwrap();
+/// let theme_name = settings.add_theme_from_file("my_theme.tmTheme").unwrap();
+/// syntax_highlighting::set_syntect_settings(ctx, settings);
+///
+/// let mut theme = CodeTheme::from_memory(ctx, &ctx.global_style());
+/// theme.set_syntect_theme(&theme_name);
+/// theme.store_in_memory(ctx);
+/// # });
+/// ```
+///
+/// With the `serde` feature, the files in [`SyntectSettings::syntax_files`] and [`SyntectSettings::theme_files`]
+/// are persisted, and loaded again the next time the app starts.
+/// Grammars and themes added with [`SyntectSettings::add_syntax`] and [`SyntectSettings::add_theme`]
+/// are not, so add them again at startup.
+#[cfg(feature = "syntect")]
+pub fn set_syntect_settings(ctx: &egui::Context, settings: SyntectSettings) {
+    ctx.data_mut(|d| {
+        #[cfg(feature = "serde")]
+        d.insert_persisted(
+            egui::Id::NULL,
+            PersistedFiles {
+                syntax_files: settings.syntax_files.clone(),
+                theme_files: settings.theme_files.clone(),
+            },
+        );
+        d.insert_temp(egui::Id::NULL, PrivateSettings(Arc::new(settings)));
+    });
+}
+
+// Private type, so that users can't interfere with it in the `IdTypeMap`
+#[cfg(feature = "syntect")]
+#[derive(Clone, Default)]
+struct PrivateSettings(Arc<SyntectSettings>);
+
+// Dummy private settings, to minimize code changes without `syntect`
+#[cfg(not(feature = "syntect"))]
+#[derive(Clone, Default)]
+struct PrivateSettings(Arc<()>);
+
+impl PrivateSettings {
+    /// The settings set with [`set_syntect_settings`], or else the defaults
+    /// plus any grammars and themes persisted from the last run.
+    fn get(data: &mut egui::util::IdTypeMap) -> &mut Self {
+        if data.get_temp::<Self>(egui::Id::NULL).is_none() {
+            let settings = Self::load(data);
+            data.insert_temp(egui::Id::NULL, settings);
+        }
+        data.get_temp_mut_or_default::<Self>(egui::Id::NULL)
+    }
+
+    #[cfg(all(feature = "syntect", feature = "serde"))]
+    fn load(data: &mut egui::util::IdTypeMap) -> Self {
+        let Some(files) = data.get_persisted::<PersistedFiles>(egui::Id::NULL) else {
+            return Self::default();
+        };
+        let mut settings = SyntectSettings::default();
+        for path in &files.syntax_files {
+            if let Err(err) = settings.add_syntax_from_file(path) {
+                log::warn!("Failed to load syntax from {}: {err}", path.display());
+            }
         }
+        for path in &files.theme_files {
+            if let Err(err) = settings.add_theme_from_file(path) {
+                log::warn!("Failed to load theme from {}: {err}", path.display());
+            }
+        }
+        Self(Arc::new(settings))
     }
+
+    #[cfg(not(all(feature = "syntect", feature = "serde")))]
+    fn load(_data: &mut egui::util::IdTypeMap) -> Self {
+        Self::default()
+    }
+}
+
+/// The files of the custom grammars and themes set with [`set_syntect_settings`], persisted across restarts.
+#[cfg(all(feature = "syntect", feature = "serde"))]
+#[derive(Clone, Default, serde::Serialize, serde::Deserialize)]
+struct PersistedFiles {
+    syntax_files: Vec<std::path::PathBuf>,
+    theme_files: Vec<std::path::PathBuf>,
 }
 
 /// Highlight settings are memoized by reference address, rather than value
@@ -503,9 +1118,9 @@ impl std::hash::Hash for HighlightSettings<'_> {
 }
 
 #[derive(Default)]
-struct Highlighter;
+struct CachedHighlighter;
 
-impl Highlighter {
+impl CachedHighlighter {
     fn highlight(
         font_id: egui::FontId,
         theme: &CodeTheme,
@@ -515,16 +1130,7 @@ impl Highlighter {
     ) -> LayoutJob {
         Self::highlight_impl(theme, code, lang, settings).unwrap_or_else(|| {
             // Fallback:
-            LayoutJob::simple(
-                code.into(),
-                font_id,
-                if theme.dark_mode {
-                    egui::Color32::LIGHT_GRAY
-                } else {
-                    egui::Color32::DARK_GRAY
-                },
-                f32::INFINITY,
-            )
+            LayoutJob::simple(code.into(), font_id, plain_text_color(theme), f32::INFINITY)
         })
     }
 
@@ -536,79 +1142,99 @@ impl Highlighter {
         highlighter: HighlightSettings<'_>,
     ) -> Option<LayoutJob> {
         profiling::function_scope!();
-        use syntect::easy::HighlightLines;
-        use syntect::highlighting::FontStyle;
-        use syntect::util::LinesWithEndings;
 
-        let syntax = highlighter
-            .0
-            .ps
-            .find_syntax_by_name(language)
-            .or_else(|| highlighter.0.ps.find_syntax_by_extension(language))?;
-
-        let syn_theme = theme.syntect_theme.syntect_key_name();
-        let mut h = HighlightLines::new(syntax, &highlighter.0.ts.themes[syn_theme]);
-
-        use egui::text::{LayoutSection, TextFormat};
+        let settings = highlighter.0;
+        let syntax = settings.find_syntax(language)?;
+        let syn_theme = theme.syntect_theme(settings)?;
+        let mut state = SyntectState::new(syntax, syn_theme);
 
         let mut job = LayoutJob {
             text: text.into(),
             ..Default::default()
         };
 
-        for line in LinesWithEndings::from(text) {
-            for (style, range) in h.highlight_line(line, &highlighter.0.ps).ok()? {
+        let mut offset = 0;
+        for line in text.split_inclusive('\n') {
+            for (range, format) in state.highlight_line(&settings.ps, syn_theme, theme, line)? {
+                job.sections.push(LayoutSection::new(
+                    offset + range.start..offset + range.end,
+                    format,
+                ));
+            }
+            offset += line.len();
+        }
+
+        Some(job)
+    }
+}
+
+/// The state of `syntect` at the start of a line.
+#[cfg(feature = "syntect")]
+#[derive(Clone, PartialEq, Eq)]
+struct SyntectState {
+    parse: syntect::parsing::ParseState,
+    highlight: syntect::highlighting::HighlightState,
+}
+
+#[cfg(feature = "syntect")]
+impl SyntectState {
+    fn new(
+        syntax: &syntect::parsing::SyntaxReference,
+        syn_theme: &syntect::highlighting::Theme,
+    ) -> Self {
+        let highlighter = syntect::highlighting::Highlighter::new(syn_theme);
+        Self {
+            parse: syntect::parsing::ParseState::new(syntax),
+            highlight: syntect::highlighting::HighlightState::new(
+                &highlighter,
+                syntect::parsing::ScopeStack::new(),
+            ),
+        }
+    }
+
+    fn highlight_line(
+        &mut self,
+        syntax_set: &syntect::parsing::SyntaxSet,
+        syn_theme: &syntect::highlighting::Theme,
+        theme: &CodeTheme,
+        line: &str,
+    ) -> Option<Vec<(Range<usize>, TextFormat)>> {
+        use syntect::highlighting::{FontStyle, RangedHighlightIterator};
+
+        let ops = self.parse.parse_line(line, syntax_set).ok()?;
+        let highlighter = syntect::highlighting::Highlighter::new(syn_theme);
+        let sections = RangedHighlightIterator::new(&mut self.highlight, &ops, line, &highlighter)
+            .map(|(style, _, range)| {
                 let fg = style.foreground;
                 let text_color = egui::Color32::from_rgb(fg.r, fg.g, fg.b);
                 let italics = style.font_style.contains(FontStyle::ITALIC);
-                let underline = style.font_style.contains(FontStyle::ITALIC);
+                let underline = style.font_style.contains(FontStyle::UNDERLINE);
                 let underline = if underline {
                     egui::Stroke::new(1.0, text_color)
                 } else {
                     egui::Stroke::NONE
                 };
-                job.sections.push(LayoutSection {
-                    leading_space: 0.0,
-                    byte_range: as_byte_range(text, range),
-                    format: TextFormat {
-                        font_id: theme.font_id.clone(),
-                        color: text_color,
-                        italics,
-                        underline,
-                        ..Default::default()
-                    },
-                });
-            }
-        }
-
-        Some(job)
+                let format = TextFormat {
+                    font_id: theme.font_id.clone(),
+                    color: text_color,
+                    italics,
+                    underline,
+                    ..Default::default()
+                };
+                (range, format)
+            })
+            .collect();
+        Some(sections)
     }
 }
 
-#[cfg(feature = "syntect")]
-fn as_byte_range(whole: &str, range: &str) -> std::ops::Range<usize> {
-    let whole_start = whole.as_ptr() as usize;
-    let range_start = range.as_ptr() as usize;
-    assert!(
-        whole_start <= range_start,
-        "range must be within whole, but was {range}"
-    );
-    assert!(
-        range_start + range.len() <= whole_start + whole.len(),
-        "range_start + range length must be smaller than whole_start + whole length, but was {}",
-        range_start + range.len()
-    );
-    let offset = range_start - whole_start;
-    offset..(offset + range.len())
-}
-
 // ----------------------------------------------------------------------------
 
 #[cfg(not(feature = "syntect"))]
-impl Highlighter {
+impl CachedHighlighter {
     fn highlight_impl(
         theme: &CodeTheme,
-        mut text: &str,
+        text: &str,
         language: &str,
         _settings: HighlightSettings<'_>,
     ) -> Option<LayoutJob> {
@@ -616,61 +1242,11 @@ impl Highlighter {
 
         let language = Language::new(language)?;
 
-        // Extremely simple syntax highlighter for when we compile without syntect
-
         let mut job = LayoutJob::default();
-
-        while !text.is_empty() {
-            if language.double_slash_comments && text.starts_with("//")
-                || language.hash_comments && text.starts_with('#')
-            {
-                let end = text.find('\n').unwrap_or(text.len());
-                job.append(&text[..end], 0.0, theme.formats[TokenType::Comment].clone());
-                text = &text[end..];
-            } else if text.starts_with('"') {
-                let end = text[1..]
-                    .find('"')
-                    .map(|i| i + 2)
-                    .or_else(|| text.find('\n'))
-                    .unwrap_or(text.len());
-                job.append(
-                    &text[..end],
-                    0.0,
-                    theme.formats[TokenType::StringLiteral].clone(),
-                );
-                text = &text[end..];
-            } else if text.starts_with(|c: char| c.is_ascii_alphanumeric()) {
-                let end = text[1..]
-                    .find(|c: char| !c.is_ascii_alphanumeric())
-                    .map_or_else(|| text.len(), |i| i + 1);
-                let word = &text[..end];
-                let tt = if language.is_keyword(word) {
-                    TokenType::Keyword
-                } else {
-                    TokenType::Literal
-                };
-                job.append(word, 0.0, theme.formats[tt].clone());
-                text = &text[end..];
-            } else if text.starts_with(|c: char| c.is_ascii_whitespace()) {
-                let end = text[1..]
-                    .find(|c: char| !c.is_ascii_whitespace())
-                    .map_or_else(|| text.len(), |i| i + 1);
-                job.append(
-                    &text[..end],
-                    0.0,
-                    theme.formats[TokenType::Whitespace].clone(),
-                );
-                text = &text[end..];
-            } else {
-                let mut it = text.char_indices();
-                it.next();
-                let end = it.next().map_or(text.len(), |(idx, _chr)| idx);
-                job.append(
-                    &text[..end],
-                    0.0,
-                    theme.formats[TokenType::Punctuation].clone(),
-                );
-                text = &text[end..];
+        let mut in_string = false;
+        for line in text.split_inclusive('\n') {
+            for (range, format) in language.highlight_line(theme, &mut in_string, line) {
+                job.append(&line[range], 0.0, format);
             }
         }
 
@@ -707,6 +1283,67 @@ impl Language {
         self.keywords.contains(word)
     }
 
+    /// Extremely simple syntax highlighter for when we compile without syntect.
+    ///
+    /// `in_string` is whether a string literal continues from the previous line.
+    fn highlight_line(
+        &self,
+        theme: &CodeTheme,
+        in_string: &mut bool,
+        line: &str,
+    ) -> Vec<(Range<usize>, TextFormat)> {
+        let mut sections = Vec::new();
+        let mut start = 0;
+
+        while start < line.len() {
+            let text = &line[start..];
+
+            let (len, tt) = if *in_string {
+                let len = text.find('"').map_or(text.len(), |i| {
+                    *in_string = false;
+                    i + 1
+                });
+                (len, TokenType::StringLiteral)
+            } else if self.double_slash_comments && text.starts_with("//")
+                || self.hash_comments && text.starts_with('#')
+            {
+                (text.find('\n').unwrap_or(text.len()), TokenType::Comment)
+            } else if let Some(rest) = text.strip_prefix('"') {
+                let len = rest.find('"').map_or_else(
+                    || {
+                        *in_string = true;
+                        text.len()
+                    },
+                    |i| i + 2,
+                );
+                (len, TokenType::StringLiteral)
+            } else if text.starts_with(|c: char| c.is_ascii_alphanumeric()) {
+                let len = text[1..]
+                    .find(|c: char| !c.is_ascii_alphanumeric())
+                    .map_or_else(|| text.len(), |i| i + 1);
+                let tt = if self.is_keyword(&text[..len]) {
+                    TokenType::Keyword
+                } else {
+                    TokenType::Literal
+                };
+                (len, tt)
+            } else if text.starts_with(|c: char| c.is_ascii_whitespace()) {
+                let len = text[1..]
+                    .find(|c: char| !c.is_ascii_whitespace())
+                    .map_or_else(|| text.len(), |i| i + 1);
+                (len, TokenType::Whitespace)
+            } else {
+                let len = text.chars().next().map_or(text.len(), char::len_utf8);
+                (len, TokenType::Punctuation)
+            };
+
+            sections.push((start..start + len, theme.formats[tt].clone()));
+            start += len;
+        }
+
+        sections
+    }
+
     fn cpp() -> Self {
         Self {
             double_slash_comments: true,
@@ -853,3 +1490,183 @@ impl Language {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Highlights `/* block comments */`, and counts how many lines it highlighted.
+    #[derive(Default)]
+    struct BlockComments {
+        num_lines: std::cell::Cell<usize>,
+    }
+
+    impl Highlighter for BlockComments {
+        type State = bool;
+
+        fn start_state(&self) -> bool {
+            false
+        }
+
+        fn highlight_line(
+            &self,
+            in_comment: &mut bool,
+            line: &str,
+        ) -> Vec<(Range<usize>, TextFormat)> {
+            self.num_lines.set(self.num_lines.get() + 1);
+            if line.contains("/*") {
+                *in_comment = true;
+            }
+            let format = if *in_comment {
+                TextFormat::simple(egui::FontId::default(), egui::Color32::GREEN)
+            } else {
+                TextFormat::default()
+            };
+            if line.contains("*/") {
+                *in_comment = false;
+            }
+            vec![(0..line.len(), format)]
+        }
+    }
+
+    #[test]
+    fn incremental_highlighting() {
+        let mut highlighter = IncrementalHighlighter::new(BlockComments::default());
+        let mut lines: Vec<String> = (0..100).map(|i| format!("line {i}\n")).collect();
+        let num_lines_highlighted = |highlighter: &mut IncrementalHighlighter<BlockComments>,
+                                     lines: &[String]| {
+            highlighter.highlighter().num_lines.set(0);
+            let job = highlighter.highlight(&lines.concat());
+            assert_eq!(job.sections.len(), lines.len());
+            highlighter.highlighter().num_lines.get()
+        };
+
+        assert_eq!(num_lines_highlighted(&mut highlighter, &lines), 100);
+        assert_eq!(num_lines_highlighted(&mut highlighter, &lines), 0);
+
+        // Editing a line only highlights that line again:
+        lines[50] = "edited\n".to_owned();
+        assert_eq!(num_lines_highlighted(&mut highlighter, &lines), 1);
+
+        lines.insert(20, "inserted\n".to_owned());
+        assert_eq!(num_lines_highlighted(&mut highlighter, &lines), 1);
+
+        // Opening a comment changes the state of all the following lines:
+        lines[60] = "/* comment\n".to_owned();
+        assert_eq!(num_lines_highlighted(&mut highlighter, &lines), 41);
+
+        // …until it is closed:
+        lines[70] = "comment */\n".to_owned();
+        assert_eq!(num_lines_highlighted(&mut highlighter, &lines), 31);
+        let job = highlighter.highlight(&lines.concat());
+        assert_eq!(job.sections[65].format.color, egui::Color32::GREEN);
+        assert_ne!(job.sections[75].format.color, egui::Color32::GREEN);
+
+        // Lines added at the end continue from the state after the last line:
+        lines.push("/* comment\n".to_owned());
+        assert_eq!(num_lines_highlighted(&mut highlighter, &lines), 1);
+        lines.push("comment\n".to_owned());
+        assert_eq!(num_lines_highlighted(&mut highlighter, &lines), 1);
+        let job = highlighter.highlight(&lines.concat());
+        assert_eq!(
+            job.sections.last().unwrap().format.color,
+            egui::Color32::GREEN
+        );
+    }
+
+    #[test]
+    fn incremental_highlighting_matches_full_highlighting() {
+        let mut lines: Vec<String> = (0..20).map(|i| format!("line {i}\n")).collect();
+        lines[5] = "/* comment\n".to_owned();
+        lines[8] = "comment */\n".to_owned();
+        let mut highlighter = IncrementalHighlighter::new(BlockComments::default());
+        highlighter.highlight(&lines.concat());
+
+        let mut check_edit = |lines: &[String], expected_num_lines: usize| {
+            highlighter.highlighter().num_lines.set(0);
+            let code = lines.concat();
+            let job = highlighter.highlight(&code);
+            assert_eq!(
+                highlighter.highlighter().num_lines.get(),
+                expected_num_lines,
+                "{code}"
+            );
+            let full = IncrementalHighlighter::new(BlockComments::default()).highlight(&code);
+            assert_eq!(job, full, "{code}");
+        };
+
+        // Closing the comment earlier changes the lines up to the old end of it:
+        lines[6] = "end */\n".to_owned();
+        check_edit(&lines, 3);
+
+        // An edit that doesn't change the state only affects its own line:
+        lines[12] = "edited\n".to_owned();
+        check_edit(&lines, 1);
+        lines.insert(15, "/* one line */\n".to_owned());
+        check_edit(&lines, 1);
+        lines.remove(3);
+        check_edit(&lines, 0);
+    }
+
+    #[cfg(feature = "syntect")]
+    #[test]
+    fn custom_syntax_and_theme() {
+        let ctx = egui::Context::default();
+
+        let mut settings = SyntectSettings::default();
+        let syntax = syntect::parsing::SyntaxDefinition::load_from_str(
+            r#"
+name: Greeting
+file_extensions: [greet]
+scope: source.greet
+contexts:
+  main:
+    - match: '\bhello\b'
+      scope: keyword.control.greet
+"#,
+            true,
+            None,
+        )
+        .unwrap();
+        settings.add_syntax(syntax);
+        let my_theme = settings.ts.themes["base16-ocean.dark"].clone();
+        settings.add_theme("My theme", my_theme);
+        set_syntect_settings(&ctx, settings);
+
+        let mut theme = CodeTheme::dark(12.0);
+        theme.set_syntect_theme("My theme");
+        assert_eq!(theme.syntect_theme_name(), "My theme");
+
+        let job = highlight(&ctx, &ctx.global_style(), &theme, "hello world\n", "greet");
+        assert_eq!(job.sections[0].byte_range, 0..5);
+        assert_ne!(job.sections[0].format.color, job.sections[1].format.color);
+
+        // Themes missing from the settings fall back to a built-in one:
+        theme.set_syntect_theme("Missing theme");
+        let job = highlight(&ctx, &ctx.global_style(), &theme, "hello world\n", "greet");
+        assert_eq!(job.sections[0].byte_range, 0..5);
+    }
+
+    #[cfg(all(feature = "syntect", feature = "serde"))]
+    #[test]
+    fn persisted_syntax_files() {
+        let ctx = egui::Context::default();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("greeting.sublime-syntax");
+        std::fs::write(
+            &path,
+            "name: Greeting\nfile_extensions: [greet]\nscope: source.greet\ncontexts:\n  main: []\n",
+        )
+        .unwrap();
+        let mut settings = SyntectSettings::default();
+        settings.add_syntax_from_file(&path).unwrap();
+        set_syntect_settings(&ctx, settings);
+
+        // Only the persisted data survives a restart:
+        ctx.data_mut(|d| d.remove_temp::<PrivateSettings>(egui::Id::NULL));
+        let settings = syntect_settings(&ctx);
+        assert_eq!(settings.syntax_files, [path]);
+        assert!(settings.find_syntax("greet").is_some());
+    }
+}
@@ -4,13 +4,14 @@
 //! Takes all available height, so if you want something below the table, put it in a strip.
 
 use egui::{
-    Align, Id, NumExt as _, Rangef, Rect, Response, ScrollArea, Ui, Vec2, Vec2b,
+    Align, Id, Key, Modifiers, NumExt as _, Rangef, Rect, Response, ScrollArea, Ui, Vec2, Vec2b,
+    accesskit::Role,
     scroll_area::{ScrollAreaOutput, ScrollBarVisibility, ScrollSource},
 };
 
 use crate::{
     StripLayout,
-    layout::{CellDirection, CellSize, StripLayoutFlags},
+    layout::{CellAccessibility, CellDirection, CellSize, StripLayoutFlags},
 };
 
 // -----------------------------------------------------------------=----------
@@ -253,6 +254,7 @@ pub struct TableBuilder<'a> {
     cell_layout: egui::Layout,
     scroll_options: TableScrollOptions,
     sense: egui::Sense,
+    keyboard_navigation: bool,
 }
 
 impl<'a> TableBuilder<'a> {
@@ -267,6 +269,7 @@ impl<'a> TableBuilder<'a> {
             cell_layout,
             scroll_options: Default::default(),
             sense: egui::Sense::hover(),
+            keyboard_navigation: false,
         }
     }
 
@@ -304,6 +307,21 @@ impl<'a> TableBuilder<'a> {
         self
     }
 
+    /// Let the cells receive keyboard focus, and move the focus between them with the arrow keys.
+    ///
+    /// `Home` and `End` move to the first and last column of the row.
+    /// Moving up or down also works into rows that [`TableBody::rows`] hasn't shown yet,
+    /// by scrolling to them.
+    ///
+    /// Screen readers follow the focus, so this is how keyboard users explore a table.
+    ///
+    /// Default is `false`.
+    #[inline]
+    pub fn keyboard_navigation(mut self, keyboard_navigation: bool) -> Self {
+        self.keyboard_navigation = keyboard_navigation;
+        self
+    }
+
     /// Make the columns resizable by dragging.
     ///
     /// You can set this for individual columns with [`Column::resizable`].
@@ -468,6 +486,7 @@ impl<'a> TableBuilder<'a> {
             cell_layout,
             scroll_options,
             sense,
+            keyboard_navigation,
         } = self;
 
         for (i, column) in columns.iter_mut().enumerate() {
@@ -486,6 +505,8 @@ impl<'a> TableBuilder<'a> {
         let (is_sizing_pass, state) =
             TableState::load(ui, state_id, resizable, &columns, available_width);
 
+        let nav = TableNav::new(ui, state_id, columns.len(), true, keyboard_navigation);
+
         let mut max_used_widths = vec![0.0; columns.len()];
         let table_top = ui.cursor().top();
 
@@ -509,6 +530,8 @@ impl<'a> TableBuilder<'a> {
                 selected: false,
                 overline: false,
                 response: &mut response,
+                nav,
+                is_header: true,
             });
             layout.allocate_rect();
         });
@@ -527,6 +550,7 @@ impl<'a> TableBuilder<'a> {
             cell_layout,
             scroll_options,
             sense,
+            nav,
         }
     }
 
@@ -546,6 +570,7 @@ impl<'a> TableBuilder<'a> {
             cell_layout,
             scroll_options,
             sense,
+            keyboard_navigation,
         } = self;
 
         let striped = striped.unwrap_or_else(|| ui.visuals().striped);
@@ -555,6 +580,8 @@ impl<'a> TableBuilder<'a> {
         let (is_sizing_pass, state) =
             TableState::load(ui, state_id, resizable, &columns, available_width);
 
+        let nav = TableNav::new(ui, state_id, columns.len(), false, keyboard_navigation);
+
         let max_used_widths = vec![0.0; columns.len()];
         let table_top = ui.cursor().top();
 
@@ -572,6 +599,7 @@ impl<'a> TableBuilder<'a> {
             cell_layout,
             scroll_options,
             sense,
+            nav,
         }
         .body(add_body_contents)
     }
@@ -697,6 +725,8 @@ pub struct Table<'a> {
     scroll_options: TableScrollOptions,
 
     sense: egui::Sense,
+
+    nav: TableNav,
 }
 
 impl Table<'_> {
@@ -726,6 +756,7 @@ impl Table<'_> {
             cell_layout,
             scroll_options,
             sense,
+            nav,
         } = self;
 
         let TableScrollOptions {
@@ -741,6 +772,13 @@ impl Table<'_> {
             animated,
         } = scroll_options;
 
+        // Scroll to the cell that keyboard navigation is moving to, in case it isn't shown yet:
+        let scroll_to_row = scroll_to_row.or_else(|| {
+            nav.focus_target(ui.ctx())
+                .filter(|target| !target.header)
+                .map(|target| (target.row, None))
+        });
+
         let cursor_position = ui.cursor().min;
 
         let mut scroll_area = ScrollArea::new([false, vscroll])
@@ -792,6 +830,8 @@ impl Table<'_> {
                     scroll_to_y_range: &mut scroll_to_y_range,
                     hovered_row_index,
                     hovered_row_index_id,
+                    nav,
+                    num_rows: 0,
                 });
 
                 if scroll_to_row.is_some() && scroll_to_y_range.is_none() {
@@ -946,6 +986,11 @@ pub struct TableBody<'a> {
 
     /// Used to store the hovered row index between frames.
     hovered_row_index_id: egui::Id,
+
+    nav: TableNav,
+
+    /// Total number of rows, including the ones that weren't shown.
+    num_rows: usize,
 }
 
 impl<'a> TableBody<'a> {
@@ -996,6 +1041,8 @@ impl<'a> TableBody<'a> {
             selected: false,
             overline: false,
             response: &mut response,
+            nav: self.nav,
+            is_header: false,
         });
         self.capture_hover_state(&response, self.row_index);
         let bottom_y = self.layout.cursor.y;
@@ -1005,6 +1052,7 @@ impl<'a> TableBody<'a> {
         }
 
         self.row_index += 1;
+        self.num_rows = self.num_rows.max(self.row_index);
     }
 
     /// Add many rows with same height.
@@ -1039,6 +1087,7 @@ impl<'a> TableBody<'a> {
     ) {
         let spacing = self.layout.ui.spacing().item_spacing;
         let row_height_with_spacing = row_height_sans_spacing + spacing.y;
+        self.num_rows = self.num_rows.max(total_rows);
 
         if let Some(scroll_to_row) = self.scroll_to_row {
             let scroll_to_row = scroll_to_row.at_most(total_rows.saturating_sub(1)) as f32;
@@ -1078,6 +1127,8 @@ impl<'a> TableBody<'a> {
                 selected: false,
                 overline: false,
                 response: &mut response,
+                nav: self.nav,
+                is_header: false,
             });
             self.capture_hover_state(&response, row_index);
         }
@@ -1133,6 +1184,7 @@ impl<'a> TableBody<'a> {
 
         // Skip the invisible rows, and populate the first non-virtual row.
         for (row_index, row_height) in &mut enumerated_heights {
+            self.num_rows = self.num_rows.max(row_index + 1);
             let old_cursor_y = cursor_y;
             cursor_y += (row_height + spacing.y) as f64;
 
@@ -1160,6 +1212,8 @@ impl<'a> TableBody<'a> {
                     selected: false,
                     overline: false,
                     response: &mut response,
+                    nav: self.nav,
+                    is_header: false,
                 });
                 self.capture_hover_state(&response, row_index);
                 break;
@@ -1168,6 +1222,7 @@ impl<'a> TableBody<'a> {
 
         // populate visible rows:
         for (row_index, row_height) in &mut enumerated_heights {
+            self.num_rows = self.num_rows.max(row_index + 1);
             let top_y = cursor_y;
             let mut response: Option<Response> = None;
             add_row_content(TableRow {
@@ -1183,6 +1238,8 @@ impl<'a> TableBody<'a> {
                 overline: false,
                 selected: false,
                 response: &mut response,
+                nav: self.nav,
+                is_header: false,
             });
             self.capture_hover_state(&response, row_index);
             cursor_y += (row_height + spacing.y) as f64;
@@ -1202,6 +1259,7 @@ impl<'a> TableBody<'a> {
         // calculate height below the visible table range:
         let mut height_below_visible: f64 = 0.0;
         for (row_index, row_height) in enumerated_heights {
+            self.num_rows = self.num_rows.max(row_index + 1);
             height_below_visible += (row_height + spacing.y) as f64;
 
             let top_y = cursor_y;
@@ -1248,6 +1306,18 @@ impl<'a> TableBody<'a> {
 impl Drop for TableBody<'_> {
     fn drop(&mut self) {
         self.layout.allocate_rect();
+
+        let ctx = self.layout.ui.ctx();
+        let num_rows = self.num_rows + usize::from(self.nav.has_header);
+        ctx.accesskit_node_builder(self.nav.table_id, |node| node.set_row_count(num_rows));
+
+        // Don't try to move the focus past the last row:
+        if let Some(target) = self.nav.focus_target(ctx)
+            && !target.header
+            && self.num_rows <= target.row
+        {
+            ctx.data_mut(|data| data.remove::<CellIndex>(self.nav.focus_target_id()));
+        }
     }
 }
 
@@ -1271,6 +1341,9 @@ pub struct TableRow<'a, 'b> {
     overline: bool,
 
     response: &'b mut Option<Response>,
+
+    nav: TableNav,
+    is_header: bool,
 }
 
 impl TableRow<'_, '_> {
@@ -1308,16 +1381,44 @@ impl TableRow<'_, '_> {
             selected: self.selected,
             overline: self.overline,
             sizing_pass: auto_size_this_frame || self.layout.ui.is_sizing_pass(),
+            focusable: self.nav.keyboard_navigation,
+        };
+
+        let accessibility = CellAccessibility {
+            row_id: self
+                .nav
+                .accesskit_row(self.layout.ui, self.is_header, self.row_index),
+            role: if self.is_header {
+                Role::ColumnHeader
+            } else {
+                Role::Cell
+            },
+            row_index: self.nav.accesskit_row_index(self.is_header, self.row_index),
+            col_index,
+        };
+
+        let child_ui_id_salt = if self.nav.keyboard_navigation {
+            // Focus is tracked by id, so it must not depend on which rows are shown:
+            self.nav
+                .table_id
+                .with((self.is_header, self.row_index, col_index))
+        } else {
+            egui::Id::new((self.row_index, col_index))
         };
 
         let (used_rect, response) = self.layout.add(
             flags,
             width,
             height,
-            egui::Id::new((self.row_index, col_index)),
+            child_ui_id_salt,
+            Some(accessibility),
             add_cell_contents,
         );
 
+        if self.nav.keyboard_navigation && !flags.sizing_pass {
+            self.keyboard_navigation(col_index, &response);
+        }
+
         if let Some(max_w) = self.max_used_widths.get_mut(col_index) {
             *max_w = max_w.max(used_rect.width());
         }
@@ -1370,6 +1471,98 @@ impl TableRow<'_, '_> {
     pub fn col_index(&self) -> usize {
         self.col_index
     }
+
+    fn keyboard_navigation(&self, col_index: usize, response: &Response) {
+        let ctx = &response.ctx;
+        let this = CellIndex {
+            header: self.is_header,
+            row: self.row_index,
+            col: col_index,
+        };
+
+        if self.nav.focus_target(ctx) == Some(this) {
+            ctx.data_mut(|data| data.remove::<CellIndex>(self.nav.focus_target_id()));
+            response.request_focus();
+            response.scroll_to_me(None);
+        }
+
+        if !response.has_focus() {
+            return;
+        }
+
+        let ui = &self.layout.ui;
+        ui.painter().rect_stroke(
+            response.rect,
+            0.0,
+            ui.visuals().selection.stroke,
+            egui::StrokeKind::Inside,
+        );
+
+        // We move the focus with the arrow keys ourselves,
+        // so that we can reach rows that aren't shown yet:
+        ctx.memory_mut(|mem| {
+            mem.set_focus_lock_filter(
+                response.id,
+                egui::EventFilter {
+                    horizontal_arrows: true,
+                    vertical_arrows: true,
+                    ..Default::default()
+                },
+            );
+        });
+
+        let num_columns = self.widths.len();
+        let has_header = self.nav.has_header;
+        let target = ctx.input_mut(|i| {
+            if i.consume_key(Modifiers::NONE, Key::ArrowLeft) {
+                col_index
+                    .checked_sub(1)
+                    .map(|col| CellIndex { col, ..this })
+            } else if i.consume_key(Modifiers::NONE, Key::ArrowRight) {
+                (col_index + 1 < num_columns).then_some(CellIndex {
+                    col: col_index + 1,
+                    ..this
+                })
+            } else if i.consume_key(Modifiers::NONE, Key::Home) {
+                Some(CellIndex { col: 0, ..this })
+            } else if i.consume_key(Modifiers::NONE, Key::End) {
+                Some(CellIndex {
+                    col: num_columns.saturating_sub(1),
+                    ..this
+                })
+            } else if i.consume_key(Modifiers::NONE, Key::ArrowUp) {
+                match (this.header, this.row.checked_sub(1)) {
+                    (true, _) => None,
+                    (false, Some(row)) => Some(CellIndex { row, ..this }),
+                    (false, None) => has_header.then_some(CellIndex {
+                        header: true,
+                        ..this
+                    }),
+                }
+            } else if i.consume_key(Modifiers::NONE, Key::ArrowDown) {
+                Some(if this.header {
+                    CellIndex {
+                        header: false,
+                        row: 0,
+                        col: col_index,
+                    }
+                } else {
+                    CellIndex {
+                        row: this.row + 1,
+                        ..this
+                    }
+                })
+            } else {
+                None
+            }
+        });
+
+        if let Some(target) = target {
+            ctx.data_mut(|data| data.insert_temp(self.nav.focus_target_id(), target));
+            ctx.memory_mut(|mem| mem.move_focus(egui::FocusDirection::None));
+            ctx.request_repaint();
+        }
+    }
 }
 
 impl Drop for TableRow<'_, '_> {
@@ -1378,3 +1571,90 @@ impl Drop for TableRow<'_, '_> {
         self.layout.end_line();
     }
 }
+
+// ----------------------------------------------------------------------------
+
+/// A cell, for keyboard navigation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct CellIndex {
+    /// Is this a cell in the header row?
+    header: bool,
+
+    /// Index of the body row (ignored for the header).
+    row: usize,
+
+    col: usize,
+}
+
+/// Accessibility and keyboard navigation info shared by all rows of a table.
+#[derive(Clone, Copy)]
+struct TableNav {
+    /// The AccessKit node of the whole table.
+    table_id: Id,
+
+    has_header: bool,
+    keyboard_navigation: bool,
+}
+
+impl TableNav {
+    fn new(
+        ui: &Ui,
+        state_id: Id,
+        num_columns: usize,
+        has_header: bool,
+        keyboard_navigation: bool,
+    ) -> Self {
+        let table_id = state_id.with("__accesskit_table");
+        ui.ctx().register_accesskit_parent(table_id, ui.unique_id());
+        ui.ctx().accesskit_node_builder(table_id, |node| {
+            node.set_role(Role::Table);
+            node.set_column_count(num_columns);
+        });
+
+        Self {
+            table_id,
+            has_header,
+            keyboard_navigation,
+        }
+    }
+
+    /// Where we store the [`CellIndex`] that should receive focus.
+    fn focus_target_id(&self) -> Id {
+        self.table_id.with("__focus_target")
+    }
+
+    fn focus_target(&self, ctx: &egui::Context) -> Option<CellIndex> {
+        if self.keyboard_navigation {
+            ctx.data(|data| data.get_temp(self.focus_target_id()))
+        } else {
+            None
+        }
+    }
+
+    /// The row index reported to AccessKit, which counts the header row.
+    fn accesskit_row_index(&self, is_header: bool, row_index: usize) -> usize {
+        if is_header {
+            0
+        } else {
+            row_index + usize::from(self.has_header)
+        }
+    }
+
+    /// Create the AccessKit node of a row, and return its id.
+    fn accesskit_row(&self, ui: &Ui, is_header: bool, row_index: usize) -> Id {
+        let row_id = if is_header {
+            self.table_id.with("__header")
+        } else {
+            self.table_id.with(row_index)
+        };
+        let accesskit_row_index = self.accesskit_row_index(is_header, row_index);
+
+        ui.ctx().register_accesskit_parent(row_id, self.table_id);
+        ui.ctx().accesskit_node_builder(row_id, |node| {
+            node.set_role(Role::Row);
+            node.set_row_index(accesskit_row_index);
+        });
+
+        row_id
+    }
+}
@@ -0,0 +1,206 @@
+//! Load [`egui::ThemeFile`]s from disk, with hot reloading in debug builds.
+//!
+//! ```no_run
+//! # let mut theme_watcher = egui_extras::theme::ThemeWatcher::new("my_theme.toml");
+//! # egui::__run_test_ctx(|ctx| {
+//! // Once per frame:
+//! theme_watcher.update(ctx);
+//! # });
+//! ```
+
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use egui::{ThemeFile, ThemeFileError};
+
+/// How often [`ThemeWatcher`] checks the file for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The file formats a theme can be written in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ThemeFormat {
+    /// [JSON](https://www.json.org), with a `.json` extension.
+    Json,
+
+    /// [RON](https://github.com/ron-rs/ron), with a `.ron` extension.
+    Ron,
+
+    /// [TOML](https://toml.io), with a `.toml` extension.
+    Toml,
+}
+
+impl ThemeFormat {
+    /// Guess the format from the file extension (`.json`, `.ron` or `.toml`).
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "json" => Some(Self::Json),
+            "ron" => Some(Self::Ron),
+            "toml" => Some(Self::Toml),
+            _ => None,
+        }
+    }
+
+    /// Parse and validate a theme written in this format.
+    ///
+    /// # Errors
+    /// If the text is malformed, or does not describe a valid theme.
+    pub fn parse(self, text: &str) -> Result<ThemeFile, ThemeFileError> {
+        match self {
+            Self::Json => ThemeFile::from_json(text),
+            Self::Ron => ThemeFile::from_ron(text),
+            Self::Toml => {
+                let value: serde_json::Value =
+                    toml::from_str(text).map_err(|err| ThemeFileError::Parse(err.to_string()))?;
+                ThemeFile::from_value(value)
+            }
+        }
+    }
+}
+
+/// Read and parse a theme file.
+///
+/// The format is picked based on the file extension.
+///
+/// # Errors
+/// If the file can't be read, has an unknown extension, or does not contain a valid theme.
+pub fn load_theme(path: impl AsRef<Path>) -> Result<ThemeFile, ThemeFileError> {
+    let path = path.as_ref();
+    let format = ThemeFormat::from_path(path).ok_or_else(|| {
+        ThemeFileError::Parse(format!(
+            "Unknown theme format for {:?}: expected a .json, .ron or .toml extension",
+            path.display()
+        ))
+    })?;
+    let text = std::fs::read_to_string(path).map_err(|err| {
+        ThemeFileError::Parse(format!("Failed to read {:?}: {err}", path.display()))
+    })?;
+    format.parse(&text)
+}
+
+/// Applies a theme file to a [`egui::Context`], and in debug builds reloads it whenever it changes on disk.
+///
+/// The theme is set with [`ThemeFile::apply`], i.e. it replaces the style of its `base` theme.
+pub struct ThemeWatcher {
+    path: PathBuf,
+
+    /// Modification time of the file the last time we loaded it.
+    modified: Option<SystemTime>,
+
+    /// When we last checked the file, in [`egui::InputState::time`].
+    last_check: Option<f64>,
+
+    error: Option<ThemeFileError>,
+}
+
+impl ThemeWatcher {
+    /// Watch the theme file at this path. It is first loaded by [`Self::update`].
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            modified: None,
+            last_check: None,
+            error: None,
+        }
+    }
+
+    /// The path of the watched theme file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The error from the last attempt at loading the theme, if it failed.
+    ///
+    /// When a reload fails, the previously loaded theme stays in effect.
+    pub fn error(&self) -> Option<&ThemeFileError> {
+        self.error.as_ref()
+    }
+
+    /// Load the theme the first time this is called.
+    ///
+    /// In debug builds, this also checks the file for changes (at most twice per second)
+    /// and reloads it when it was modified.
+    ///
+    /// Call this once per frame.
+    pub fn update(&mut self, ctx: &egui::Context) {
+        let now = ctx.input(|i| i.time);
+        let first_time = self.last_check.is_none();
+
+        if !first_time {
+            if !cfg!(debug_assertions) {
+                return;
+            }
+
+            // Keep polling even if nothing else triggers a repaint.
+            ctx.request_repaint_after(POLL_INTERVAL);
+
+            if self
+                .last_check
+                .is_some_and(|last_check| now - last_check < POLL_INTERVAL.as_secs_f64())
+            {
+                return;
+            }
+        }
+        self.last_check = Some(now);
+
+        let modified = std::fs::metadata(&self.path)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        if !first_time && modified == self.modified {
+            return;
+        }
+        self.modified = modified;
+
+        match load_theme(&self.path).and_then(|theme| theme.apply(ctx)) {
+            Ok(()) => {
+                log::debug!("Loaded theme from {}", self.path.display());
+                self.error = None;
+            }
+            Err(err) => {
+                log::warn!("{err}");
+                self.error = Some(err);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_toml() {
+        let theme = ThemeFormat::Toml
+            .parse(
+                r#"
+                base = "Light"
+
+                [visuals]
+                hyperlink_color = [1, 2, 3, 255]
+
+                [style.spacing]
+                indent = 30.0
+                "#,
+            )
+            .unwrap();
+
+        let style = theme.style().unwrap();
+        assert_eq!(theme.base, egui::Theme::Light);
+        assert_eq!(
+            style.visuals.hyperlink_color,
+            egui::Color32::from_rgb(1, 2, 3)
+        );
+        assert_eq!(style.spacing.indent, 30.0);
+    }
+
+    #[test]
+    fn format_from_extension() {
+        assert_eq!(
+            ThemeFormat::from_path(Path::new("themes/dark.TOML")),
+            Some(ThemeFormat::Toml)
+        );
+        assert_eq!(ThemeFormat::from_path(Path::new("theme.yaml")), None);
+    }
+}
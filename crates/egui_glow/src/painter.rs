@@ -1,7 +1,10 @@
 #![allow(clippy::collapsible_else_if)]
 #![allow(unsafe_code)]
 
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use egui::{
     emath::Rect,
@@ -87,6 +90,7 @@ pub struct Painter {
     program: glow::Program,
     u_screen_size: glow::UniformLocation,
     u_sampler: glow::UniformLocation,
+    u_distance_field: glow::UniformLocation,
     is_webgl_1: bool,
     vao: crate::vao::VertexArrayObject,
     srgb_textures: bool,
@@ -96,6 +100,9 @@ pub struct Painter {
 
     textures: HashMap<egui::TextureId, glow::Texture>,
 
+    /// The textures with [`egui::TextureOptions::distance_field`].
+    distance_field_textures: HashSet<egui::TextureId>,
+
     next_native_tex_id: u64,
 
     /// Stores outdated OpenGL textures that are yet to be deleted
@@ -212,6 +219,9 @@ impl Painter {
             gl.delete_shader(frag);
             let u_screen_size = gl.get_uniform_location(program, "u_screen_size").unwrap();
             let u_sampler = gl.get_uniform_location(program, "u_sampler").unwrap();
+            let u_distance_field = gl
+                .get_uniform_location(program, "u_distance_field")
+                .unwrap();
 
             let vbo = gl.create_buffer()?;
 
@@ -258,6 +268,7 @@ impl Painter {
                 program,
                 u_screen_size,
                 u_sampler,
+                u_distance_field,
                 is_webgl_1,
                 vao,
                 srgb_textures,
@@ -265,6 +276,7 @@ impl Painter {
                 vbo,
                 element_array_buffer,
                 textures: Default::default(),
+                distance_field_textures: Default::default(),
                 next_native_tex_id: 1 << 32,
                 textures_to_destroy: Vec::new(),
                 destroyed: false,
@@ -484,6 +496,12 @@ impl Painter {
                 );
 
                 self.gl.bind_texture(glow::TEXTURE_2D, Some(texture));
+
+                let distance_field = self.distance_field_textures.contains(&mesh.texture_id);
+                self.gl.uniform_1_f32(
+                    Some(&self.u_distance_field),
+                    if distance_field { 1.0 } else { 0.0 },
+                );
             }
 
             unsafe {
@@ -516,6 +534,12 @@ impl Painter {
             self.gl.bind_texture(glow::TEXTURE_2D, Some(glow_texture));
         }
 
+        if delta.options.distance_field {
+            self.distance_field_textures.insert(tex_id);
+        } else {
+            self.distance_field_textures.remove(&tex_id);
+        }
+
         match &delta.image {
             egui::ImageData::Color(image) => {
                 assert_eq!(
@@ -632,6 +656,7 @@ impl Painter {
     }
 
     pub fn free_texture(&mut self, tex_id: egui::TextureId) {
+        self.distance_field_textures.remove(&tex_id);
         if let Some(old_tex) = self.textures.remove(&tex_id) {
             unsafe { self.gl.delete_texture(old_tex) };
         }
@@ -1,3 +1,14 @@
+// Screen-space derivatives are needed for distance field textures.
+// They are always available, except on WebGL1 (and other GLSL ES 1.0), where they are an extension:
+#if defined(GL_ES) && !NEW_SHADER_INTERFACE
+    #ifdef GL_OES_standard_derivatives
+        #extension GL_OES_standard_derivatives : enable
+        #define HAS_DERIVATIVES 1
+    #endif
+#else
+    #define HAS_DERIVATIVES 1
+#endif
+
 #ifdef GL_ES
     // To avoid weird distortion issues when rendering text etc, we want highp if possible.
     // But apparently some devices don't support it, so we have to check first.
@@ -10,6 +21,9 @@
 
 uniform sampler2D u_sampler;
 
+// 1.0 if the texture is a signed distance field (`TextureOptions::distance_field`), else 0.0.
+uniform float u_distance_field;
+
 #if NEW_SHADER_INTERFACE
     in vec4 v_rgba_in_gamma;
     in vec2 v_tc;
@@ -46,6 +60,18 @@ vec3 dither_interleaved(vec3 rgb, float levels) {
 void main() {
     vec4 texture_in_gamma = texture2D(u_sampler, v_tc);
 
+    if (u_distance_field > 0.5) {
+        // Turn the signed distance in the alpha channel into coverage,
+        // with an anti-aliased edge that is one pixel wide at any scale.
+        float distance = texture_in_gamma.a;
+#ifdef HAS_DERIVATIVES
+        float pixel_width = max(fwidth(distance), 0.0001);
+#else
+        float pixel_width = 0.1; // Soft, but better than nothing
+#endif
+        texture_in_gamma = vec4(clamp((distance - 0.5) / pixel_width + 0.5, 0.0, 1.0));
+    }
+
     // We multiply the colors in gamma space, because that's the only way to get text to look right.
     vec4 frag_color_gamma = v_rgba_in_gamma * texture_in_gamma;
 
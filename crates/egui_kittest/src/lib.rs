@@ -73,6 +73,7 @@ pub struct Harness<'a, State = ()> {
     input: egui::RawInput,
     kittest: kittest::State,
     output: egui::FullOutput,
+    output_events: Vec<egui::output::OutputEvent>,
     app: AppKind<'a, State>,
     response: Option<egui::Response>,
     state: State,
@@ -120,6 +121,8 @@ impl<'a, State> Harness<'a, State> {
         ctx.set_theme(theme);
         ctx.set_os(os);
         ctx.enable_accesskit();
+        // The wgpu renderer supports distance field textures:
+        ctx.set_distance_field_supported(cfg!(feature = "wgpu"));
         ctx.all_styles_mut(|style| {
             // Disable cursor blinking so it doesn't interfere with snapshots
             style.visuals.text_cursor.blink = false;
@@ -154,6 +157,7 @@ impl<'a, State> Harness<'a, State> {
                     .take()
                     .expect("AccessKit was disabled"),
             ),
+            output_events: output.platform_output.events.clone(),
             output,
             response,
             state,
@@ -298,6 +302,8 @@ impl<'a, State> Harness<'a, State> {
                 .expect("AccessKit was disabled"),
         );
         self.renderer.handle_delta(&output.textures_delta);
+        self.output_events
+            .extend(output.platform_output.events.iter().cloned());
         self.output = output;
     }
 
@@ -470,6 +476,37 @@ impl<'a, State> Harness<'a, State> {
         &self.output
     }
 
+    /// The [`egui::output::OutputEvent`]s of all frames since the last call to
+    /// [`Harness::take_output_events`].
+    ///
+    /// Unlike [`Harness::output`], this includes the events of all steps of a [`Harness::run`].
+    pub fn output_events(&self) -> &[egui::output::OutputEvent] {
+        &self.output_events
+    }
+
+    /// Take the [`egui::output::OutputEvent`]s collected so far, see [`Harness::output_events`].
+    ///
+    /// ```
+    /// # use egui::{output::OutputEvent, Politeness};
+    /// # use egui_kittest::Harness;
+    /// let mut harness = Harness::new(|ctx| {
+    ///     if ctx.cumulative_pass_nr() == 0 {
+    ///         ctx.announce("File saved", Politeness::Polite);
+    ///     }
+    /// });
+    /// harness.run();
+    /// assert_eq!(
+    ///     harness.take_output_events(),
+    ///     [OutputEvent::Announcement {
+    ///         text: "File saved".to_owned(),
+    ///         politeness: Politeness::Polite,
+    ///     }]
+    /// );
+    /// ```
+    pub fn take_output_events(&mut self) -> Vec<egui::output::OutputEvent> {
+        std::mem::take(&mut self.output_events)
+    }
+
     /// Access the [`kittest::State`].
     pub fn kittest_state(&self) -> &kittest::State {
         &self.kittest
@@ -661,6 +698,40 @@ impl<'a, State> Harness<'a, State> {
         });
     }
 
+    /// Find all text in the last output that doesn't meet the given WCAG contrast level.
+    ///
+    /// The panel fill of the current theme is assumed to be behind everything.
+    /// See [`egui::contrast`] for details.
+    pub fn contrast_issues(
+        &self,
+        level: egui::contrast::WcagLevel,
+    ) -> Vec<egui::contrast::ContrastIssue> {
+        let background = self.ctx.global_style().visuals.panel_fill;
+        egui::contrast::audit_contrast(&self.output.shapes, level, background)
+    }
+
+    /// Panic if any text in the last output doesn't meet the given WCAG contrast level.
+    ///
+    /// ```
+    /// # use egui_kittest::Harness;
+    /// let harness = Harness::new_ui(|ui| {
+    ///     ui.label("Hello world!");
+    /// });
+    /// harness.assert_contrast(egui::contrast::WcagLevel::AA);
+    /// ```
+    #[track_caller]
+    pub fn assert_contrast(&self, level: egui::contrast::WcagLevel) {
+        let issues = self.contrast_issues(level);
+        if !issues.is_empty() {
+            let issues: Vec<String> = issues.iter().map(ToString::to_string).collect();
+            panic!(
+                "{} text runs don't meet WCAG {level} contrast:\n{}",
+                issues.len(),
+                issues.join("\n")
+            );
+        }
+    }
+
     /// Render the last output to an image.
     ///
     /// # Errors
@@ -2,7 +2,7 @@
 
 use egui::{
     CentralPanel, Context, RawInput, Window,
-    accesskit::{NodeId, Role, TreeUpdate},
+    accesskit::{Node, NodeId, Role, TreeUpdate},
 };
 
 /// Baseline test that asserts there are no spurious nodes in the
@@ -138,6 +138,302 @@ fn window_children() {
     assert_button_exists(&output, "Hide", window_id);
 }
 
+#[test]
+fn grid_table_semantics() {
+    let output = accesskit_output_single_egui_frame(|ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            egui::Grid::new("grid").show(ui, |ui| {
+                ui.label("a");
+                ui.label("b");
+                ui.end_row();
+
+                ui.label("c");
+                ui.horizontal(|ui| {
+                    ui.label("d");
+                });
+                ui.end_row();
+            });
+        });
+    });
+
+    let (table_id, table) = find_node(&output, |node| node.role() == Role::Table);
+    assert_eq!(table.row_count(), Some(2));
+    assert_eq!(table.column_count(), Some(2));
+
+    for (label, row, col) in [("a", 0, 0), ("b", 0, 1), ("c", 1, 0), ("d", 1, 1)] {
+        let (label_id, _) = find_node(&output, |node| node.value() == Some(label));
+        let (cell_id, _) = find_node(&output, |node| {
+            node.role() == Role::Cell
+                && node.row_index() == Some(row)
+                && node.column_index() == Some(col)
+        });
+        assert_parent_child(&output, cell_id, label_id);
+        assert_parent_child(&output, table_id, cell_id);
+    }
+}
+
+#[test]
+fn grid_header_row() {
+    let output = accesskit_output_single_egui_frame(|ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            egui::Grid::new("grid").header_row(true).show(ui, |ui| {
+                ui.label("Name");
+                ui.label("Size");
+                ui.end_row();
+
+                ui.label("a.txt");
+                ui.label("1 kB");
+                ui.end_row();
+            });
+        });
+    });
+
+    for (label, role) in [("Size", Role::ColumnHeader), ("1 kB", Role::Cell)] {
+        let (label_id, _) = find_node(&output, |node| node.value() == Some(label));
+        let (cell_id, _) = find_node(&output, |node| {
+            node.role() == role && node.column_index() == Some(1)
+        });
+        assert_parent_child(&output, cell_id, label_id);
+    }
+}
+
+#[test]
+fn grid_widget_before_first_cell() {
+    let output = accesskit_output_single_egui_frame(|ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            egui::Grid::new("grid").show(ui, |ui| {
+                // Interact before any cell has been placed:
+                let response = ui.interact(
+                    ui.max_rect(),
+                    ui.id().with("background"),
+                    egui::Sense::click(),
+                );
+                response.widget_info(|| {
+                    egui::WidgetInfo::labeled(egui::WidgetType::Other, true, "Background")
+                });
+
+                ui.label("a");
+                ui.end_row();
+            });
+        });
+    });
+
+    let (table_id, _) = find_node(&output, |node| node.role() == Role::Table);
+    let (background_id, _) = find_node(&output, |node| node.label() == Some("Background"));
+    // Not to a cell that doesn't exist yet:
+    assert_parent_child(&output, table_id, background_id);
+}
+
+#[test]
+fn table_builder_semantics() {
+    use egui_extras::{Column, TableBuilder};
+
+    let output = accesskit_output_single_egui_frame(|ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            TableBuilder::new(ui)
+                .columns(Column::auto(), 2)
+                .header(20.0, |mut header| {
+                    header.col(|ui| {
+                        ui.label("Name");
+                    });
+                    header.col(|ui| {
+                        ui.label("Size");
+                    });
+                })
+                .body(|body| {
+                    body.rows(20.0, 10_000, |mut row| {
+                        let index = row.index();
+                        row.col(|ui| {
+                            ui.label(format!("File {index}"));
+                        });
+                        row.col(|ui| {
+                            ui.label(format!("{index} kB"));
+                        });
+                    });
+                });
+        });
+    });
+
+    let (table_id, table) = find_node(&output, |node| node.role() == Role::Table);
+    assert_eq!(
+        table.row_count(),
+        Some(10_001),
+        "Should count rows that aren't shown"
+    );
+    assert_eq!(table.column_count(), Some(2));
+
+    let (header_id, header) = find_node(&output, |node| {
+        node.role() == Role::ColumnHeader && node.column_index() == Some(1)
+    });
+    assert_eq!(header.row_index(), Some(0));
+    let (label_id, _) = find_node(&output, |node| node.value() == Some("Size"));
+    assert_parent_child(&output, header_id, label_id);
+    assert_parent_child(&output, table_id, header_id);
+
+    let (cell_id, cell) = find_node(&output, |node| {
+        node.role() == Role::Cell && node.column_index() == Some(0) && node.row_index() == Some(3)
+    });
+    let (label_id, _) = find_node(&output, |node| node.value() == Some("File 2"));
+    assert_parent_child(&output, cell_id, label_id);
+    let (row_id, _) = find_node(&output, |node| {
+        node.role() == Role::Row && node.row_index() == cell.row_index()
+    });
+    assert_parent_child(&output, row_id, cell_id);
+
+    // Rows that are scrolled out of view are only counted, without nodes of their own:
+    assert!(
+        !output
+            .nodes
+            .iter()
+            .any(|(_, node)| node.role() == Role::Row && node.row_index() == Some(5_001)),
+        "Should not create nodes for rows that aren't shown"
+    );
+}
+
+#[test]
+fn table_keyboard_navigation() {
+    use egui::Key;
+    use egui_extras::{Column, TableBuilder};
+    use egui_kittest::Harness;
+    use kittest::{NodeT as _, Queryable as _};
+
+    let mut harness = Harness::new_ui_state(
+        |ui, focused: &mut Option<(usize, usize)>| {
+            let request_focus = ui.ctx().cumulative_pass_nr() == 0;
+            *focused = None;
+            TableBuilder::new(ui)
+                .keyboard_navigation(true)
+                .columns(Column::exact(100.0), 3)
+                .body(|body| {
+                    body.rows(20.0, 1_000, |mut row| {
+                        let index = row.index();
+                        for col in 0..3 {
+                            let (_, response) = row.col(|ui| {
+                                ui.label(format!("{index}, {col}"));
+                            });
+                            if request_focus && index == 0 && col == 0 {
+                                response.request_focus();
+                            }
+                            if response.has_focus() {
+                                *focused = Some((index, col));
+                            }
+                        }
+                    });
+                });
+        },
+        None,
+    );
+    harness.run();
+    assert_eq!(*harness.state(), Some((0, 0)));
+
+    harness.key_press(Key::ArrowRight);
+    harness.run();
+    assert_eq!(*harness.state(), Some((0, 1)));
+
+    harness.key_press(Key::End);
+    harness.run();
+    harness.key_press(Key::ArrowRight);
+    harness.run();
+    assert_eq!(
+        *harness.state(),
+        Some((0, 2)),
+        "Should stop at the last column"
+    );
+
+    // Move way past the rows that are shown, so the table has to scroll:
+    for _ in 0..100 {
+        harness.key_press(Key::ArrowDown);
+        harness.run();
+    }
+    assert_eq!(*harness.state(), Some((100, 2)));
+
+    let focused = harness.get_by(|node| node.is_focused()).accesskit_node();
+    assert_eq!(
+        focused.role(),
+        Role::Cell,
+        "The cell should have accessibility focus"
+    );
+    assert_eq!(focused.data().row_index(), Some(100));
+    assert_eq!(focused.data().column_index(), Some(2));
+
+    harness.key_press(Key::Home);
+    harness.run();
+    harness.key_press(Key::ArrowUp);
+    harness.run();
+    assert_eq!(*harness.state(), Some((99, 0)));
+}
+
+#[test]
+fn announcement_live_region() {
+    use egui::{Politeness, output::OutputEvent};
+    use egui_kittest::Harness;
+    use kittest::{NodeT as _, Queryable as _};
+
+    let mut harness = Harness::new_ui(|ui| {
+        if ui.button("Save").clicked() {
+            ui.ctx().announce("File saved", Politeness::Polite);
+        }
+    });
+    assert!(harness.take_output_events().is_empty());
+
+    harness.get_by_label("Save").click();
+    harness.run();
+
+    let announcements: Vec<_> = harness
+        .take_output_events()
+        .into_iter()
+        .filter(|event| matches!(event, OutputEvent::Announcement { .. }))
+        .collect();
+    assert_eq!(
+        announcements,
+        [OutputEvent::Announcement {
+            text: "File saved".to_owned(),
+            politeness: Politeness::Polite,
+        }]
+    );
+
+    let live = |node: &egui::accesskit::Node| node.live() == Some(egui::accesskit::Live::Polite);
+    let node = harness.get_by(|node| live(node.data()));
+    assert_eq!(node.accesskit_node().value().as_deref(), Some("File saved"));
+
+    // The live region goes away after a while:
+    let time = harness.ctx.input(|i| i.time);
+    harness.input_mut().time = Some(time + Context::ANNOUNCEMENT_DURATION.as_secs_f64());
+    harness.run();
+    assert!(harness.query_by(|node| live(node.data())).is_none());
+}
+
+#[test]
+fn announcement_and_click_in_same_frame() {
+    use egui::{Politeness, WidgetInfo, WidgetType, output::OutputEvent};
+    use egui_kittest::Harness;
+    use kittest::Queryable as _;
+
+    let mut harness = Harness::new_ui(|ui| {
+        if ui.input(|i| i.pointer.any_click()) {
+            ui.ctx().announce("Saving", Politeness::Polite);
+        }
+        _ = ui.button("Save");
+    });
+
+    harness.get_by_label("Save").click();
+    harness.step();
+
+    let platform_output = &harness.output().platform_output;
+    assert!(matches!(
+        platform_output.events.as_slice(),
+        [OutputEvent::Announcement { .. }, OutputEvent::Clicked(_)]
+    ));
+    assert_eq!(
+        platform_output.speech_descriptions(),
+        [
+            "Saving".to_owned(),
+            WidgetInfo::labeled(WidgetType::Button, true, "Save").description(),
+        ],
+        "Both the announcement and the click should be spoken"
+    );
+}
+
 fn accesskit_output_single_egui_frame(run_ui: impl FnMut(&Context)) -> TreeUpdate {
     let ctx = Context::default();
     // Disable animations, so we do not need to wait for animations to end to see the result.
@@ -188,6 +484,16 @@ fn assert_parent_child(tree: &TreeUpdate, parent_id: NodeId, child: NodeId) {
     );
 }
 
+#[track_caller]
+fn find_node(tree: &TreeUpdate, predicate: impl Fn(&Node) -> bool) -> (NodeId, &Node) {
+    let (id, node) = tree
+        .nodes
+        .iter()
+        .find(|(_, node)| predicate(node))
+        .expect("No node matches.");
+    (*id, node)
+}
+
 fn has_child_recursively(tree: &TreeUpdate, parent: NodeId, child: NodeId) -> bool {
     let (_, parent) = tree
         .nodes
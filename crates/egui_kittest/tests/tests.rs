@@ -161,6 +161,83 @@ fn test_scroll_down() {
     );
 }
 
+#[test]
+fn test_markdown_anchor_link() {
+    let mut source = "[Jump to the end](#the-end)\n\n".to_owned();
+    for i in 0..30 {
+        source += &format!("Paragraph {i}\n\n");
+    }
+    source += "## The End\n";
+
+    let mut harness = Harness::builder()
+        .with_size(Vec2::new(200.0, 200.0))
+        .build_ui(move |ui| {
+            ScrollArea::vertical().show(ui, |ui| {
+                egui_extras::markdown::MarkdownViewer::new("doc").show(ui, &source);
+            });
+        });
+
+    let screen_bottom = harness.ctx.content_rect().bottom();
+    assert!(screen_bottom < harness.get_by_label("The End").rect().top());
+
+    harness
+        .get_by_role_and_label(egui::accesskit::Role::Link, "Jump to the end")
+        .click();
+    harness.run();
+
+    let heading = harness.get_by_label("The End").rect();
+    assert!(
+        heading.bottom() <= screen_bottom,
+        "The heading was not scrolled into view: {heading:?}"
+    );
+}
+
+#[test]
+fn test_pasted_images_and_files() {
+    #[derive(Default)]
+    struct State {
+        images: [usize; 2],
+        files: [Vec<std::path::PathBuf>; 2],
+    }
+    let mut harness = Harness::new_ui_state(
+        |ui, state| {
+            for (i, label) in ["Drop zone A", "Drop zone B"].into_iter().enumerate() {
+                let response = ui.button(label);
+                if response.clicked() {
+                    response.request_focus();
+                }
+                state.images[i] += response.pasted_images().len();
+                state.files[i].extend(response.pasted_files());
+            }
+        },
+        State::default(),
+    );
+
+    harness.get_by_label("Drop zone A").click();
+    harness.run();
+
+    // Integrations paste the text of the clipboard too:
+    harness.event(egui::Event::Paste("notes.txt".to_owned()));
+    harness.event(egui::Event::PasteImage(egui::ColorImage::filled(
+        [2, 2],
+        egui::Color32::RED,
+    )));
+    harness.event(egui::Event::PasteFiles(vec!["notes.txt".into()]));
+    harness.run();
+
+    let state = harness.state();
+    assert_eq!(
+        state.images,
+        [1, 0],
+        "Only the focused widget gets the image"
+    );
+    assert_eq!(
+        state.files,
+        [vec![std::path::PathBuf::from("notes.txt")], vec![]],
+        "Only the focused widget gets the files"
+    );
+}
+
 #[test]
 fn test_masking() {
     let mut harness = Harness::new_ui(|ui| {
@@ -214,3 +291,140 @@ fn test_remove_cursor() {
         "The button appearance should change"
     );
 }
+
+#[test]
+fn test_contrast_issues() {
+    use egui::contrast::WcagLevel;
+
+    let harness = Harness::new_ui(|ui| {
+        ui.label("Readable");
+        ui.colored_label(egui::Color32::from_gray(40), "Too dark");
+    });
+
+    let issues = harness.contrast_issues(WcagLevel::AA);
+    assert_eq!(issues.len(), 1, "{issues:#?}");
+    assert_eq!(issues[0].text, "Too dark");
+}
+
+#[test]
+#[should_panic(expected = "don't meet WCAG AA contrast")]
+fn test_assert_contrast() {
+    let harness = Harness::new_ui(|ui| {
+        ui.colored_label(egui::Color32::from_gray(40), "Too dark");
+    });
+    harness.assert_contrast(egui::contrast::WcagLevel::AA);
+}
+
+#[test]
+fn test_label_links() {
+    #[derive(Default)]
+    struct State {
+        hovered_link: Option<String>,
+        clicked_link: Option<String>,
+        label_sense: Option<egui::Sense>,
+    }
+
+    let mut harness = Harness::new_ui_state(
+        |ui, state: &mut State| {
+            use egui::text::{LayoutJob, TextFormat};
+
+            let mut job = LayoutJob::default();
+            job.append("Read the ", 0.0, TextFormat::default());
+            job.append_link("manual", TextFormat::default(), "manual.html");
+            job.append(" or the ", 0.0, TextFormat::default());
+            job.append_link("FAQ", TextFormat::default(), "faq.html");
+
+            let output = egui::Label::new(job).selectable(false).show(ui);
+            state.label_sense = Some(output.response.sense);
+            state.hovered_link = output.hovered_link().map(ToOwned::to_owned);
+            if let Some(link) = output.clicked_link() {
+                state.clicked_link = Some(link.to_owned());
+            }
+        },
+        State::default(),
+    );
+
+    // Only the links are clickable, not the whole label:
+    assert_eq!(harness.state().label_sense, Some(egui::Sense::hover()));
+
+    harness.get_by_label("FAQ").hover();
+    harness.run();
+    assert_eq!(harness.state().hovered_link.as_deref(), Some("faq.html"));
+    assert_eq!(harness.state().clicked_link, None);
+
+    harness.get_by_label("manual").click();
+    harness.run();
+    assert_eq!(harness.state().clicked_link.as_deref(), Some("manual.html"));
+
+    harness.get_by_label("FAQ").click_accesskit();
+    harness.run();
+    assert_eq!(harness.state().clicked_link.as_deref(), Some("faq.html"));
+
+    // The links can be reached with the keyboard:
+    for link in ["manual", "faq"] {
+        harness.key_press(egui::Key::Tab);
+        harness.run();
+        harness.key_press(egui::Key::Enter);
+        harness.run();
+        assert_eq!(
+            harness.state().clicked_link.as_deref(),
+            Some(format!("{link}.html").as_str())
+        );
+    }
+
+    harness.hover_at(
+        harness
+            .get_by_label("Read the manual or the FAQ")
+            .rect()
+            .left_center(),
+    );
+    harness.run();
+    assert_eq!(harness.state().hovered_link, None);
+}
+
+#[test]
+fn test_vertical_label_links() {
+    #[derive(Default)]
+    struct State {
+        section_centers: Vec<egui::Pos2>,
+        hovered_section: Option<usize>,
+        clicked_link: Option<String>,
+    }
+
+    let mut harness = Harness::new_ui_state(
+        |ui, state: &mut State| {
+            use egui::text::{LayoutJob, TextFormat, WritingMode};
+
+            let mut job = LayoutJob {
+                writing_mode: WritingMode::VerticalRightToLeft,
+                ..Default::default()
+            };
+            job.append("Read the ", 0.0, TextFormat::default());
+            job.append_link("manual", TextFormat::default(), "manual.html");
+
+            let output = egui::Label::new(job).selectable(false).show(ui);
+            state.section_centers = (0..output.galley.job.sections.len())
+                .map(|section_index| {
+                    let rects = output.galley.section_rects(section_index);
+                    let rect = rects.into_iter().reduce(|a, b| a.union(b)).unwrap();
+                    output.galley_pos + rect.center().to_vec2()
+                })
+                .collect();
+            state.hovered_section = output.hovered_section;
+            if let Some(link) = output.clicked_link() {
+                state.clicked_link = Some(link.to_owned());
+            }
+        },
+        State::default(),
+    );
+
+    for section_index in [0, 1] {
+        harness.hover_at(harness.state().section_centers[section_index]);
+        harness.run();
+        assert_eq!(harness.state().hovered_section, Some(section_index));
+    }
+
+    harness.get_by_label("manual").click();
+    harness.run();
+    assert_eq!(harness.state().clicked_link.as_deref(), Some("manual.html"));
+}
@@ -99,7 +99,8 @@ pub const WHITE_UV: emath::Pos2 = emath::pos2(0.0, 0.0);
 pub enum TextureId {
     /// Textures allocated using [`TextureManager`].
     ///
-    /// The first texture (`TextureId::Managed(0)`) is used for the font data.
+    /// The first texture (`TextureId::Managed(0)`) is used for the font data,
+    /// and the second ([`TextureId::DISTANCE_FIELD_FONT`]) for the distance field font data.
     Managed(u64),
 
     /// Your own texture, defined in any which way you want.
@@ -114,6 +115,13 @@ impl Default for TextureId {
     }
 }
 
+impl TextureId {
+    /// The font texture used for [`text::GlyphRendering::DistanceField`].
+    ///
+    /// Its texture options has [`textures::TextureOptions::distance_field`] set.
+    pub const DISTANCE_FIELD_FONT: Self = Self::Managed(1);
+}
+
 /// A [`Shape`] within a clip rectangle.
 ///
 /// Everything is using logical points.
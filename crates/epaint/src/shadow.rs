@@ -5,7 +5,7 @@ use crate::{Color32, CornerRadius, MarginF32, Rect, RectShape, Vec2};
 /// Can be used for a rectangular shadow with a soft penumbra.
 ///
 /// Very similar to a box-shadow in CSS.
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Shadow {
     /// Move the shadow by this much.
@@ -395,6 +395,8 @@ impl Shape {
             mesh.texture_id
         } else if let Self::Rect(rect_shape) = self {
             rect_shape.fill_texture_id()
+        } else if let Self::Text(text_shape) = self {
+            text_shape.texture_id()
         } else {
             crate::TextureId::default()
         }
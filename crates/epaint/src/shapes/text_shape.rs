@@ -67,6 +67,17 @@ impl TextShape {
             .translate(self.pos.to_vec2())
     }
 
+    /// The font texture the glyphs are in.
+    ///
+    /// This is [`TextureId::DISTANCE_FIELD_FONT`] for [`text::GlyphRendering::DistanceField`].
+    #[inline]
+    pub fn texture_id(&self) -> TextureId {
+        match self.galley.job.glyph_rendering {
+            text::GlyphRendering::Bitmap => TextureId::default(),
+            text::GlyphRendering::DistanceField => TextureId::DISTANCE_FIELD_FONT,
+        }
+    }
+
     #[inline]
     pub fn with_underline(mut self, underline: Stroke) -> Self {
         self.underline = underline;
@@ -148,6 +159,7 @@ impl TextShape {
             let text::Row {
                 section_index_at_start: _,
                 glyphs: _, // TODO(emilk): would it make sense to transform these?
+                extra_glyphs: _,
                 size,
                 visuals,
             } = Arc::make_mut(row);
@@ -159,6 +171,7 @@ impl TextShape {
                 mesh_bounds,
                 glyph_index_start: _,
                 glyph_vertex_range: _,
+                color_glyph_vertex_ranges: _,
             } = visuals;
 
             *mesh_bounds = transform.scaling * *mesh_bounds;
@@ -2006,23 +2006,44 @@ impl Tessellator {
             );
         }
 
+        let texture_id = text_shape.texture_id();
+        if out.is_empty() {
+            out.texture_id = texture_id;
+        } else {
+            debug_assert_eq!(
+                out.texture_id, texture_id,
+                "Mixing different `texture_id` in the same mesh"
+            );
+        }
+
         out.vertices.reserve(galley.num_vertices);
         out.indices.reserve(galley.num_indices);
 
         // The contents of the galley are already snapped to pixel coordinates,
         // but we need to make sure the galley ends up on the start of a physical pixel:
-        let galley_pos = if self.options.round_text_to_pixels {
+        let mut galley_pos = if self.options.round_text_to_pixels {
             galley_pos.round_to_pixels(self.pixels_per_point)
         } else {
             *galley_pos
         };
 
-        let uv_normalizer = vec2(
-            1.0 / self.font_tex_size[0] as f32,
-            1.0 / self.font_tex_size[1] as f32,
-        );
+        let uv_normalizer = match galley.job.glyph_rendering {
+            crate::text::GlyphRendering::Bitmap => vec2(
+                1.0 / self.font_tex_size[0] as f32,
+                1.0 / self.font_tex_size[1] as f32,
+            ),
+            crate::text::GlyphRendering::DistanceField => {
+                Vec2::splat(1.0 / crate::TextureAtlas::DISTANCE_FIELD_SIZE as f32)
+            }
+        };
 
-        let rotator = Rot2::from_angle(*angle);
+        let mut rotator = Rot2::from_angle(*angle);
+
+        if galley.is_vertical() {
+            // Turn the rows into columns, see `Galley::pos_from_row_space`:
+            galley_pos += rotator * vec2(galley.rect.max.x, 0.0);
+            rotator = rotator * Rot2::from_angle(std::f32::consts::FRAC_PI_2);
+        }
 
         for row in &galley.rows {
             if row.visuals.mesh.is_empty() {
@@ -2032,7 +2053,7 @@ impl Tessellator {
             let final_row_pos = galley_pos + rotator * row.pos.to_vec2();
 
             let mut row_rect = row.visuals.mesh_bounds;
-            if *angle != 0.0 {
+            if rotator != Rot2::IDENTITY {
                 row_rect = row_rect.rotate_bb(rotator);
             }
             row_rect = row_rect.translate(final_row_pos.to_vec2());
@@ -2063,8 +2084,11 @@ impl Tessellator {
                         let Vertex { pos, uv, mut color } = *vertex;
 
                         if let Some(override_text_color) = override_text_color {
-                            // Only override the glyph color (not background color, strike-through color, etc)
-                            if row.visuals.glyph_vertex_range.contains(&i) {
+                            // Only override the glyph color (not background color, strike-through color, etc),
+                            // and leave color glyphs (emojis) alone:
+                            if row.visuals.glyph_vertex_range.contains(&i)
+                                && !row.visuals.is_color_glyph_vertex(i)
+                            {
                                 color = *override_text_color;
                             }
                         } else if color == Color32::PLACEHOLDER {
@@ -2077,7 +2101,7 @@ impl Tessellator {
 
                         debug_assert!(color != Color32::PLACEHOLDER, "A placeholder color made it to the tessellator. You forgot to set a fallback color.");
 
-                        let offset = if *angle == 0.0 {
+                        let offset = if rotator == Rot2::IDENTITY {
                             pos.to_vec2()
                         } else {
                             rotator * pos.to_vec2()
@@ -11,10 +11,10 @@ use skrifa::{
 use vello_cpu::{color, kurbo};
 
 use crate::{
-    TextOptions, TextureAtlas,
+    Color32, TextOptions, TextureAtlas,
     text::{
-        FontTweak,
-        fonts::{Blob, CachedFamily, FontFaceKey},
+        DecorationMetrics, FontFeature, FontTweak, FontVariations, Language,
+        fonts::{Blob, CachedFamily, FontFaceKey, FontFaces},
     },
 };
 
@@ -35,6 +35,12 @@ pub struct UvRect {
 
     /// Bottom right corner (exclusive).
     pub max: [u16; 2],
+
+    /// Is this a color glyph (e.g. an emoji)?
+    ///
+    /// Color glyphs are stored with their own colors in the font atlas,
+    /// and are not tinted by the text color.
+    pub colored: bool,
 }
 
 impl UvRect {
@@ -140,6 +146,14 @@ pub struct GlyphAllocation {
     pub uv_rect: UvRect,
 }
 
+/// The size at which glyphs are stored in a distance field atlas.
+///
+/// See [`crate::text::GlyphRendering::DistanceField`].
+const DISTANCE_FIELD_PX_PER_EM: f32 = 32.0;
+
+/// See [`TextureAtlas::DISTANCE_FIELD_SPREAD`].
+const DISTANCE_FIELD_SPREAD: f64 = TextureAtlas::DISTANCE_FIELD_SPREAD as f64;
+
 #[derive(Hash, PartialEq, Eq)]
 struct GlyphCacheKey(u64);
 
@@ -150,6 +164,7 @@ impl GlyphCacheKey {
         let ScaledMetrics {
             pixels_per_point,
             px_scale_factor,
+            variations,
             ..
         } = *metrics;
         debug_assert!(
@@ -165,8 +180,14 @@ impl GlyphCacheKey {
             pixels_per_point.to_bits(),
             px_scale_factor.to_bits(),
             bin,
+            variations,
         )))
     }
+
+    /// Distance field glyphs are the same for all sizes and subpixel positions.
+    fn distance_field(glyph_id: skrifa::GlyphId, variations: FontVariations) -> Self {
+        Self(crate::util::hash(("distance_field", glyph_id, variations)))
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -178,6 +199,27 @@ struct DependentFontData<'a> {
     metrics: skrifa::metrics::Metrics,
     glyph_metrics: skrifa::metrics::GlyphMetrics<'a>,
     hinting_instance: Option<skrifa::outline::HintingInstance>,
+    color_glyphs: skrifa::color::ColorGlyphCollection<'a>,
+    bitmap_strikes: skrifa::bitmap::BitmapStrikes<'a>,
+}
+
+impl DependentFontData<'_> {
+    fn location(&self, variations: FontVariations) -> skrifa::instance::Location {
+        self.skrifa.axes().location(
+            variations
+                .settings()
+                .map(|(tag, value)| (skrifa::Tag::new(&tag), value)),
+        )
+    }
+
+    /// Is this glyph drawn with its own colors (COLR layers, or PNG bitmaps from CBDT/sbix)?
+    fn is_color_glyph(&self, glyph_id: skrifa::GlyphId, size: skrifa::instance::Size) -> bool {
+        self.color_glyphs.get(glyph_id).is_some()
+            || self
+                .bitmap_strikes
+                .glyph_for_size(size, glyph_id)
+                .is_some_and(|glyph| matches!(glyph.data, skrifa::bitmap::BitmapData::Png(_)))
+    }
 }
 
 self_cell! {
@@ -201,6 +243,7 @@ impl FontCell {
         metrics: &ScaledMetrics,
         glyph_info: &GlyphInfo,
         bin: SubpixelBin,
+        font_index: u32,
     ) -> Option<GlyphAllocation> {
         let glyph_id = glyph_info.id?;
 
@@ -209,6 +252,20 @@ impl FontCell {
             "Can't allocate glyph for id 0"
         );
 
+        let advance_width_px = glyph_info.advance_width_unscaled.0 * metrics.px_scale_factor;
+
+        if self
+            .borrow_dependent()
+            .is_color_glyph(glyph_id, skrifa::instance::Size::new(metrics.scale))
+        {
+            return Some(GlyphAllocation {
+                id: glyph_id,
+                advance_width_px,
+                uv_rect: self
+                    .allocate_color_glyph_uncached(atlas, metrics, glyph_id, bin, font_index),
+            });
+        }
+
         let mut path = kurbo::BezPath::new();
         let mut pen = VelloPen {
             path: &mut path,
@@ -217,15 +274,18 @@ impl FontCell {
 
         self.with_dependent_mut(|_, font_data| {
             let outline = font_data.outline_glyphs.get(glyph_id)?;
+            let location = font_data.location(metrics.variations);
 
             if let Some(hinting_instance) = &mut font_data.hinting_instance {
                 let size = skrifa::instance::Size::new(metrics.scale);
-                if hinting_instance.size() != size {
+                if hinting_instance.size() != size
+                    || hinting_instance.location().coords() != location.coords()
+                {
                     hinting_instance
                         .reconfigure(
                             &font_data.outline_glyphs,
                             size,
-                            skrifa::instance::LocationRef::default(),
+                            &location,
                             skrifa::outline::Target::Smooth {
                                 mode: skrifa::outline::SmoothMode::Normal,
                                 symmetric_rendering: true,
@@ -239,7 +299,7 @@ impl FontCell {
             } else {
                 let draw_settings = skrifa::outline::DrawSettings::unhinted(
                     skrifa::instance::Size::new(metrics.scale),
-                    skrifa::instance::LocationRef::default(),
+                    &location,
                 );
                 outline.draw(draw_settings, &mut pen).ok()?;
             }
@@ -285,15 +345,188 @@ impl FontCell {
                     (glyph_pos.0 + width as usize) as u16,
                     (glyph_pos.1 + height as usize) as u16,
                 ],
+                colored: false,
             }
         };
 
         Some(GlyphAllocation {
             id: glyph_id,
-            advance_width_px: glyph_info.advance_width_unscaled.0 * metrics.px_scale_factor,
+            advance_width_px,
             uv_rect,
         })
     }
+
+    /// Render a color glyph with its own (premultiplied) colors into the atlas.
+    fn allocate_color_glyph_uncached(
+        &self,
+        atlas: &mut TextureAtlas,
+        metrics: &ScaledMetrics,
+        glyph_id: skrifa::GlyphId,
+        bin: SubpixelBin,
+        font_index: u32,
+    ) -> UvRect {
+        let coords: Vec<i16> = self
+            .borrow_dependent()
+            .location(metrics.variations)
+            .coords()
+            .iter()
+            .map(|coord| coord.to_bits())
+            .collect();
+
+        // Color glyphs have no single outline we can measure,
+        // so we render them into a generous canvas and crop it afterwards:
+        let em = metrics.scale.ceil();
+        let canvas_size = (2.0 * em) as u16 + 2;
+        let origin = vec2(0.5 * em + 1.0, 1.5 * em + 1.0);
+
+        let font = vello_cpu::peniko::FontData::new(
+            vello_cpu::peniko::Blob::new(self.borrow_owner().clone()),
+            font_index,
+        );
+        let mut ctx = vello_cpu::RenderContext::new(canvas_size, canvas_size);
+        ctx.set_transform(kurbo::Affine::translate((origin.x as f64, origin.y as f64)));
+        ctx.set_paint(color::OpaqueColor::<color::Srgb>::WHITE);
+        ctx.glyph_run(&font)
+            .font_size(metrics.scale)
+            .hint(false)
+            .normalized_coords(&coords)
+            .fill_glyphs(std::iter::once(vello_cpu::Glyph {
+                id: glyph_id.to_u32(),
+                x: bin.as_float(),
+                y: 0.0,
+            }));
+        let mut dest = vello_cpu::Pixmap::new(canvas_size, canvas_size);
+        ctx.render_to_pixmap(&mut dest);
+
+        let canvas_size = canvas_size as usize;
+        let pixels = dest.data_as_u8_slice();
+        let alpha = |x: usize, y: usize| pixels[(y * canvas_size + x) * 4 + 3];
+
+        // Find the bounding box of the visible pixels:
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (canvas_size, canvas_size, 0, 0);
+        for y in 0..canvas_size {
+            for x in 0..canvas_size {
+                if alpha(x, y) != 0 {
+                    min_x = min_x.min(x);
+                    min_y = min_y.min(y);
+                    max_x = max_x.max(x + 1);
+                    max_y = max_y.max(y + 1);
+                }
+            }
+        }
+        if max_x <= min_x || max_y <= min_y {
+            return UvRect::default();
+        }
+        let (width, height) = (max_x - min_x, max_y - min_y);
+
+        let (glyph_pos, image) = atlas.allocate((width, height));
+        for y in 0..height {
+            for x in 0..width {
+                let i = ((y + min_y) * canvas_size + x + min_x) * 4;
+                image[(x + glyph_pos.0, y + glyph_pos.1)] = Color32::from_rgba_premultiplied(
+                    pixels[i],
+                    pixels[i + 1],
+                    pixels[i + 2],
+                    pixels[i + 3],
+                );
+            }
+        }
+
+        let offset_in_pixels = vec2(min_x as f32, min_y as f32) - origin;
+        UvRect {
+            offset: offset_in_pixels / metrics.pixels_per_point
+                + metrics.y_offset_in_points * Vec2::Y,
+            size: vec2(width as f32, height as f32) / metrics.pixels_per_point,
+            min: [glyph_pos.0 as u16, glyph_pos.1 as u16],
+            max: [(glyph_pos.0 + width) as u16, (glyph_pos.1 + height) as u16],
+            colored: true,
+        }
+    }
+}
+
+impl FontCell {
+    /// Store the signed distance field of the outline of a glyph in the atlas.
+    ///
+    /// The returned offset and size are in distance field texels, see [`DISTANCE_FIELD_PX_PER_EM`].
+    fn allocate_distance_field_glyph_uncached(
+        &self,
+        atlas: &mut TextureAtlas,
+        variations: FontVariations,
+        glyph_id: skrifa::GlyphId,
+    ) -> UvRect {
+        use kurbo::{ParamCurveNearest as _, Shape as _};
+
+        let mut path = kurbo::BezPath::new();
+        let mut pen = VelloPen {
+            path: &mut path,
+            x_offset: 0.0,
+        };
+        let font_data = self.borrow_dependent();
+        let Some(outline) = font_data.outline_glyphs.get(glyph_id) else {
+            return UvRect::default();
+        };
+        let location = font_data.location(variations);
+        let draw_settings = skrifa::outline::DrawSettings::unhinted(
+            skrifa::instance::Size::new(DISTANCE_FIELD_PX_PER_EM),
+            &location,
+        );
+        if outline.draw(draw_settings, &mut pen).is_err() || path.elements().is_empty() {
+            return UvRect::default();
+        }
+
+        // Flatten the outline, so we only need to measure the distance to line segments:
+        let mut lines = vec![];
+        let (mut start, mut last) = (kurbo::Point::ZERO, kurbo::Point::ZERO);
+        kurbo::flatten(&path, 0.05, |element| match element {
+            kurbo::PathEl::MoveTo(point) => {
+                (start, last) = (point, point);
+            }
+            kurbo::PathEl::LineTo(point) => {
+                lines.push(kurbo::Line::new(last, point));
+                last = point;
+            }
+            kurbo::PathEl::ClosePath => {
+                lines.push(kurbo::Line::new(last, start));
+                last = start;
+            }
+            kurbo::PathEl::QuadTo(..) | kurbo::PathEl::CurveTo(..) => {} // `flatten` only outputs lines
+        });
+
+        let bounds = path.control_box();
+        let x0 = (bounds.x0 - DISTANCE_FIELD_SPREAD).floor();
+        let y0 = (bounds.y0 - DISTANCE_FIELD_SPREAD).floor();
+        let width = ((bounds.x1 + DISTANCE_FIELD_SPREAD).ceil() - x0) as usize;
+        let height = ((bounds.y1 + DISTANCE_FIELD_SPREAD).ceil() - y0) as usize;
+
+        let (glyph_pos, image) = atlas.allocate((width, height));
+        for y in 0..height {
+            for x in 0..width {
+                let center = kurbo::Point::new(x0 + x as f64 + 0.5, y0 + y as f64 + 0.5);
+                let distance = lines
+                    .iter()
+                    .map(|line| line.nearest(center, 1e-3).distance_sq)
+                    .fold(f64::INFINITY, f64::min)
+                    .sqrt();
+                let signed_distance = if path.winding(center) == 0 {
+                    -distance
+                } else {
+                    distance
+                };
+                // 0.5 on the edge, 1.0 deep inside and 0.0 far outside:
+                let value = (0.5 + 0.5 * signed_distance / DISTANCE_FIELD_SPREAD).clamp(0.0, 1.0);
+                image[(glyph_pos.0 + x, glyph_pos.1 + y)] =
+                    Color32::from_white_alpha((value * 255.0).round() as u8);
+            }
+        }
+
+        UvRect {
+            offset: vec2(x0 as f32, y0 as f32),
+            size: vec2(width as f32, height as f32),
+            min: [glyph_pos.0 as u16, glyph_pos.1 as u16],
+            max: [(glyph_pos.0 + width) as u16, (glyph_pos.1 + height) as u16],
+            colored: false,
+        }
+    }
 }
 
 struct VelloPen<'a> {
@@ -335,9 +568,20 @@ impl skrifa::outline::OutlinePen for VelloPen<'_> {
 pub struct FontFace {
     name: String,
     font: FontCell,
+
+    /// Index of the face in the font collection.
+    index: u32,
+
     tweak: FontTweak,
-    glyph_info_cache: ahash::HashMap<char, GlyphInfo>,
+
+    /// Keyed by the char and the hash of the [`FontVariations`],
+    /// since the advance width depends on the axes of a variable font.
+    glyph_info_cache: ahash::HashMap<(char, u64), GlyphInfo>,
     glyph_alloc_cache: ahash::HashMap<GlyphCacheKey, GlyphAllocation>,
+    shaper_data: harfrust::ShaperData,
+
+    /// Creating a shape plan is slow, so we cache one per direction, script, language and set of features.
+    shape_plans: nohash_hasher::IntMap<u64, harfrust::ShapePlan>,
 }
 
 impl FontFace {
@@ -353,6 +597,8 @@ impl FontFace {
                 skrifa::FontRef::from_index(AsRef::<[u8]>::as_ref(font_data.as_ref()), index)?;
 
             let charmap = skrifa_font.charmap();
+            let color_glyphs = skrifa_font.color_glyphs();
+            let bitmap_strikes = skrifa_font.bitmap_strikes();
             let glyphs = skrifa_font.outline_glyphs();
             let metrics = skrifa_font.metrics(
                 skrifa::instance::Size::unscaled(),
@@ -385,17 +631,29 @@ impl FontFace {
                 metrics,
                 glyph_metrics,
                 hinting_instance,
+                color_glyphs,
+                bitmap_strikes,
             })
         })?;
+        let shaper_data = harfrust::ShaperData::new(&font.borrow_dependent().skrifa);
         Ok(Self {
             name,
             font,
+            index,
             tweak,
             glyph_info_cache: Default::default(),
             glyph_alloc_cache: Default::default(),
+            shaper_data,
+            shape_plans: Default::default(),
         })
     }
 
+    /// The name of the font, i.e. its key in [`crate::text::FontDefinitions::font_data`].
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
     /// Code points that will always be replaced by the replacement character.
     ///
     /// See also [`invisible_char`].
@@ -425,9 +683,12 @@ impl FontFace {
             .filter_map(|(chr, _)| char::from_u32(chr).filter(|c| !self.ignore_character(*c)))
     }
 
+    /// The glyph of `c`, with its advance width at the given axes of a variable font.
+    ///
     /// `\n` will result in `None`
-    pub(super) fn glyph_info(&mut self, c: char) -> Option<GlyphInfo> {
-        if let Some(glyph_info) = self.glyph_info_cache.get(&c) {
+    pub(super) fn glyph_info(&mut self, c: char, variations: FontVariations) -> Option<GlyphInfo> {
+        let cache_key = (c, crate::util::hash(variations));
+        if let Some(glyph_info) = self.glyph_info_cache.get(&cache_key) {
             return Some(*glyph_info);
         }
 
@@ -436,7 +697,7 @@ impl FontFace {
         }
 
         if c == '\t'
-            && let Some(space) = self.glyph_info(' ')
+            && let Some(space) = self.glyph_info(' ', variations)
         {
             let glyph_info = GlyphInfo {
                 advance_width_unscaled: (crate::text::TAB_SIZE as f32
@@ -444,7 +705,7 @@ impl FontFace {
                     .into(),
                 ..space
             };
-            self.glyph_info_cache.insert(c, glyph_info);
+            self.glyph_info_cache.insert(cache_key, glyph_info);
             return Some(glyph_info);
         }
 
@@ -453,21 +714,21 @@ impl FontFace {
             // https://www.compart.com/en/unicode/U+2009
             // https://en.wikipedia.org/wiki/Thin_space
 
-            if let Some(space) = self.glyph_info(' ') {
+            if let Some(space) = self.glyph_info(' ', variations) {
                 let em = self.font.borrow_dependent().metrics.units_per_em as f32;
                 let advance_width = f32::min(em / 6.0, space.advance_width_unscaled.0 * 0.5); // TODO(emilk): make configurable
                 let glyph_info = GlyphInfo {
                     advance_width_unscaled: advance_width.into(),
                     ..space
                 };
-                self.glyph_info_cache.insert(c, glyph_info);
+                self.glyph_info_cache.insert(cache_key, glyph_info);
                 return Some(glyph_info);
             }
         }
 
         if invisible_char(c) {
             let glyph_info = GlyphInfo::INVISIBLE;
-            self.glyph_info_cache.insert(c, glyph_info);
+            self.glyph_info_cache.insert(cache_key, glyph_info);
             return Some(glyph_info);
         }
 
@@ -479,18 +740,98 @@ impl FontFace {
             .map(c)
             .filter(|id| *id != skrifa::GlyphId::NOTDEF)?;
 
+        let advance_width = if variations.is_default() {
+            font_data.glyph_metrics.advance_width(glyph_id)
+        } else {
+            let location = font_data.location(variations);
+            font_data
+                .skrifa
+                .glyph_metrics(skrifa::instance::Size::unscaled(), &location)
+                .advance_width(glyph_id)
+        };
         let glyph_info = GlyphInfo {
             id: Some(glyph_id),
-            advance_width_unscaled: font_data
-                .glyph_metrics
-                .advance_width(glyph_id)
-                .unwrap_or_default()
-                .into(),
+            advance_width_unscaled: advance_width.unwrap_or_default().into(),
         };
-        self.glyph_info_cache.insert(c, glyph_info);
+        self.glyph_info_cache.insert(cache_key, glyph_info);
         Some(glyph_info)
     }
 
+    /// Shape a run of text with OpenType shaping (GSUB/GPOS), e.g. to get ligatures,
+    /// Arabic joining forms and Indic reordering.
+    ///
+    /// All of `chars` should be supported by this font face, and have the same direction.
+    /// The clusters of the output are indices into `chars`,
+    /// and the glyphs are in visual order, i.e. reversed for right-to-left text.
+    /// Advances and offsets are in the "unscaled" units of the font.
+    ///
+    /// `font_features` are applied after those in [`FontTweak::font_features`].
+    pub(super) fn shape(
+        &mut self,
+        chars: &[char],
+        rtl: bool,
+        font_features: &[FontFeature],
+        variations: FontVariations,
+        language: Option<Language>,
+    ) -> harfrust::GlyphBuffer {
+        let mut buffer = harfrust::UnicodeBuffer::new();
+        for (i, &chr) in chars.iter().enumerate() {
+            buffer.add(chr, i as u32);
+        }
+        buffer.set_direction(if rtl {
+            harfrust::Direction::RightToLeft
+        } else {
+            harfrust::Direction::LeftToRight
+        });
+        let language: Option<harfrust::Language> =
+            language.and_then(|language| language.as_str().parse().ok());
+        if let Some(language) = &language {
+            buffer.set_language(language.clone());
+        }
+        buffer.guess_segment_properties();
+
+        let features: Vec<harfrust::Feature> = self
+            .tweak
+            .font_features
+            .iter()
+            .chain(font_features)
+            .map(|feature| {
+                harfrust::Feature::new(harfrust::Tag::new(&feature.tag), feature.value, ..)
+            })
+            .collect();
+
+        let direction = buffer.direction();
+        let script = buffer.script();
+        let skrifa_font = &self.font.borrow_dependent().skrifa;
+        let instance = (!variations.is_default()).then(|| {
+            harfrust::ShaperInstance::from_variations(
+                skrifa_font,
+                variations
+                    .settings()
+                    .map(|(tag, value)| harfrust::Variation {
+                        tag: harfrust::Tag::new(&tag),
+                        value,
+                    }),
+            )
+        });
+        let shaper = self
+            .shaper_data
+            .shaper(skrifa_font)
+            .instance(instance.as_ref())
+            .build();
+        let plan_key = crate::util::hash((direction, script, &language, font_features, variations));
+        let plan = self.shape_plans.entry(plan_key).or_insert_with(|| {
+            harfrust::ShapePlan::new(
+                &shaper,
+                direction,
+                Some(script),
+                language.as_ref(),
+                &features,
+            )
+        });
+        shaper.shape_with_plan(plan, buffer, &features)
+    }
+
     #[inline]
     pub(super) fn pair_kerning_pixels(
         &self,
@@ -545,6 +886,8 @@ impl FontFace {
             y_offset_in_points,
             ascent,
             row_height: ascent - descent + line_gap,
+            decoration: decoration_metrics(&font_data.metrics, pt_scale_factor),
+            variations: FontVariations::DEFAULT,
         }
     }
 
@@ -563,6 +906,42 @@ impl FontFace {
             return (GlyphAllocation::default(), h_pos as i32);
         };
 
+        if atlas.is_distance_field() {
+            let uv_rect = self
+                .glyph_alloc_cache
+                .entry(GlyphCacheKey::distance_field(glyph_id, metrics.variations))
+                .or_insert_with(|| GlyphAllocation {
+                    id: glyph_id,
+                    advance_width_px,
+                    uv_rect: self.font.allocate_distance_field_glyph_uncached(
+                        atlas,
+                        metrics.variations,
+                        glyph_id,
+                    ),
+                })
+                .uv_rect;
+
+            // Scale the glyph to this font size, and put it at the exact (subpixel) position:
+            let points_per_texel =
+                metrics.scale / (DISTANCE_FIELD_PX_PER_EM * metrics.pixels_per_point);
+            let h_pos_round = h_pos.round();
+            let offset = uv_rect.offset * points_per_texel
+                + vec2(
+                    (h_pos - h_pos_round) / metrics.pixels_per_point,
+                    metrics.y_offset_in_points,
+                );
+            let allocation = GlyphAllocation {
+                id: glyph_id,
+                advance_width_px,
+                uv_rect: UvRect {
+                    offset,
+                    size: uv_rect.size * points_per_texel,
+                    ..uv_rect
+                },
+            };
+            return (allocation, h_pos_round as i32);
+        }
+
         // CJK scripts contain a lot of characters and could hog the glyph atlas if we stored 4 subpixel offsets per
         // glyph.
         let (h_pos_round, bin) = if is_cjk(chr) {
@@ -585,7 +964,7 @@ impl FontFace {
 
         let allocation = self
             .font
-            .allocate_glyph_uncached(atlas, metrics, &glyph_info, bin)
+            .allocate_glyph_uncached(atlas, metrics, &glyph_info, bin, self.index)
             .unwrap_or_default();
 
         entry.insert(allocation);
@@ -596,7 +975,7 @@ impl FontFace {
 // TODO(emilk): rename?
 /// Wrapper over multiple [`FontFace`] (e.g. a primary + fallbacks for emojis)
 pub struct Font<'a> {
-    pub(super) fonts_by_id: &'a mut nohash_hasher::IntMap<FontFaceKey, FontFace>,
+    pub(super) fonts_by_id: &'a mut FontFaces,
     pub(super) cached_family: &'a mut CachedFamily,
     pub(super) atlas: &'a mut TextureAtlas,
 }
@@ -613,7 +992,9 @@ impl Font<'_> {
         self.cached_family.characters.get_or_insert_with(|| {
             let mut characters: BTreeMap<char, Vec<String>> = Default::default();
             for font_id in &self.cached_family.fonts {
-                let font = self.fonts_by_id.get(font_id).expect("Nonexistent font ID");
+                let Some(font) = self.fonts_by_id.get_mut(font_id) else {
+                    continue; // Failed to parse
+                };
                 for chr in font.characters() {
                     characters.entry(chr).or_default().push(font.name.clone());
                 }
@@ -667,6 +1048,24 @@ impl Font<'_> {
             .insert(c, font_index_glyph_info);
         font_index_glyph_info
     }
+
+    /// Like [`Self::glyph_info`], but with the advance width at the given axes of a variable font.
+    pub(crate) fn glyph_info_with_variations(
+        &mut self,
+        c: char,
+        variations: FontVariations,
+    ) -> (FontFaceKey, GlyphInfo) {
+        let (font_id, glyph_info) = self.glyph_info(c);
+        if variations.is_default() {
+            return (font_id, glyph_info);
+        }
+        let glyph_info = self
+            .fonts_by_id
+            .get_mut(&font_id)
+            .and_then(|font_face| font_face.glyph_info(c, variations))
+            .unwrap_or(glyph_info);
+        (font_id, glyph_info)
+    }
 }
 
 /// Metrics for a font at a specific screen-space scale.
@@ -695,13 +1094,81 @@ pub struct ScaledMetrics {
     ///
     /// Returns a value rounded to [`emath::GUI_ROUNDING`].
     pub row_height: f32,
+
+    /// Where to put the underline and strikethrough, in points.
+    pub decoration: DecorationMetrics,
+
+    /// Where on the axes of a variable font to render the glyphs.
+    pub variations: FontVariations,
+}
+
+impl ScaledMetrics {
+    /// Render the glyphs at these axis values of a variable font.
+    #[inline]
+    pub fn with_variations(self, variations: FontVariations) -> Self {
+        Self { variations, ..self }
+    }
+}
+
+/// Where to put the underline (from the `post` table) and strikethrough (from the `OS/2` table),
+/// in points.
+fn decoration_metrics(
+    metrics: &skrifa::metrics::Metrics,
+    pt_scale_factor: f32,
+) -> DecorationMetrics {
+    let units_per_em = metrics.units_per_em as f32;
+    // Some fonts lack the tables, or have zeros in them:
+    let valid = |decoration: &&skrifa::metrics::Decoration| 0.0 < decoration.thickness;
+    let underline =
+        metrics
+            .underline
+            .as_ref()
+            .filter(valid)
+            .copied()
+            .unwrap_or(skrifa::metrics::Decoration {
+                offset: -0.1 * units_per_em,
+                thickness: 0.05 * units_per_em,
+            });
+    let strikethrough =
+        metrics
+            .strikeout
+            .as_ref()
+            .filter(valid)
+            .copied()
+            .unwrap_or(skrifa::metrics::Decoration {
+                offset: 0.3 * units_per_em,
+                thickness: underline.thickness,
+            });
+
+    // The offsets are to the top of the lines, with y going up:
+    DecorationMetrics {
+        underline_offset: (0.5 * underline.thickness - underline.offset) * pt_scale_factor,
+        underline_thickness: underline.thickness * pt_scale_factor,
+        strikethrough_offset: (strikethrough.offset - 0.5 * strikethrough.thickness)
+            * pt_scale_factor,
+        strikethrough_thickness: strikethrough.thickness * pt_scale_factor,
+    }
+}
+
+/// The Unicode script of a character, e.g. Han or Arabic.
+///
+/// Returns `None` for characters shared between scripts, like digits and punctuation.
+pub(super) fn char_script(c: char) -> Option<harfrust::Script> {
+    use unicode_script::{Script, UnicodeScript as _};
+
+    match c.script() {
+        Script::Common | Script::Inherited | Script::Unknown => None,
+        script => {
+            harfrust::Script::from_iso15924_tag(harfrust::Tag::from_u32(script.as_iso15924_tag()))
+        }
+    }
 }
 
 /// Code points that will always be invisible (zero width).
 ///
 /// See also [`FontFace::ignore_character`].
 #[inline]
-fn invisible_char(c: char) -> bool {
+pub(super) fn invisible_char(c: char) -> bool {
     if c == '\r' {
         // A character most vile and pernicious. Don't display it.
         return true;
@@ -711,11 +1178,10 @@ fn invisible_char(c: char) -> bool {
 
     // From https://www.fileformat.info/info/unicode/category/Cf/list.htm
 
-    // TODO(emilk): heed bidi characters
-
     matches!(
         c,
-        '\u{200B}' // ZERO WIDTH SPACE
+        '\u{00AD}' // SOFT HYPHEN (only shown at a row break)
+            | '\u{200B}' // ZERO WIDTH SPACE
             | '\u{200C}' // ZERO WIDTH NON-JOINER
             | '\u{200D}' // ZERO WIDTH JOINER
             | '\u{200E}' // LEFT-TO-RIGHT MARK
@@ -741,6 +1207,10 @@ fn invisible_char(c: char) -> bool {
             | '\u{206E}' // NATIONAL DIGIT SHAPES
             | '\u{206F}' // NOMINAL DIGIT SHAPES
             | '\u{FEFF}' // ZERO WIDTH NO-BREAK SPACE
+            | '\u{FE00}'..='\u{FE0F}' // VARIATION SELECTORS (e.g. emoji presentation)
+            | '\u{E0001}' // LANGUAGE TAG
+            | '\u{E0020}'..='\u{E007F}' // TAG CHARACTERS (e.g. subdivision flags)
+            | '\u{E0100}'..='\u{E01EF}' // VARIATION SELECTORS SUPPLEMENT
     )
 }
 
@@ -763,8 +1233,47 @@ pub(super) fn is_cjk(c: char) -> bool {
     is_cjk_ideograph(c) || is_kana(c)
 }
 
-#[inline]
-pub(super) fn is_cjk_break_allowed(c: char) -> bool {
-    // See: https://en.wikipedia.org/wiki/Line_breaking_rules_in_East_Asian_languages#Characters_not_permitted_on_the_start_of_a_line.
-    !")]｝〕〉》」』】〙〗〟'\"｠»ヽヾーァィゥェォッャュョヮヵヶぁぃぅぇぉっゃゅょゎゕゖㇰㇱㇲㇳㇴㇵㇶㇷㇸㇹㇺㇻㇼㇽㇾㇿ々〻‐゠–〜?!‼⁇⁈⁉・、:;,。.".contains(c)
+/// Is this character kept upright in vertical text, rather than turned 90° clockwise with the text?
+///
+/// This roughly follows the `Vertical_Orientation` property of [UAX #50](https://www.unicode.org/reports/tr50/):
+/// CJK characters stay upright (`U` and `Tu`), while e.g. Latin and brackets are turned (`R` and `Tr`).
+/// The upright characters are shaped with the `vert` and `vrt2` features,
+/// so that punctuation like `。` uses its vertical form if the font has one.
+pub(super) fn is_upright_in_vertical_text(c: char) -> bool {
+    match c {
+        // Brackets, colons, dashes and long vowel marks, which look right when turned:
+        '\u{3008}'..='\u{3011}'
+        | '\u{3014}'..='\u{301C}'
+        | '\u{3030}'
+        | '\u{30A0}'
+        | '\u{30FC}'
+        | '\u{FF08}'
+        | '\u{FF09}'
+        | '\u{FF0D}'
+        | '\u{FF1A}'..='\u{FF1E}'
+        | '\u{FF3B}'
+        | '\u{FF3D}'
+        | '\u{FF3F}'
+        | '\u{FF5B}'..='\u{FF60}' => false,
+
+        _ if is_cjk(c) => true,
+
+        '\u{1100}'..='\u{11FF}' // Hangul Jamo
+        | '\u{2E80}'..='\u{2FFF}' // CJK radicals, Kangxi radicals, ideographic description
+        | '\u{3000}'..='\u{303F}' // CJK symbols and punctuation
+        | '\u{3100}'..='\u{31FF}' // Bopomofo, Hangul compatibility Jamo, Kanbun, katakana extensions
+        | '\u{3200}'..='\u{33FF}' // Enclosed CJK letters, CJK compatibility
+        | '\u{A960}'..='\u{A97F}' // Hangul Jamo extended-A
+        | '\u{AC00}'..='\u{D7FF}' // Hangul syllables, Hangul Jamo extended-B
+        | '\u{F900}'..='\u{FAFF}' // CJK compatibility ideographs
+        | '\u{FE10}'..='\u{FE1F}' // Vertical forms
+        | '\u{FE30}'..='\u{FE6F}' // CJK compatibility forms, small form variants
+        | '\u{FF00}'..='\u{FF60}' // Fullwidth forms
+        | '\u{FFE0}'..='\u{FFE7}'
+        | '\u{1F000}'..='\u{1FAFF}' // Emoji and other pictographs
+        | '\u{20000}'..='\u{3FFFF}' // CJK ideograph extensions
+        => true,
+
+        _ => false,
+    }
 }
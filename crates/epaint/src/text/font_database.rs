@@ -0,0 +1,563 @@
+use std::path::{Path, PathBuf};
+
+use skrifa::{MetadataProvider as _, raw::FileRef, string::StringId};
+
+use super::{FontData, FontInsert, InsertFontFamily};
+
+/// Information about one font face found by a [`FontDatabase`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct FontFaceInfo {
+    /// The family name, e.g. `"Noto Sans CJK JP"`.
+    pub family: String,
+
+    /// The PostScript name, e.g. `"NotoSansCJKjp-Bold"`.
+    ///
+    /// This is unique for each face, and is used as the name in [`super::FontDefinitions::font_data`].
+    pub postscript_name: String,
+
+    /// The font file.
+    pub path: PathBuf,
+
+    /// Index of the face in the font file (non-zero for font collections like `.ttc`).
+    pub index: u32,
+
+    /// Weight of the face, from 1 to 1000, where 400 is regular and 700 is bold.
+    pub weight: f32,
+
+    /// Width of the face, relative to normal (1.0).
+    pub width: f32,
+
+    /// Is this an italic or oblique face?
+    pub italic: bool,
+}
+
+impl FontFaceInfo {
+    /// Read the font file.
+    ///
+    /// # Errors
+    /// If the file can't be read.
+    pub fn load(&self) -> std::io::Result<FontData> {
+        let bytes = std::fs::read(&self.path)?;
+        Ok(FontData {
+            index: self.index,
+            ..FontData::from_owned(bytes)
+        })
+    }
+}
+
+/// An index of the fonts in some directories, e.g. the fonts installed on the system.
+///
+/// Scanning reads the names, weights and styles of each font,
+/// but doesn't keep the font data around.
+/// Use [`Self::font_insert`] to add a font family to egui by name.
+///
+/// Fonts added this way are only parsed by [`super::Fonts`] once we need a glyph from them,
+/// so it is cheap to add e.g. big CJK fonts as fallbacks.
+///
+/// ```no_run
+/// # use epaint::text::{FontDatabase, FontFamily, FontPriority, InsertFontFamily};
+/// let database = FontDatabase::system();
+/// let font_insert = database.font_insert(
+///     "Noto Sans CJK JP",
+///     vec![InsertFontFamily {
+///         family: FontFamily::Proportional,
+///         priority: FontPriority::Lowest,
+///     }],
+/// );
+/// if let Ok(font_insert) = font_insert {
+///     // egui_ctx.add_font(font_insert);
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct FontDatabase {
+    faces: Vec<FontFaceInfo>,
+}
+
+impl FontDatabase {
+    /// An empty database.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A database of all the fonts in [`Self::system_font_dirs`].
+    pub fn system() -> Self {
+        let mut database = Self::new();
+        for dir in Self::system_font_dirs() {
+            database.scan_dir(&dir);
+        }
+        database
+    }
+
+    /// The directories where fonts are installed on this system.
+    ///
+    /// On Linux this includes the directories listed in the fontconfig configuration,
+    /// and in the files it includes.
+    pub fn system_font_dirs() -> Vec<PathBuf> {
+        let home = std::env::var_os("HOME")
+            .or_else(|| std::env::var_os("USERPROFILE"))
+            .map(PathBuf::from);
+
+        let mut dirs = vec![];
+
+        if cfg!(target_os = "macos") {
+            dirs.push(PathBuf::from("/System/Library/Fonts"));
+            dirs.push(PathBuf::from("/Library/Fonts"));
+            dirs.extend(home.as_ref().map(|home| home.join("Library/Fonts")));
+        } else if cfg!(target_os = "windows") {
+            let windir = std::env::var_os("WINDIR")
+                .map_or_else(|| PathBuf::from("C:\\Windows"), PathBuf::from);
+            dirs.push(windir.join("Fonts"));
+            if let Some(local_app_data) = std::env::var_os("LOCALAPPDATA") {
+                dirs.push(PathBuf::from(local_app_data).join("Microsoft\\Windows\\Fonts"));
+            }
+        } else if cfg!(unix) {
+            let data_home = std::env::var_os("XDG_DATA_HOME")
+                .map(PathBuf::from)
+                .or_else(|| home.as_ref().map(|home| home.join(".local/share")));
+            let config_home = std::env::var_os("XDG_CONFIG_HOME")
+                .map(PathBuf::from)
+                .or_else(|| home.as_ref().map(|home| home.join(".config")));
+
+            let fontconfig_homes = FontconfigHomes {
+                home: home.as_deref(),
+                data_home: data_home.as_deref(),
+                config_home: config_home.as_deref(),
+            };
+            dirs.extend(fontconfig_dirs(
+                Path::new("/etc/fonts/fonts.conf"),
+                &fontconfig_homes,
+            ));
+            dirs.push(PathBuf::from("/usr/share/fonts"));
+            dirs.push(PathBuf::from("/usr/local/share/fonts"));
+            dirs.extend(data_home.map(|data_home| data_home.join("fonts")));
+            dirs.extend(home.map(|home| home.join(".fonts")));
+        }
+
+        let mut unique = vec![];
+        for dir in dirs {
+            if !unique.contains(&dir) {
+                unique.push(dir);
+            }
+        }
+        unique
+    }
+
+    /// Index all the fonts (`.ttf`, `.otf`, `.ttc`, `.otc`) in a directory and its subdirectories.
+    ///
+    /// Unreadable files and directories are skipped.
+    /// Symlinked directories are followed, but each directory is only scanned once.
+    /// Returns the number of font faces that were added.
+    pub fn scan_dir(&mut self, dir: impl AsRef<Path>) -> usize {
+        let num_faces = self.faces.len();
+        let mut visited = ahash::HashSet::default();
+        let mut dirs = vec![dir.as_ref().to_path_buf()];
+        while let Some(dir) = dirs.pop() {
+            // Don't get stuck in symlink loops:
+            let Ok(canonical) = dir.canonicalize() else {
+                continue;
+            };
+            if !visited.insert(canonical) {
+                continue;
+            }
+            let Ok(entries) = std::fs::read_dir(&dir) else {
+                continue;
+            };
+            let mut paths: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+            paths.sort(); // Be deterministic
+            for path in paths {
+                if path.is_dir() {
+                    dirs.push(path);
+                } else if is_font_file(&path) {
+                    if let Err(err) = self.add_file(&path) {
+                        log::debug!("Skipping font file {}: {err}", path.display());
+                    }
+                }
+            }
+        }
+        self.faces.len() - num_faces
+    }
+
+    /// Index all the font faces in a font file.
+    ///
+    /// Returns the number of font faces that were added.
+    ///
+    /// # Errors
+    /// If the file can't be read or isn't a font.
+    pub fn add_file(&mut self, path: impl AsRef<Path>) -> std::io::Result<usize> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path)?;
+        let invalid_data = |err| std::io::Error::new(std::io::ErrorKind::InvalidData, err);
+
+        // Keep the index of each face in the file, even if an earlier face fails to parse:
+        let fonts: Vec<(usize, skrifa::FontRef<'_>)> =
+            match FileRef::new(&bytes).map_err(|err| invalid_data(err.to_string()))? {
+                FileRef::Font(font) => vec![(0, font)],
+                FileRef::Collection(collection) => collection
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(index, font)| Some((index, font.ok()?)))
+                    .collect(),
+            };
+
+        let num_faces = self.faces.len();
+        for (index, font) in &fonts {
+            let name = |id| {
+                font.localized_strings(id)
+                    .english_or_first()
+                    .map(|name| name.to_string())
+            };
+            let Some(family) =
+                name(StringId::TYPOGRAPHIC_FAMILY_NAME).or_else(|| name(StringId::FAMILY_NAME))
+            else {
+                continue;
+            };
+            let postscript_name = name(StringId::POSTSCRIPT_NAME)
+                .unwrap_or_else(|| format!("{}-{index}", path.display()));
+
+            let attributes = font.attributes();
+            self.faces.push(FontFaceInfo {
+                family,
+                postscript_name,
+                path: path.to_path_buf(),
+                index: *index as u32,
+                weight: attributes.weight.value(),
+                width: attributes.stretch.ratio(),
+                italic: attributes.style != skrifa::attribute::Style::Normal,
+            });
+        }
+
+        if self.faces.len() == num_faces {
+            Err(invalid_data("No named font faces found".to_owned()))
+        } else {
+            Ok(self.faces.len() - num_faces)
+        }
+    }
+
+    /// All the indexed font faces.
+    pub fn faces(&self) -> &[FontFaceInfo] {
+        &self.faces
+    }
+
+    /// The names of all indexed font families, sorted and deduplicated.
+    pub fn families(&self) -> Vec<&str> {
+        let mut families: Vec<&str> = self.faces.iter().map(|face| face.family.as_str()).collect();
+        families.sort_unstable();
+        families.dedup();
+        families
+    }
+
+    /// All the faces of a font family (case-insensitive).
+    pub fn family_faces<'a, 'b>(
+        &'a self,
+        family: &'b str,
+    ) -> impl Iterator<Item = &'a FontFaceInfo> + use<'a, 'b> {
+        self.faces
+            .iter()
+            .filter(move |face| face.family.eq_ignore_ascii_case(family))
+    }
+
+    /// Find the face of a font family (case-insensitive) that best matches the given weight and style.
+    pub fn find(&self, family: &str, weight: f32, italic: bool) -> Option<&FontFaceInfo> {
+        self.family_faces(family).min_by_key(|face| {
+            let style_mismatch = face.italic != italic;
+            let width_distance = emath::OrderedFloat((face.width - 1.0).abs());
+            let weight_distance = emath::OrderedFloat((face.weight - weight).abs());
+            (style_mismatch, weight_distance, width_distance)
+        })
+    }
+
+    /// Prepare the regular face of a font family for [`super::Fonts`] (or `egui::Context::add_font`).
+    ///
+    /// The font file is only read and parsed once a glyph is needed from it,
+    /// see [`FontData::from_file`].
+    ///
+    /// # Errors
+    /// If there is no such family.
+    pub fn font_insert(
+        &self,
+        family: &str,
+        families: Vec<InsertFontFamily>,
+    ) -> std::io::Result<FontInsert> {
+        let face = self.find(family, 400.0, false).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("No font family named {family:?}"),
+            )
+        })?;
+        Ok(FontInsert {
+            name: face.postscript_name.clone(),
+            data: FontData::from_file(&face.path, face.index),
+            families,
+        })
+    }
+}
+
+fn is_font_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            ["ttf", "otf", "ttc", "otc"]
+                .iter()
+                .any(|font_extension| extension.eq_ignore_ascii_case(font_extension))
+        })
+}
+
+/// The directories that paths in fontconfig files can be relative to.
+struct FontconfigHomes<'a> {
+    home: Option<&'a Path>,
+
+    /// For `<dir prefix="xdg">`.
+    data_home: Option<&'a Path>,
+
+    /// For `<include prefix="xdg">`.
+    config_home: Option<&'a Path>,
+}
+
+/// The `<dir>` entries of a fontconfig configuration file,
+/// and of the files and directories it `<include>`s.
+fn fontconfig_dirs(config_path: &Path, homes: &FontconfigHomes<'_>) -> Vec<PathBuf> {
+    let mut dirs = vec![];
+    let mut visited = vec![];
+    add_fontconfig_dirs(config_path, homes, &mut visited, &mut dirs);
+    dirs
+}
+
+fn add_fontconfig_dirs(
+    path: &Path,
+    homes: &FontconfigHomes<'_>,
+    visited: &mut Vec<PathBuf>,
+    dirs: &mut Vec<PathBuf>,
+) {
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    if visited.contains(&canonical) {
+        return; // Include cycle
+    }
+    visited.push(canonical);
+
+    if path.is_dir() {
+        // Like fontconfig, read the `.conf` files of an included directory in order:
+        let Ok(entries) = std::fs::read_dir(path) else {
+            return;
+        };
+        let mut paths: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension == "conf")
+            })
+            .collect();
+        paths.sort();
+        for path in paths {
+            add_fontconfig_dirs(&path, homes, visited, dirs);
+        }
+        return;
+    }
+
+    let Ok(config) = std::fs::read_to_string(path) else {
+        return; // Includes are often `ignore_missing`
+    };
+    let config_dir = path.parent();
+    for element in fontconfig_elements(&config) {
+        let xdg_home = match element.tag {
+            "dir" => homes.data_home,
+            _ => homes.config_home,
+        };
+        let resolved = if element.attributes.contains("prefix=\"xdg\"") {
+            xdg_home.map(|xdg_home| xdg_home.join(element.value))
+        } else if let Some(relative) = element.value.strip_prefix("~/") {
+            homes.home.map(|home| home.join(relative))
+        } else {
+            Some(config_dir.map_or_else(
+                || PathBuf::from(element.value),
+                |config_dir| config_dir.join(element.value),
+            ))
+        };
+        let Some(resolved) = resolved else {
+            continue;
+        };
+        if element.tag == "dir" {
+            dirs.push(resolved);
+        } else {
+            add_fontconfig_dirs(&resolved, homes, visited, dirs);
+        }
+    }
+}
+
+/// A `<dir>` or `<include>` element in a fontconfig file.
+struct FontconfigElement<'a> {
+    tag: &'a str,
+    attributes: &'a str,
+    value: &'a str,
+}
+
+/// The `<dir>` and `<include>` elements of a fontconfig file, in order.
+fn fontconfig_elements(config: &str) -> Vec<FontconfigElement<'_>> {
+    let mut elements = vec![];
+    let mut rest = config;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start + 1..];
+        if let Some(comment) = rest.strip_prefix("!--") {
+            let Some(end) = comment.find("-->") else {
+                break;
+            };
+            rest = &comment[end + "-->".len()..];
+            continue;
+        }
+        let Some(tag_end) = rest.find('>') else {
+            break;
+        };
+        let tag_and_attributes = &rest[..tag_end];
+        rest = &rest[tag_end + 1..];
+        if tag_and_attributes.ends_with('/') {
+            continue; // e.g. `<dir/>`
+        }
+        let (tag, attributes) = tag_and_attributes
+            .split_once(' ')
+            .unwrap_or((tag_and_attributes, ""));
+        if tag != "dir" && tag != "include" {
+            continue;
+        }
+        let end_tag = format!("</{tag}>");
+        let Some(end) = rest.find(&end_tag) else {
+            break;
+        };
+        elements.push(FontconfigElement {
+            tag,
+            attributes,
+            value: rest[..end].trim(),
+        });
+        rest = &rest[end + end_tag.len()..];
+    }
+    elements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fontconfig_dirs() {
+        let etc = tempfile::tempdir().unwrap();
+        let config_home = tempfile::tempdir().unwrap();
+        std::fs::write(
+            etc.path().join("fonts.conf"),
+            r#"
+            <fontconfig>
+                <dir>/usr/share/fonts</dir>
+                <dir prefix="xdg">fonts</dir>
+                <!-- the following element will be removed in the future -->
+                <dir>~/.fonts</dir>
+                <!-- <dir>/commented/out</dir> -->
+                <cachedir>/var/cache/fontconfig</cachedir>
+                <include ignore_missing="yes">conf.d</include>
+                <include ignore_missing="yes" prefix="xdg">fontconfig/fonts.conf</include>
+                <include ignore_missing="yes">missing.conf</include>
+            </fontconfig>
+        "#,
+        )
+        .unwrap();
+        std::fs::create_dir(etc.path().join("conf.d")).unwrap();
+        std::fs::write(
+            etc.path().join("conf.d/20-b.conf"),
+            "<fontconfig><dir>/opt/b</dir></fontconfig>",
+        )
+        .unwrap();
+        std::fs::write(
+            etc.path().join("conf.d/10-a.conf"),
+            // Include cycles are ignored:
+            "<fontconfig><dir>/opt/a</dir><include>../fonts.conf</include></fontconfig>",
+        )
+        .unwrap();
+        std::fs::write(etc.path().join("conf.d/README"), "<dir>/not/a/conf</dir>").unwrap();
+        std::fs::create_dir(config_home.path().join("fontconfig")).unwrap();
+        std::fs::write(
+            config_home.path().join("fontconfig/fonts.conf"),
+            "<fontconfig><dir>/opt/user</dir></fontconfig>",
+        )
+        .unwrap();
+
+        let homes = FontconfigHomes {
+            home: Some(Path::new("/home/user")),
+            data_home: Some(Path::new("/home/user/.local/share")),
+            config_home: Some(config_home.path()),
+        };
+        let dirs = fontconfig_dirs(&etc.path().join("fonts.conf"), &homes);
+        assert_eq!(
+            dirs,
+            [
+                PathBuf::from("/usr/share/fonts"),
+                PathBuf::from("/home/user/.local/share/fonts"),
+                PathBuf::from("/home/user/.fonts"),
+                PathBuf::from("/opt/a"),
+                PathBuf::from("/opt/b"),
+                PathBuf::from("/opt/user"),
+            ]
+        );
+    }
+
+    #[cfg(feature = "default_fonts")]
+    #[test]
+    fn test_scan_dir() {
+        use crate::text::{FontFamily, FontPriority};
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("mono")).unwrap();
+        std::fs::write(
+            dir.path().join("mono/Hack-Regular.ttf"),
+            epaint_default_fonts::HACK_REGULAR,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("Ubuntu-Light.TTF"),
+            epaint_default_fonts::UBUNTU_LIGHT,
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("README.txt"), "not a font").unwrap();
+        std::fs::write(dir.path().join("broken.otf"), "not a font either").unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(dir.path(), dir.path().join("mono/loop")).unwrap();
+
+        let mut database = FontDatabase::new();
+        assert_eq!(database.scan_dir(dir.path()), 2);
+        assert_eq!(database.families(), ["Hack", "Ubuntu"]);
+
+        let ubuntu = database.find("Ubuntu", 400.0, false).unwrap();
+        assert_eq!(ubuntu.weight, 300.0);
+
+        let hack = database.find("hack", 700.0, true).unwrap();
+        assert_eq!(hack.postscript_name, "Hack-Regular");
+        assert_eq!(hack.weight, 400.0);
+        assert!(!hack.italic);
+
+        let font_insert = database
+            .font_insert(
+                "Hack",
+                vec![InsertFontFamily {
+                    family: FontFamily::Monospace,
+                    priority: FontPriority::Highest,
+                }],
+            )
+            .unwrap();
+        assert_eq!(font_insert.name, "Hack-Regular");
+        assert_eq!(
+            font_insert.data.path.as_deref(),
+            Some(dir.path().join("mono/Hack-Regular.ttf").as_path())
+        );
+
+        // The file is read once the font is needed:
+        let mut definitions = crate::text::FontDefinitions::empty();
+        definitions.font_data.insert(
+            font_insert.name.clone(),
+            std::sync::Arc::new(font_insert.data.clone()),
+        );
+        definitions
+            .families
+            .insert(FontFamily::Monospace, vec![font_insert.name.clone()]);
+        let mut fonts = crate::text::Fonts::new(Default::default(), definitions);
+        let font_id = crate::FontId::monospace(14.0);
+        assert!(fonts.with_pixels_per_point(1.0).has_glyph(&font_id, 'H'));
+
+        assert!(database.font_insert("Comic Sans", vec![]).is_err());
+    }
+}
@@ -1,6 +1,7 @@
 use std::{
     borrow::Cow,
     collections::BTreeMap,
+    path::PathBuf,
     sync::{
         Arc,
         atomic::{AtomicU64, Ordering},
@@ -10,11 +11,11 @@ use std::{
 use crate::{
     TextureAtlas,
     text::{
-        Galley, LayoutJob, LayoutSection, TextOptions,
-        font::{Font, FontFace, GlyphInfo},
+        Galley, GlyphRendering, Hyphenator, LayoutJob, LayoutSection, TextOptions,
+        font::{Font, FontFace, GlyphInfo, UvRect, char_script},
     },
 };
-use emath::{NumExt as _, OrderedFloat};
+use emath::{NumExt as _, OrderedFloat, Vec2, vec2};
 
 #[cfg(feature = "default_fonts")]
 use epaint_default_fonts::{EMOJI_ICON, HACK_REGULAR, NOTO_EMOJI_REGULAR, UBUNTU_LIGHT};
@@ -30,7 +31,11 @@ pub struct FontId {
 
     /// What font family to use.
     pub family: FontFamily,
-    // TODO(emilk): weight (bold), italics, …
+
+    /// Weight, width, etc. for variable fonts.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub variations: FontVariations,
+    // TODO(emilk): italics, …
 }
 
 impl Default for FontId {
@@ -39,6 +44,7 @@ impl Default for FontId {
         Self {
             size: 14.0,
             family: FontFamily::Proportional,
+            variations: FontVariations::DEFAULT,
         }
     }
 }
@@ -46,7 +52,11 @@ impl Default for FontId {
 impl FontId {
     #[inline]
     pub const fn new(size: f32, family: FontFamily) -> Self {
-        Self { size, family }
+        Self {
+            size,
+            family,
+            variations: FontVariations::DEFAULT,
+        }
     }
 
     #[inline]
@@ -58,14 +68,249 @@ impl FontId {
     pub const fn monospace(size: f32) -> Self {
         Self::new(size, FontFamily::Monospace)
     }
+
+    /// Set the weight axis (`wght`) of a variable font, e.g. `700.0` for bold.
+    ///
+    /// See [`FontVariations::weight`].
+    #[inline]
+    pub const fn weight(mut self, weight: f32) -> Self {
+        self.variations.weight = Some(weight);
+        self
+    }
+
+    /// Use the bold weight of a variable font.
+    #[inline]
+    pub const fn bold(self) -> Self {
+        self.weight(FontVariations::BOLD)
+    }
+
+    /// Set the width axis (`wdth`) of a variable font.
+    ///
+    /// See [`FontVariations::width`].
+    #[inline]
+    pub const fn width(mut self, width: f32) -> Self {
+        self.variations.width = Some(width);
+        self
+    }
+
+    /// Set the slant axis (`slnt`) of a variable font.
+    ///
+    /// See [`FontVariations::slant`].
+    #[inline]
+    pub const fn slant(mut self, slant: f32) -> Self {
+        self.variations.slant = Some(slant);
+        self
+    }
+
+    /// Set the optical size axis (`opsz`) of a variable font.
+    ///
+    /// See [`FontVariations::optical_size`].
+    #[inline]
+    pub const fn optical_size(mut self, optical_size: f32) -> Self {
+        self.variations.optical_size = Some(optical_size);
+        self
+    }
 }
 
 impl std::hash::Hash for FontId {
     #[inline(always)]
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        let Self { size, family } = self;
+        let Self {
+            size,
+            family,
+            variations,
+        } = self;
         emath::OrderedFloat(*size).hash(state);
         family.hash(state);
+        variations.hash(state);
+    }
+}
+
+/// Where on the variation axes of a variable font to render text.
+///
+/// This lets a single variable font file provide e.g. both regular and bold text.
+/// Axes that the font doesn't have are ignored, so this has no effect on static fonts.
+///
+/// `None` means the default value of the font.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct FontVariations {
+    /// The `wght` axis, from `1` to `1000`.
+    ///
+    /// `400` is regular, `700` is bold.
+    pub weight: Option<f32>,
+
+    /// The `wdth` axis, as a percentage of the normal width.
+    ///
+    /// `75` is condensed, `100` is normal, `125` is expanded.
+    pub width: Option<f32>,
+
+    /// The `slnt` axis, in degrees counter-clockwise from upright.
+    ///
+    /// So for text that leans forward, use a negative value, e.g. `-10`.
+    pub slant: Option<f32>,
+
+    /// The `opsz` axis, which adapts the design of the glyphs to the size they are shown at.
+    ///
+    /// Usually you want this to be the font size, in points.
+    pub optical_size: Option<f32>,
+}
+
+impl FontVariations {
+    /// Use the defaults of the font.
+    pub const DEFAULT: Self = Self {
+        weight: None,
+        width: None,
+        slant: None,
+        optical_size: None,
+    };
+
+    /// The regular [`Self::weight`].
+    pub const REGULAR: f32 = 400.0;
+
+    /// The bold [`Self::weight`].
+    pub const BOLD: f32 = 700.0;
+
+    /// Is every axis at the default of the font?
+    #[inline]
+    pub fn is_default(&self) -> bool {
+        *self == Self::DEFAULT
+    }
+
+    /// The axis tags and values that are set.
+    pub fn settings(self) -> impl Iterator<Item = ([u8; 4], f32)> {
+        let Self {
+            weight,
+            width,
+            slant,
+            optical_size,
+        } = self;
+        [
+            (*b"wght", weight),
+            (*b"wdth", width),
+            (*b"slnt", slant),
+            (*b"opsz", optical_size),
+        ]
+        .into_iter()
+        .filter_map(|(tag, value)| Some((tag, value?)))
+    }
+}
+
+impl std::hash::Hash for FontVariations {
+    #[inline(always)]
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let Self {
+            weight,
+            width,
+            slant,
+            optical_size,
+        } = self;
+        weight.map(emath::OrderedFloat).hash(state);
+        width.map(emath::OrderedFloat).hash(state);
+        slant.map(emath::OrderedFloat).hash(state);
+        optical_size.map(emath::OrderedFloat).hash(state);
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A [BCP 47](https://www.rfc-editor.org/info/bcp47) language tag, like `"ja"`, `"zh-Hans"` or `"zh-Hant-TW"`.
+///
+/// The language of a text decides which fonts are preferred for it
+/// (see [`FontDefinitions::language_fallbacks`]),
+/// and is passed on to the shaper, so that fonts can pick language-specific glyph variants.
+///
+/// This is mostly important for CJK text, where Japanese, Simplified Chinese and Traditional Chinese
+/// use different variants of the same Han characters.
+///
+/// Tags are case-insensitive, and `_` is treated like `-`.
+/// Only the first 16 ASCII letters, digits and separators of a tag are kept.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(from = "String", into = "String"))]
+pub struct Language {
+    /// Normalized (lowercase) tag, padded with zeros.
+    tag: [u8; Self::MAX_LEN],
+}
+
+impl Language {
+    const MAX_LEN: usize = 16;
+
+    pub fn new(tag: &str) -> Self {
+        let mut bytes = [0; Self::MAX_LEN];
+        let valid = tag
+            .bytes()
+            .filter(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_'));
+        for (dst, src) in bytes.iter_mut().zip(valid) {
+            *dst = match src {
+                b'_' => b'-',
+                _ => src.to_ascii_lowercase(),
+            };
+        }
+        Self { tag: bytes }
+    }
+
+    /// The normalized tag, e.g. `"zh-hant-tw"`.
+    pub fn as_str(&self) -> &str {
+        let len = self
+            .tag
+            .iter()
+            .position(|&b| b == 0)
+            .unwrap_or(Self::MAX_LEN);
+        std::str::from_utf8(&self.tag[..len]).unwrap_or_default()
+    }
+
+    /// The tag, followed by less and less specific versions of it.
+    ///
+    /// For `"zh-Hant-TW"` this is `"zh-hant-tw"`, `"zh-hant"`, `"zh"`.
+    pub fn fallback_chain(&self) -> impl Iterator<Item = &str> {
+        let tag = self.as_str();
+        std::iter::successors(Some(tag), |tag| {
+            tag.rsplit_once('-').map(|(prefix, _)| prefix)
+        })
+        .filter(|tag| !tag.is_empty())
+    }
+
+    /// Does the given tag match this language, or a less specific version of it?
+    ///
+    /// `"zh"` and `"zh-Hant"` both match `"zh-Hant-TW"`, but `"zh-Hans"` does not.
+    pub fn matches(&self, tag: &str) -> bool {
+        let tag = Self::new(tag);
+        self.fallback_chain().any(|prefix| prefix == tag.as_str())
+    }
+}
+
+impl std::fmt::Debug for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Language({:?})", self.as_str())
+    }
+}
+
+impl std::fmt::Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+impl From<&str> for Language {
+    #[inline]
+    fn from(tag: &str) -> Self {
+        Self::new(tag)
+    }
+}
+
+impl From<String> for Language {
+    #[inline]
+    fn from(tag: String) -> Self {
+        Self::new(&tag)
+    }
+}
+
+impl From<Language> for String {
+    #[inline]
+    fn from(language: Language) -> Self {
+        language.as_str().to_owned()
     }
 }
 
@@ -117,8 +362,16 @@ impl std::fmt::Display for FontFamily {
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct FontData {
     /// The content of a `.ttf` or `.otf` file.
+    ///
+    /// Empty if the font is read from [`Self::path`].
     pub font: Cow<'static, [u8]>,
 
+    /// A font file to read the first time a glyph is needed from this font, instead of using [`Self::font`].
+    ///
+    /// See [`Self::from_file`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub path: Option<PathBuf>,
+
     /// Which font face in the file to use.
     /// When in doubt, use `0`.
     pub index: u32,
@@ -131,6 +384,7 @@ impl FontData {
     pub fn from_static(font: &'static [u8]) -> Self {
         Self {
             font: Cow::Borrowed(font),
+            path: None,
             index: 0,
             tweak: Default::default(),
         }
@@ -139,11 +393,26 @@ impl FontData {
     pub fn from_owned(font: Vec<u8>) -> Self {
         Self {
             font: Cow::Owned(font),
+            path: None,
             index: 0,
             tweak: Default::default(),
         }
     }
 
+    /// A font face in a `.ttf`, `.otf`, `.ttc` or `.otc` file.
+    ///
+    /// The file is only read the first time a glyph is needed from this font,
+    /// so this is cheap even for big fonts that are rarely used, like CJK fallbacks.
+    /// If the file can't be read then, an error is logged and the font is skipped.
+    pub fn from_file(path: impl Into<PathBuf>, index: u32) -> Self {
+        Self {
+            font: Cow::Borrowed(&[]),
+            path: Some(path.into()),
+            index,
+            tweak: Default::default(),
+        }
+    }
+
     pub fn tweak(self, tweak: FontTweak) -> Self {
         Self { tweak, ..self }
     }
@@ -158,7 +427,7 @@ impl AsRef<[u8]> for FontData {
 // ----------------------------------------------------------------------------
 
 /// Extra scale and vertical tweak to apply to all text of a certain font.
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct FontTweak {
     /// Scale the font's glyphs by this much.
@@ -190,6 +459,13 @@ pub struct FontTweak {
     ///
     /// `None` means use the global setting.
     pub hinting_override: Option<bool>,
+
+    /// OpenType features to apply to all text using this font, e.g. to always use
+    /// a stylistic set.
+    ///
+    /// Features in [`crate::text::TextFormat::font_features`] are applied after these,
+    /// and so take precedence.
+    pub font_features: Vec<FontFeature>,
 }
 
 impl Default for FontTweak {
@@ -199,6 +475,94 @@ impl Default for FontTweak {
             y_offset_factor: 0.0,
             y_offset: 0.0,
             hinting_override: None,
+            font_features: Vec::new(),
+        }
+    }
+}
+
+/// An OpenType feature to turn on or off when shaping text,
+/// e.g. tabular numbers, small caps or ligatures.
+///
+/// See <https://learn.microsoft.com/en-us/typography/opentype/spec/featuretags>
+/// for the available features.
+/// Features that the font doesn't support are ignored.
+///
+/// ```
+/// # use epaint::text::{FontFeature, TextFormat};
+/// let format = TextFormat {
+///     font_features: vec![FontFeature::TABULAR_NUMBERS, FontFeature::stylistic_set(1)],
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct FontFeature {
+    /// The four-letter tag of the feature, e.g. `*b"tnum"`.
+    pub tag: [u8; 4],
+
+    /// `0` turns the feature off, `1` turns it on.
+    ///
+    /// Some features, like `aalt`, use higher values to pick between alternates.
+    pub value: u32,
+}
+
+impl FontFeature {
+    /// Tabular (monospaced) numbers, so that columns of numbers line up.
+    pub const TABULAR_NUMBERS: Self = Self::enable(*b"tnum");
+
+    /// Proportional numbers.
+    pub const PROPORTIONAL_NUMBERS: Self = Self::enable(*b"pnum");
+
+    /// Lowercase letters as small capitals.
+    pub const SMALL_CAPS: Self = Self::enable(*b"smcp");
+
+    /// Slashed zero, to tell it apart from the letter O.
+    pub const SLASHED_ZERO: Self = Self::enable(*b"zero");
+
+    /// Turns off standard, contextual and discretionary ligatures.
+    ///
+    /// Useful for code editors, where e.g. `!=` should show as two characters.
+    pub const NO_LIGATURES: [Self; 3] = [
+        Self::disable(*b"liga"),
+        Self::disable(*b"clig"),
+        Self::disable(*b"calt"),
+    ];
+
+    #[inline]
+    pub const fn new(tag: [u8; 4], value: u32) -> Self {
+        Self { tag, value }
+    }
+
+    #[inline]
+    pub const fn enable(tag: [u8; 4]) -> Self {
+        Self::new(tag, 1)
+    }
+
+    #[inline]
+    pub const fn disable(tag: [u8; 4]) -> Self {
+        Self::new(tag, 0)
+    }
+
+    /// One of the stylistic sets `ss01`-`ss20`.
+    ///
+    /// `set` is clamped to `1..=20`.
+    pub fn stylistic_set(set: u8) -> Self {
+        let set = set.clamp(1, 20);
+        Self::enable([b's', b's', b'0' + set / 10, b'0' + set % 10])
+    }
+
+    /// The tag as a string, e.g. `"tnum"`.
+    pub fn tag_str(&self) -> &str {
+        std::str::from_utf8(&self.tag).unwrap_or("????")
+    }
+}
+
+impl std::fmt::Display for FontFeature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.value {
+            0 => write!(f, "-{}", self.tag_str()),
+            1 => write!(f, "+{}", self.tag_str()),
+            value => write!(f, "{}={value}", self.tag_str()),
         }
     }
 }
@@ -207,11 +571,14 @@ impl Default for FontTweak {
 
 pub type Blob = Arc<dyn AsRef<[u8]> + Send + Sync>;
 
-fn blob_from_font_data(data: &FontData) -> Blob {
-    match data.clone().font {
+fn blob_from_font_data(data: &FontData) -> std::io::Result<Blob> {
+    if let Some(path) = &data.path {
+        return Ok(Arc::new(std::fs::read(path)?) as Blob);
+    }
+    Ok(match data.clone().font {
         Cow::Borrowed(bytes) => Arc::new(bytes) as Blob,
         Cow::Owned(bytes) => Arc::new(bytes) as Blob,
-    }
+    })
 }
 
 /// Describes the font data and the sizes to use.
@@ -251,6 +618,9 @@ pub struct FontDefinitions {
     /// List of font names and their definitions.
     ///
     /// `epaint` has built-in-default for these, but you can override them if you like.
+    ///
+    /// Each font is only read and parsed the first time a glyph is needed from it.
+    /// A font that fails to load is logged as an error and then skipped, like a font without the glyph.
     pub font_data: BTreeMap<String, Arc<FontData>>,
 
     /// Which fonts (names) to use for each [`FontFamily`].
@@ -260,6 +630,38 @@ pub struct FontDefinitions {
     /// the first font and then move to the second, and so on.
     /// So the first font is the primary, and then comes a list of fallbacks in order of priority.
     pub families: BTreeMap<FontFamily, Vec<String>>,
+
+    /// Extra fallback fonts (names) for text in a specific [`Language`].
+    ///
+    /// The keys are language tags like `"ja"`, `"zh-Hans"` or `"zh-Hant"`.
+    /// Text in the language `"zh-Hant-TW"` will use the fonts for `"zh-Hant-TW"`, `"zh-Hant"` and `"zh"`,
+    /// in that order.
+    ///
+    /// These fonts are tried right after the primary font of the [`FontFamily`],
+    /// but before its fallbacks (see [`Self::families`]).
+    /// This way you can have a Latin primary font, and still get the right Han glyph variants
+    /// for Japanese vs Chinese text.
+    ///
+    /// The language of a text is set with [`crate::text::TextFormat::language`]
+    /// or [`crate::text::TextOptions::language`].
+    pub language_fallbacks: BTreeMap<String, Vec<String>>,
+
+    /// Extra fallback fonts (names) for characters of a specific Unicode script.
+    ///
+    /// The keys are [ISO 15924](https://en.wikipedia.org/wiki/ISO_15924) script codes,
+    /// like `"Arab"`, `"Hani"`, `"Hira"`, `"Kana"` or `"Hang"`.
+    ///
+    /// These fonts are tried after the fonts in [`Self::language_fallbacks`],
+    /// but before the fallbacks of the [`FontFamily`].
+    pub script_fallbacks: BTreeMap<String, Vec<String>>,
+
+    /// Hyphenation dictionaries for text in a specific [`Language`].
+    ///
+    /// The keys are language tags, like in [`Self::language_fallbacks`]:
+    /// the dictionary for `"en"` is also used for `"en-US"`.
+    ///
+    /// These are only used for text with [`crate::text::TextWrapping::hyphenate`] turned on.
+    pub hyphenation: BTreeMap<String, Arc<Hyphenator>>,
 }
 
 #[derive(Debug, Clone)]
@@ -371,6 +773,9 @@ impl Default for FontDefinitions {
         Self {
             font_data,
             families,
+            language_fallbacks: Default::default(),
+            script_fallbacks: Default::default(),
+            hyphenation: Default::default(),
         }
     }
 }
@@ -385,6 +790,9 @@ impl FontDefinitions {
         Self {
             font_data: Default::default(),
             families,
+            language_fallbacks: Default::default(),
+            script_fallbacks: Default::default(),
+            hyphenation: Default::default(),
         }
     }
 
@@ -407,12 +815,22 @@ impl FontDefinitions {
 }
 
 /// Unique ID for looking up a single font face/file.
+///
+/// Each [`crate::text::Glyph`] remembers which font face it was rendered with.
+/// Use [`FontsView::font_face_name`] to find its name.
+///
+/// The keys are only valid for the [`Fonts`] that created them.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-pub(crate) struct FontFaceKey(u64);
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct FontFaceKey(u64);
 
 impl FontFaceKey {
+    /// Used for glyphs that weren't rendered with any font.
     pub const INVALID: Self = Self(0);
 
+    /// Used for the glyphs of [`crate::text::Placeholder`]s, which only reserve space.
+    pub const PLACEHOLDER: Self = Self(1);
+
     fn new() -> Self {
         static KEY_COUNTER: AtomicU64 = AtomicU64::new(1);
         Self(crate::util::hash(
@@ -424,11 +842,79 @@ impl FontFaceKey {
 // Safe, because we hash the value in the constructor.
 impl nohash_hasher::IsEnabled for FontFaceKey {}
 
+/// All the font faces in [`FontDefinitions::font_data`].
+///
+/// Parsing a font can be slow (especially big CJK fonts),
+/// so each font face is only parsed the first time we look for a glyph in it.
+pub(crate) struct FontFaces {
+    options: TextOptions,
+    loaded: nohash_hasher::IntMap<FontFaceKey, FontFace>,
+    unloaded: nohash_hasher::IntMap<FontFaceKey, (String, Arc<FontData>)>,
+}
+
+impl FontFaces {
+    fn new(options: TextOptions) -> Self {
+        Self {
+            options,
+            loaded: Default::default(),
+            unloaded: Default::default(),
+        }
+    }
+
+    fn insert(&mut self, name: String, font_data: Arc<FontData>) -> FontFaceKey {
+        let key = FontFaceKey::new();
+        self.unloaded.insert(key, (name, font_data));
+        key
+    }
+
+    /// Get an already parsed font face.
+    pub fn get(&self, key: &FontFaceKey) -> Option<&FontFace> {
+        self.loaded.get(key)
+    }
+
+    /// Get a font face, parsing it first if needed.
+    ///
+    /// Returns `None` for unknown keys, and fonts that failed to load.
+    pub fn get_mut(&mut self, key: &FontFaceKey) -> Option<&mut FontFace> {
+        if let Some((name, font_data)) = self.unloaded.remove(key) {
+            match blob_from_font_data(&font_data) {
+                Ok(blob) => match FontFace::new(
+                    self.options,
+                    name.clone(),
+                    blob,
+                    font_data.index,
+                    font_data.tweak.clone(),
+                ) {
+                    Ok(font_face) => {
+                        self.loaded.insert(*key, font_face);
+                    }
+                    Err(err) => log::error!("Error parsing {name:?} TTF/OTF font file: {err}"),
+                },
+                Err(err) => log::error!("Error reading {name:?} font file: {err}"),
+            }
+        }
+        self.loaded.get_mut(key)
+    }
+
+    fn name(&self, key: &FontFaceKey) -> Option<&str> {
+        self.loaded
+            .get(key)
+            .map(|font_face| font_face.name())
+            .or_else(|| self.unloaded.get(key).map(|(name, _)| name.as_str()))
+    }
+}
+
 /// Cached data for working with a font family (e.g. doing character lookups).
 #[derive(Debug)]
 pub(super) struct CachedFamily {
     pub fonts: Vec<FontFaceKey>,
 
+    /// Fallbacks for the language, tried after the primary font.
+    pub language_fonts: Vec<FontFaceKey>,
+
+    /// Fallbacks for specific scripts, tried after the language fallbacks.
+    pub script_fonts: Vec<(harfrust::Script, Vec<FontFaceKey>)>,
+
     /// Lazily calculated.
     pub characters: Option<BTreeMap<char, Vec<String>>>,
 
@@ -440,19 +926,28 @@ pub(super) struct CachedFamily {
 impl CachedFamily {
     fn new(
         fonts: Vec<FontFaceKey>,
-        fonts_by_id: &mut nohash_hasher::IntMap<FontFaceKey, FontFace>,
+        language_fonts: Vec<FontFaceKey>,
+        script_fonts: Vec<(harfrust::Script, Vec<FontFaceKey>)>,
+        fonts_by_id: &mut FontFaces,
     ) -> Self {
         if fonts.is_empty() {
             return Self {
                 fonts,
+                language_fonts,
+                script_fonts,
                 characters: None,
                 replacement_glyph: (FontFaceKey::INVALID, GlyphInfo::INVISIBLE),
                 glyph_info_cache: Default::default(),
             };
         }
 
+        // The metrics of the family come from the primary font, so make sure it is parsed:
+        fonts_by_id.get_mut(&fonts[0]);
+
         let mut slf = Self {
             fonts,
+            language_fonts,
+            script_fonts,
             characters: None,
             replacement_glyph: (FontFaceKey::INVALID, GlyphInfo::INVISIBLE),
             glyph_info_cache: Default::default(),
@@ -478,11 +973,29 @@ impl CachedFamily {
     pub(crate) fn glyph_info_no_cache_or_fallback(
         &mut self,
         c: char,
-        fonts_by_id: &mut nohash_hasher::IntMap<FontFaceKey, FontFace>,
+        fonts_by_id: &mut FontFaces,
     ) -> Option<(FontFaceKey, GlyphInfo)> {
-        for font_key in &self.fonts {
-            let font_face = fonts_by_id.get_mut(font_key).expect("Nonexistent font ID");
-            if let Some(glyph_info) = font_face.glyph_info(c) {
+        let script_fonts = if self.script_fonts.is_empty() {
+            &[][..]
+        } else {
+            char_script(c)
+                .and_then(|script| self.script_fonts.iter().find(|(s, _)| *s == script))
+                .map_or(&[][..], |(_, fonts)| fonts.as_slice())
+        };
+
+        // The primary font, then the fallbacks for the language and script, then the other fallbacks:
+        let (primary, fallbacks) = self.fonts.split_at(self.fonts.len().min(1));
+        let fonts = primary
+            .iter()
+            .chain(&self.language_fonts)
+            .chain(script_fonts)
+            .chain(fallbacks);
+
+        for font_key in fonts {
+            let Some(font_face) = fonts_by_id.get_mut(font_key) else {
+                continue; // Failed to parse
+            };
+            if let Some(glyph_info) = font_face.glyph_info(c, FontVariations::DEFAULT) {
                 self.glyph_info_cache.insert(c, (*font_key, glyph_info));
                 return Some((*font_key, glyph_info));
             }
@@ -525,14 +1038,23 @@ impl Fonts {
     /// as well as notice when the font atlas is getting full, and handle that.
     pub fn begin_pass(&mut self, options: TextOptions) {
         let text_options_changed = self.fonts.options() != &options;
-        let font_atlas_almost_full = self.fonts.atlas.fill_ratio() > 0.8;
+        let font_atlas_almost_full = self.fonts.atlas.fill_ratio() > 0.8
+            || self
+                .fonts
+                .distance_field_atlas
+                .as_ref()
+                .is_some_and(|atlas| atlas.fill_ratio() > 0.8);
         let needs_recreate = text_options_changed || font_atlas_almost_full;
 
         if needs_recreate {
             let definitions = self.fonts.definitions.clone();
+            let distance_field_supported = self.fonts.distance_field_supported;
 
             *self = Self {
-                fonts: FontsImpl::new(options, definitions),
+                fonts: FontsImpl {
+                    distance_field_supported,
+                    ..FontsImpl::new(options, definitions)
+                },
                 galley_cache: Default::default(),
             };
         }
@@ -545,6 +1067,23 @@ impl Fonts {
         self.fonts.atlas.take_delta()
     }
 
+    /// Like [`Self::font_image_delta`], but for the texture [`crate::TextureId::DISTANCE_FIELD_FONT`].
+    ///
+    /// This is only ever `Some` once some text has been laid out with [`GlyphRendering::DistanceField`].
+    pub fn distance_field_font_image_delta(&mut self) -> Option<crate::ImageDelta> {
+        self.fonts.distance_field_atlas.as_mut()?.take_delta()
+    }
+
+    /// Can the renderer draw textures with [`crate::textures::TextureOptions::distance_field`]?
+    ///
+    /// If not, text with [`GlyphRendering::DistanceField`] is laid out with
+    /// [`GlyphRendering::Bitmap`] instead.
+    ///
+    /// Default: `true`.
+    pub fn set_distance_field_supported(&mut self, supported: bool) {
+        self.fonts.distance_field_supported = supported;
+    }
+
     #[inline]
     pub fn options(&self) -> &TextOptions {
         self.texture_atlas().options()
@@ -672,6 +1211,26 @@ impl FontsView<'_> {
         self.fonts.definitions.families.keys().cloned().collect()
     }
 
+    /// The name of a font face, i.e. its key in [`FontDefinitions::font_data`].
+    ///
+    /// See [`crate::text::Glyph::font_face`].
+    pub fn font_face_name(&self, font_face: FontFaceKey) -> Option<&str> {
+        self.fonts.font_face_name(font_face)
+    }
+
+    /// Which font rendered each glyph of the galley?
+    ///
+    /// Useful for debugging font fallback.
+    /// Returns `None` for glyphs that weren't rendered with any font.
+    pub fn glyph_font_names(&self, galley: &Galley) -> Vec<(char, Option<&str>)> {
+        galley
+            .rows
+            .iter()
+            .flat_map(|row| &row.glyphs)
+            .map(|glyph| (glyph.chr, self.font_face_name(glyph.font_face)))
+            .collect()
+    }
+
     /// Layout some text.
     ///
     /// This is the most advanced layout function.
@@ -680,7 +1239,12 @@ impl FontsView<'_> {
     ///
     /// The implementation uses memoization so repeated calls are cheap.
     #[inline]
-    pub fn layout_job(&mut self, job: LayoutJob) -> Arc<Galley> {
+    pub fn layout_job(&mut self, mut job: LayoutJob) -> Arc<Galley> {
+        if job.glyph_rendering == GlyphRendering::DistanceField
+            && !self.fonts.distance_field_supported
+        {
+            job.glyph_rendering = GlyphRendering::Bitmap;
+        }
         let allow_split_paragraphs = true; // Optimization for editing text with many paragraphs.
         self.galley_cache.layout(
             self.fonts,
@@ -753,34 +1317,44 @@ impl FontsView<'_> {
 pub struct FontsImpl {
     definitions: FontDefinitions,
     atlas: TextureAtlas,
-    fonts_by_id: nohash_hasher::IntMap<FontFaceKey, FontFace>,
+
+    /// Created the first time we lay out text with [`GlyphRendering::DistanceField`].
+    distance_field_atlas: Option<TextureAtlas>,
+
+    /// See [`Fonts::set_distance_field_supported`].
+    distance_field_supported: bool,
+
+    /// The glyph outlines and shadows in the atlases, see [`Self::glyph_halo`].
+    glyph_halos: ahash::HashMap<GlyphHaloKey, UvRect>,
+
+    fonts_by_id: FontFaces,
     fonts_by_name: ahash::HashMap<String, FontFaceKey>,
-    family_cache: ahash::HashMap<FontFamily, CachedFamily>,
+    family_cache: ahash::HashMap<(FontFamily, Option<Language>), CachedFamily>,
 }
 
 impl FontsImpl {
     /// Create a new [`FontsImpl`] for text layout.
     /// This call is expensive, so only create one [`FontsImpl`] and then reuse it.
+    ///
+    /// The fonts are parsed lazily, the first time a glyph is looked up in them.
     pub fn new(options: TextOptions, definitions: FontDefinitions) -> Self {
         let texture_width = options.max_texture_side.at_most(16 * 1024);
         let initial_height = 32; // Keep initial font atlas small, so it is fast to upload to GPU. This will expand as needed anyways.
         let atlas = TextureAtlas::new([texture_width, initial_height], options);
 
-        let mut fonts_by_id: nohash_hasher::IntMap<FontFaceKey, FontFace> = Default::default();
+        let mut fonts_by_id = FontFaces::new(options);
         let mut fonts_by_name: ahash::HashMap<String, FontFaceKey> = Default::default();
         for (name, font_data) in &definitions.font_data {
-            let tweak = font_data.tweak;
-            let blob = blob_from_font_data(font_data);
-            let font_face = FontFace::new(options, name.clone(), blob, font_data.index, tweak)
-                .unwrap_or_else(|err| panic!("Error parsing {name:?} TTF/OTF font file: {err}"));
-            let key = FontFaceKey::new();
-            fonts_by_id.insert(key, font_face);
+            let key = fonts_by_id.insert(name.clone(), font_data.clone());
             fonts_by_name.insert(name.clone(), key);
         }
 
         Self {
             definitions,
             atlas,
+            distance_field_atlas: None,
+            distance_field_supported: true,
+            glyph_halos: Default::default(),
             fonts_by_id,
             fonts_by_name,
             family_cache: Default::default(),
@@ -791,33 +1365,178 @@ impl FontsImpl {
         self.atlas.options()
     }
 
-    /// Get the right font implementation from [`FontFamily`].
+    /// The name of a font face, i.e. its key in [`FontDefinitions::font_data`].
+    pub fn font_face_name(&self, font_face: FontFaceKey) -> Option<&str> {
+        self.fonts_by_id.name(&font_face)
+    }
+
+    /// Get the right font implementation from [`FontFamily`],
+    /// using the fallbacks for [`TextOptions::language`].
     pub fn font(&mut self, family: &FontFamily) -> Font<'_> {
-        let cached_family = self.family_cache.entry(family.clone()).or_insert_with(|| {
+        let language = self.options().language;
+        self.font_for_language(family, language)
+    }
+
+    /// Get the right font implementation from [`FontFamily`],
+    /// using the fallbacks for the given language (see [`FontDefinitions::language_fallbacks`]).
+    pub fn font_for_language(
+        &mut self,
+        family: &FontFamily,
+        language: Option<Language>,
+    ) -> Font<'_> {
+        self.font_for_layout(family, language, GlyphRendering::Bitmap)
+    }
+
+    /// The hyphenation dictionary for the given language (see [`FontDefinitions::hyphenation`]), if any.
+    pub(crate) fn hyphenator(&self, language: Option<Language>) -> Option<&Hyphenator> {
+        // Most specific language tag first:
+        language
+            .iter()
+            .flat_map(|language| language.fallback_chain())
+            .find_map(|tag| {
+                self.definitions
+                    .hyphenation
+                    .iter()
+                    .find(|(key, _)| Language::new(key).as_str() == tag)
+            })
+            .map(|(_, hyphenator)| hyphenator.as_ref())
+    }
+
+    /// The glyph at `uv_rect`, grown by `radius` points with an edge that fades out over `blur` points.
+    ///
+    /// Used to paint outlines and shadows under glyphs. The result is cached.
+    pub(crate) fn glyph_halo(
+        &mut self,
+        glyph_rendering: GlyphRendering,
+        uv_rect: UvRect,
+        radius: f32,
+        blur: f32,
+    ) -> UvRect {
+        if uv_rect.is_nothing() || uv_rect.size.x <= 0.0 {
+            return UvRect::default();
+        }
+        let atlas = match glyph_rendering {
+            GlyphRendering::Bitmap => &mut self.atlas,
+            GlyphRendering::DistanceField => match &mut self.distance_field_atlas {
+                Some(atlas) => atlas,
+                None => return UvRect::default(),
+            },
+        };
+
+        let texels_per_point = (uv_rect.max[0] - uv_rect.min[0]) as f32 / uv_rect.size.x;
+        // Quantize to an eighth of a texel, so we don't fill the atlas with near-identical halos:
+        let radius = (8.0 * radius * texels_per_point).round() / 8.0;
+        let blur = (8.0 * blur * texels_per_point).round() / 8.0;
+        let key = GlyphHaloKey {
+            glyph_rendering,
+            min: uv_rect.min,
+            max: uv_rect.max,
+            radius: OrderedFloat(radius),
+            blur: OrderedFloat(blur),
+        };
+
+        *self.glyph_halos.entry(key).or_insert_with(|| {
+            let (pos, (width, height), pad) =
+                atlas.allocate_halo(uv_rect.min, uv_rect.max, radius, blur);
+            UvRect {
+                offset: uv_rect.offset - Vec2::splat(pad as f32 / texels_per_point),
+                size: vec2(width as f32, height as f32) / texels_per_point,
+                min: [pos.0 as u16, pos.1 as u16],
+                max: [(pos.0 + width) as u16, (pos.1 + height) as u16],
+                colored: false,
+            }
+        })
+    }
+
+    /// Like [`Self::font_for_language`], but the glyphs are allocated in the atlas for the given [`GlyphRendering`].
+    pub(crate) fn font_for_layout(
+        &mut self,
+        family: &FontFamily,
+        language: Option<Language>,
+        glyph_rendering: GlyphRendering,
+    ) -> Font<'_> {
+        let key = (family.clone(), language);
+        let cached_family = self.family_cache.entry(key).or_insert_with(|| {
+            let fonts_by_name = &self.fonts_by_name;
+            let font_keys = |font_names: &[String]| -> Vec<FontFaceKey> {
+                font_names
+                    .iter()
+                    .map(|font_name| {
+                        *fonts_by_name
+                            .get(font_name)
+                            .unwrap_or_else(|| panic!("No font data found for {font_name:?}"))
+                    })
+                    .collect()
+            };
+
             let fonts = &self.definitions.families.get(family);
             let fonts =
                 fonts.unwrap_or_else(|| panic!("FontFamily::{family:?} is not bound to any fonts"));
+            let fonts = font_keys(fonts);
 
-            let fonts: Vec<FontFaceKey> = fonts
+            // Most specific language tag first:
+            let language_fonts = language
                 .iter()
-                .map(|font_name| {
-                    *self
-                        .fonts_by_name
-                        .get(font_name)
-                        .unwrap_or_else(|| panic!("No font data found for {font_name:?}"))
+                .flat_map(|language| language.fallback_chain())
+                .flat_map(|tag| {
+                    self.definitions
+                        .language_fallbacks
+                        .iter()
+                        .filter(move |(key, _)| Language::new(key).as_str() == tag)
                 })
+                .flat_map(|(_, font_names)| font_keys(font_names))
                 .collect();
 
-            CachedFamily::new(fonts, &mut self.fonts_by_id)
+            let script_fonts = self
+                .definitions
+                .script_fallbacks
+                .iter()
+                .filter_map(|(script, font_names)| {
+                    let Some(script) = harfrust::Tag::new_checked(script.as_bytes())
+                        .ok()
+                        .and_then(harfrust::Script::from_iso15924_tag)
+                    else {
+                        log::warn!(
+                            "Unknown script {script:?} in FontDefinitions::script_fallbacks"
+                        );
+                        return None;
+                    };
+                    Some((script, font_keys(font_names)))
+                })
+                .collect();
+
+            CachedFamily::new(fonts, language_fonts, script_fonts, &mut self.fonts_by_id)
         });
+        let atlas = match glyph_rendering {
+            GlyphRendering::Bitmap => &mut self.atlas,
+            GlyphRendering::DistanceField => {
+                let options = *self.atlas.options();
+                self.distance_field_atlas
+                    .get_or_insert_with(|| TextureAtlas::new_distance_field(options))
+            }
+        };
         Font {
             fonts_by_id: &mut self.fonts_by_id,
             cached_family,
-            atlas: &mut self.atlas,
+            atlas,
         }
     }
 }
 
+/// See [`FontsImpl::glyph_halo`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct GlyphHaloKey {
+    glyph_rendering: GlyphRendering,
+    min: [u16; 2],
+    max: [u16; 2],
+
+    /// In texels.
+    radius: OrderedFloat<f32>,
+
+    /// In texels.
+    blur: OrderedFloat<f32>,
+}
+
 // ----------------------------------------------------------------------------
 
 struct CachedGalley {
@@ -986,6 +1705,9 @@ impl GalleyCache {
                     0.0
                 },
                 round_output_to_gui: job.round_output_to_gui,
+                glyph_rendering: job.glyph_rendering,
+                tab_stops: job.tab_stops.clone(),
+                writing_mode: job.writing_mode,
             };
 
             // Add overlapping sections:
@@ -994,6 +1716,8 @@ impl GalleyCache {
                     leading_space,
                     byte_range: section_range,
                     format,
+                    placeholder,
+                    link,
                 } = section;
 
                 // `start` and `end` are the byte range of the current paragraph.
@@ -1024,6 +1748,8 @@ impl GalleyCache {
                         },
                         byte_range: new_range,
                         format: format.clone(),
+                        placeholder: *placeholder,
+                        link: link.clone(),
                     });
                 }
             }
@@ -1038,7 +1764,7 @@ impl GalleyCache {
                 max_rows_remaining -= galley.rows.len();
             }
 
-            let elided = galley.elided;
+            let elided = galley.elided.is_some();
             child_galleys.push(galley);
             if elided {
                 break;
@@ -1071,7 +1797,11 @@ fn should_cache_each_paragraph_individually(job: &LayoutJob) -> bool {
     // We currently don't support this elided text, i.e. when `max_rows` is set.
     // Most often, elided text is elided to one row,
     // and so will always be fast to lay out.
-    job.break_on_newline && job.wrap.max_rows == usize::MAX && job.text.contains('\n')
+    // Neither do we support vertical text, which is turned as a whole.
+    job.break_on_newline
+        && job.wrap.max_rows == usize::MAX
+        && job.writing_mode == crate::text::WritingMode::Horizontal
+        && job.text.contains('\n')
 }
 
 #[cfg(feature = "default_fonts")]
@@ -1294,4 +2024,87 @@ mod tests {
         let width = view.glyph_width(&FontId::new(12.0, FontFamily::Proportional), ' ');
         assert_eq!(width, 0.0);
     }
+
+    #[test]
+    fn test_fonts_are_parsed_lazily() {
+        let mut fonts = Fonts::new(TextOptions::default(), FontDefinitions::default());
+        assert_eq!(fonts.fonts.fonts_by_id.loaded.len(), 0);
+
+        // Only the primary font is needed for latin text:
+        let mut view = fonts.with_pixels_per_point(1.0);
+        view.layout_no_wrap("Hello".to_owned(), FontId::monospace(12.0), Color32::WHITE);
+        fn loaded_names(fonts: &Fonts) -> Vec<&str> {
+            let mut names: Vec<&str> = fonts
+                .fonts
+                .fonts_by_id
+                .loaded
+                .values()
+                .map(|font_face| font_face.name())
+                .collect();
+            names.sort_unstable();
+            names
+        }
+        assert_eq!(loaded_names(&fonts), ["Hack"]);
+
+        // Only now do we need the last fallback:
+        let mut view = fonts.with_pixels_per_point(1.0);
+        view.layout_no_wrap(
+            "\u{E600}".to_owned(),
+            FontId::monospace(12.0),
+            Color32::WHITE,
+        );
+        assert_eq!(
+            loaded_names(&fonts),
+            [
+                "Hack",
+                "NotoEmoji-Regular",
+                "Ubuntu-Light",
+                "emoji-icon-font"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_distance_field_glyphs() {
+        let mut fonts = Fonts::new(TextOptions::default(), FontDefinitions::default());
+        assert!(fonts.distance_field_font_image_delta().is_none());
+
+        let mut glyph_sizes = vec![];
+        for font_size in [12.0, 48.0] {
+            let mut job = LayoutJob::simple_singleline(
+                "W".to_owned(),
+                FontId::proportional(font_size),
+                Color32::WHITE,
+            );
+            job.glyph_rendering = GlyphRendering::DistanceField;
+            let galley = fonts.with_pixels_per_point(1.0).layout_job(job);
+            let glyph = &galley.rows[0].glyphs[0];
+            assert!(!glyph.uv_rect.is_nothing());
+            glyph_sizes.push(glyph.uv_rect.size.x);
+        }
+
+        // The same distance field is reused and scaled for all sizes:
+        assert!((glyph_sizes[1] / glyph_sizes[0] - 4.0).abs() < 0.01);
+
+        let delta = fonts.distance_field_font_image_delta().unwrap();
+        assert!(delta.options.distance_field);
+        assert_eq!(delta.image.size(), [TextureAtlas::DISTANCE_FIELD_SIZE; 2]);
+    }
+
+    #[test]
+    fn test_distance_field_unsupported() {
+        let mut fonts = Fonts::new(TextOptions::default(), FontDefinitions::default());
+        fonts.set_distance_field_supported(false);
+        fonts.begin_pass(TextOptions::default());
+
+        let mut job = LayoutJob::simple_singleline(
+            "W".to_owned(),
+            FontId::proportional(12.0),
+            Color32::WHITE,
+        );
+        job.glyph_rendering = GlyphRendering::DistanceField;
+        let galley = fonts.with_pixels_per_point(1.0).layout_job(job);
+        assert_eq!(galley.job.glyph_rendering, GlyphRendering::Bitmap);
+        assert!(fonts.distance_field_font_image_delta().is_none());
+    }
 }
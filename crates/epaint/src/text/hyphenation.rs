@@ -0,0 +1,194 @@
+use std::collections::BTreeMap;
+
+/// Finds the places where a word may be hyphenated,
+/// using Liang's algorithm with a dictionary of TeX hyphenation patterns.
+///
+/// Hyphenation patterns for most languages are available from
+/// <https://github.com/hyphenation/tex-hyphen>
+/// (use the `hyph-*.pat.txt` and `hyph-*.hyp.txt` files).
+///
+/// Register it for a language in [`crate::text::FontDefinitions::hyphenation`],
+/// and turn on [`crate::text::TextWrapping::hyphenate`] to use it.
+///
+/// ```
+/// # use epaint::text::Hyphenator;
+/// let hyphenator = Hyphenator::new("1ta 1to");
+/// assert_eq!(hyphenator.hyphenate("potatoes"), vec![2, 4]); // "po-ta-toes"
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Hyphenator {
+    /// The letters of each pattern (with `.` marking the start or end of a word),
+    /// and the priority of a hyphen before each letter, and after the last one.
+    patterns: BTreeMap<String, Vec<u8>>,
+
+    /// Words with explicit hyphenation points, in lowercase.
+    ///
+    /// The values are char indices.
+    exceptions: BTreeMap<String, Vec<usize>>,
+
+    /// Never put a hyphen before this many chars into a word.
+    ///
+    /// Values below 1 are treated as 1.
+    ///
+    /// Default: 2
+    pub left_min: usize,
+
+    /// Never put a hyphen after this many chars before the end of a word.
+    ///
+    /// Values below 1 are treated as 1.
+    ///
+    /// Default: 3
+    pub right_min: usize,
+}
+
+impl std::fmt::Debug for Hyphenator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Hyphenator")
+            .field("num_patterns", &self.patterns.len())
+            .field("num_exceptions", &self.exceptions.len())
+            .field("left_min", &self.left_min)
+            .field("right_min", &self.right_min)
+            .finish()
+    }
+}
+
+impl Hyphenator {
+    /// Create a hyphenator from whitespace-separated TeX patterns, e.g. `".ach4 4ab. a1b"`.
+    pub fn new(patterns: &str) -> Self {
+        let patterns = patterns
+            .split_whitespace()
+            .filter(|pattern| !pattern.starts_with('%'))
+            .map(|pattern| {
+                let mut letters = String::new();
+                let mut priorities = vec![0];
+                for c in pattern.chars() {
+                    if let Some(digit) = c.to_digit(10) {
+                        *priorities.last_mut().unwrap() = digit as u8;
+                    } else {
+                        letters.extend(c.to_lowercase());
+                        priorities.push(0);
+                    }
+                }
+                (letters, priorities)
+            })
+            .collect();
+
+        Self {
+            patterns,
+            exceptions: Default::default(),
+            left_min: 2,
+            right_min: 3,
+        }
+    }
+
+    /// Add whitespace-separated words with explicit hyphenation, e.g. `"as-so-ciate project"`.
+    ///
+    /// These take precedence over the patterns.
+    #[inline]
+    pub fn with_exceptions(mut self, exceptions: &str) -> Self {
+        for exception in exceptions.split_whitespace() {
+            let mut word = String::new();
+            let mut points = vec![];
+            for c in exception.chars() {
+                if c == '-' {
+                    points.push(word.chars().count());
+                } else {
+                    word.extend(c.to_lowercase());
+                }
+            }
+            self.exceptions.insert(word, points);
+        }
+        self
+    }
+
+    /// Where may we break the given word with a hyphen?
+    ///
+    /// Returns the byte offsets into `word` where a hyphen may be inserted, in order.
+    pub fn hyphenate(&self, word: &str) -> Vec<usize> {
+        let chars: Vec<char> = word.chars().collect();
+        let byte_offsets: Vec<usize> = word.char_indices().map(|(i, _)| i).collect();
+        self.hyphenate_chars(&chars)
+            .into_iter()
+            .map(|i| byte_offsets[i])
+            .collect()
+    }
+
+    /// Like [`Self::hyphenate`], but returns char indices.
+    pub(crate) fn hyphenate_chars(&self, word: &[char]) -> Vec<usize> {
+        let num_chars = word.len();
+        if num_chars < self.left_min + self.right_min {
+            return vec![];
+        }
+
+        let lowercase: String = word.iter().flat_map(|c| c.to_lowercase()).collect();
+        if lowercase.chars().count() != num_chars {
+            return vec![]; // Lowercasing changed the length of the word. Play it safe.
+        }
+
+        // Never at the very start or end of the word:
+        let (left_min, right_min) = (self.left_min.max(1), self.right_min.max(1));
+        let allowed = |i: &usize| left_min <= *i && *i + right_min <= num_chars;
+
+        if let Some(points) = self.exceptions.get(&lowercase) {
+            return points.iter().copied().filter(allowed).collect();
+        }
+
+        // The word with `.` at either end, and the byte offset of each char:
+        let dotted = format!(".{lowercase}.");
+        let offsets: Vec<usize> = dotted
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain(std::iter::once(dotted.len()))
+            .collect();
+
+        // Priority of a hyphen before each char of `dotted`, and after the last one:
+        let mut priorities = vec![0_u8; offsets.len()];
+        for start in 0..offsets.len() {
+            for end in start + 1..offsets.len() {
+                if let Some(pattern) = self.patterns.get(&dotted[offsets[start]..offsets[end]]) {
+                    for (priority, &value) in priorities[start..].iter_mut().zip(pattern) {
+                        *priority = (*priority).max(value);
+                    }
+                }
+            }
+        }
+
+        // An odd priority before `dotted[i + 1]` means we may hyphenate before `word[i]`:
+        (1..num_chars)
+            .filter(allowed)
+            .filter(|&i| priorities[i + 1] % 2 == 1)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Hyphenator;
+
+    #[test]
+    fn test_liang_patterns() {
+        // A few of the patterns from the original TeX `hyphen.tex`:
+        let hyphenator = Hyphenator::new("hy3ph he2n hena4 hen5at 1na n2at 1tio 2io o2n");
+        assert_eq!(hyphenator.hyphenate("hyphenation"), vec![2, 6]); // hy-phen-ation
+        assert_eq!(hyphenator.hyphenate("Hyphenation"), vec![2, 6]);
+        assert!(hyphenator.hyphenate("hyp").is_empty());
+    }
+
+    #[test]
+    fn test_hyphenation_exceptions() {
+        let mut hyphenator = Hyphenator::new("1ta 1to").with_exceptions("pot-a-toes");
+        assert_eq!(hyphenator.hyphenate("potatoes"), vec![3, 4]);
+        assert_eq!(hyphenator.hyphenate("tomatoes"), vec![4]);
+
+        hyphenator.right_min = 5;
+        assert_eq!(hyphenator.hyphenate("potatoes"), vec![3]);
+        assert!(hyphenator.hyphenate("tomatoes").is_empty());
+
+        // No hyphen at either end of the word, even if the exception has one:
+        let mut hyphenator = Hyphenator::new("").with_exceptions("-pot-a-toes-");
+        hyphenator.left_min = 0;
+        hyphenator.right_min = 0;
+        assert_eq!(hyphenator.hyphenate("potatoes"), vec![3, 4]);
+    }
+}
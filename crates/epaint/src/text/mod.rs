@@ -2,7 +2,9 @@
 
 pub mod cursor;
 mod font;
+mod font_database;
 mod fonts;
+mod hyphenation;
 mod text_layout;
 mod text_layout_types;
 
@@ -10,10 +12,13 @@ mod text_layout_types;
 pub const TAB_SIZE: usize = 4;
 
 pub use {
+    font_database::{FontDatabase, FontFaceInfo},
     fonts::{
-        FontData, FontDefinitions, FontFamily, FontId, FontInsert, FontPriority, FontTweak, Fonts,
-        FontsImpl, FontsView, InsertFontFamily,
+        FontData, FontDefinitions, FontFaceKey, FontFamily, FontFeature, FontId, FontInsert,
+        FontPriority, FontTweak, FontVariations, Fonts, FontsImpl, FontsView, InsertFontFamily,
+        Language,
     },
+    hyphenation::Hyphenator,
     text_layout::*,
     text_layout_types::*,
 };
@@ -37,6 +42,13 @@ pub struct TextOptions {
     ///
     /// Default is `true`.
     pub font_hinting: bool,
+
+    /// The language of all text that doesn't set [`TextFormat::language`].
+    ///
+    /// This decides which of the [`FontDefinitions::language_fallbacks`] are used.
+    ///
+    /// Default is `None`.
+    pub language: Option<Language>,
 }
 
 impl Default for TextOptions {
@@ -45,6 +57,7 @@ impl Default for TextOptions {
             max_texture_side: 2048, // Small but portable
             alpha_from_coverage: crate::AlphaFromCoverage::default(),
             font_hinting: true,
+            language: None,
         }
     }
 }
@@ -1,17 +1,24 @@
-use std::sync::Arc;
+use std::{ops::Range, sync::Arc};
 
 use emath::{Align, GuiRounding as _, NumExt as _, Pos2, Rect, Vec2, pos2, vec2};
 
 use crate::{
-    Color32, Mesh, Stroke, Vertex,
+    Color32, Mesh, QuadraticBezierShape, Shadow, Shape, Stroke, Vertex,
     stroke::PathStroke,
     text::{
-        font::{ScaledMetrics, is_cjk, is_cjk_break_allowed},
+        font::{
+            Font, GlyphInfo, ScaledMetrics, UvRect, invisible_char, is_cjk,
+            is_upright_in_vertical_text,
+        },
         fonts::FontFaceKey,
     },
 };
 
-use super::{FontsImpl, Galley, Glyph, LayoutJob, LayoutSection, PlacedRow, Row, RowVisuals};
+use super::{
+    DecorationMetrics, DecorationStyle, Elision, ExtraGlyph, FontFeature, FontVariations,
+    FontsImpl, Galley, Glyph, GlyphRendering, Language, LayoutJob, LayoutSection, PlacedRow,
+    Placeholder, Row, RowVisuals, TabAlign, TabStop, TextFormat, WritingMode,
+};
 
 // ----------------------------------------------------------------------------
 
@@ -36,11 +43,6 @@ impl PointScale {
     pub fn round_to_pixel(&self, point: f32) -> f32 {
         (point * self.pixels_per_point).round() / self.pixels_per_point
     }
-
-    #[inline(always)]
-    pub fn floor_to_pixel(&self, point: f32) -> f32 {
-        (point * self.pixels_per_point).floor() / self.pixels_per_point
-    }
 }
 
 // ----------------------------------------------------------------------------
@@ -56,19 +58,102 @@ struct Paragraph {
 
     pub glyphs: Vec<Glyph>,
 
+    /// See [`Row::extra_glyphs`]. Indexes into [`Self::glyphs`].
+    pub extra_glyphs: Vec<ExtraGlyph>,
+
     /// In case of an empty paragraph ("\n"), use this as height.
     pub empty_paragraph_height: f32,
+
+    /// The paragraph embedding level from the Unicode Bidirectional Algorithm:
+    /// 0 for left-to-right paragraphs, 1 for right-to-left.
+    pub base_level: u8,
+
+    /// [`Self::glyphs`] before [`apply_tab_stops`] moved them.
+    ///
+    /// Empty if the tab stops don't need to be applied again for each wrapped row.
+    pub untabbed_glyphs: Vec<Glyph>,
 }
 
 impl Paragraph {
-    pub fn from_section_index(section_index_at_start: u32) -> Self {
+    pub fn from_section_index(section_index_at_start: u32, base_level: u8) -> Self {
         Self {
             cursor_x_px: 0.0,
             section_index_at_start,
             glyphs: vec![],
+            extra_glyphs: vec![],
             empty_paragraph_height: 0.0,
+            base_level,
+            untabbed_glyphs: vec![],
         }
     }
+
+    fn push_glyph(&mut self, glyph: Glyph, continues_cluster: bool) {
+        self.glyphs.push(Glyph {
+            continues_cluster,
+            ..glyph
+        });
+    }
+
+    /// Can we break the row before the glyph at this index?
+    ///
+    /// We never break a row in the middle of a shaping cluster.
+    fn is_cluster_start(&self, index: usize) -> bool {
+        !self
+            .glyphs
+            .get(index)
+            .is_some_and(|glyph| glyph.continues_cluster)
+    }
+
+    /// The extra glyphs of the glyphs in `range`, with indices relative to the start of the range.
+    fn extra_glyphs_in(&self, range: std::ops::Range<usize>) -> Vec<ExtraGlyph> {
+        self.extra_glyphs
+            .iter()
+            .filter(|extra| range.contains(&(extra.glyph_index as usize)))
+            .map(|extra| ExtraGlyph {
+                glyph_index: extra.glyph_index - range.start as u32,
+                ..*extra
+            })
+            .collect()
+    }
+}
+
+/// The result of running the Unicode Bidirectional Algorithm on the text of a [`LayoutJob`].
+struct BidiLevels {
+    /// One for each byte of the text.
+    levels: Vec<unicode_bidi::Level>,
+
+    /// The byte range and base level of each paragraph.
+    paragraphs: Vec<(std::ops::Range<usize>, unicode_bidi::Level)>,
+}
+
+impl BidiLevels {
+    /// Returns `None` if the text is all left-to-right.
+    fn new(text: &str) -> Option<Self> {
+        let bidi_info = unicode_bidi::BidiInfo::new(text, None);
+        bidi_info.has_rtl().then(|| Self {
+            paragraphs: bidi_info
+                .paragraphs
+                .iter()
+                .map(|paragraph| (paragraph.range.clone(), paragraph.level))
+                .collect(),
+            levels: bidi_info.levels,
+        })
+    }
+
+    fn level_at(bidi: Option<&Self>, byte_index: usize) -> u8 {
+        bidi.and_then(|bidi| bidi.levels.get(byte_index))
+            .map_or(0, |level| level.number())
+    }
+
+    fn paragraph_level_at(bidi: Option<&Self>, byte_index: usize) -> u8 {
+        bidi.and_then(|bidi| {
+            bidi.paragraphs
+                .iter()
+                .find(|(range, _)| byte_index < range.end)
+                .or_else(|| bidi.paragraphs.last())
+        })
+        .map_or(0, |(_, level)| level.number())
+    }
 }
 
 /// Layout text into a [`Galley`].
@@ -81,6 +166,7 @@ pub fn layout(fonts: &mut FontsImpl, pixels_per_point: f32, job: Arc<LayoutJob>)
     if job.wrap.max_rows == 0 {
         // Early-out: no text
         return Galley {
+            elided: Some(0..job.text.len()),
             job,
             rows: Default::default(),
             rect: Rect::ZERO,
@@ -88,36 +174,92 @@ pub fn layout(fonts: &mut FontsImpl, pixels_per_point: f32, job: Arc<LayoutJob>)
             num_vertices: 0,
             num_indices: 0,
             pixels_per_point,
-            elided: true,
             intrinsic_size: Vec2::ZERO,
         };
     }
 
     // For most of this we ignore the y coordinate:
 
-    let mut paragraphs = vec![Paragraph::from_section_index(0)];
+    let bidi = BidiLevels::new(&job.text);
+
+    let mut paragraphs = vec![Paragraph::from_section_index(
+        0,
+        BidiLevels::paragraph_level_at(bidi.as_ref(), 0),
+    )];
+    let mut section_decorations = Vec::with_capacity(job.sections.len());
     for (section_index, section) in job.sections.iter().enumerate() {
-        layout_section(
+        section_decorations.push(layout_section(
             fonts,
             pixels_per_point,
             &job,
+            bidi.as_ref(),
             section_index as u32,
             section,
             &mut paragraphs,
-        );
+        ));
     }
 
     let point_scale = PointScale::new(pixels_per_point);
 
+    if !job.tab_stops.is_empty() {
+        for paragraph in &mut paragraphs {
+            paragraph.untabbed_glyphs = paragraph.glyphs.clone();
+            apply_tab_stops(point_scale, &job, &mut paragraph.glyphs, 0.0);
+        }
+    }
+
     let intrinsic_size = calculate_intrinsic_size(point_scale, &job, &paragraphs);
 
-    let mut elided = false;
-    let mut rows = rows_from_paragraphs(paragraphs, &job, &mut elided);
-    if elided && let Some(last_placed) = rows.last_mut() {
-        let last_row = Arc::make_mut(&mut last_placed.row);
-        replace_last_glyph_with_overflow_character(fonts, pixels_per_point, &job, last_row);
-        if let Some(last) = last_row.glyphs.last() {
-            last_row.size.x = last.max_x();
+    let mut elided = None;
+    if job.wrap.max_rows == 1
+        && job.wrap.elision != Elision::End
+        && let [paragraph] = paragraphs.as_mut_slice()
+    {
+        elided = elide_paragraph(fonts, pixels_per_point, point_scale, &job, paragraph);
+        paragraph.untabbed_glyphs.clear(); // There is only one row.
+    }
+
+    let mut elided_at_end = false;
+    let (mut rows, base_levels) = rows_from_paragraphs(
+        fonts,
+        pixels_per_point,
+        paragraphs,
+        &job,
+        &mut elided_at_end,
+    );
+    if elided_at_end {
+        let mut added_overflow_character = false;
+        if let Some(last_placed) = rows.last_mut() {
+            let last_row = Arc::make_mut(&mut last_placed.row);
+            let base_level = base_levels.last().copied().unwrap_or_default();
+            added_overflow_character = replace_last_glyph_with_overflow_character(
+                fonts,
+                pixels_per_point,
+                &job,
+                last_row,
+                base_level,
+            );
+            if let Some(last) = last_row.glyphs.last() {
+                last_row.size.x = last.max_x();
+            }
+        }
+
+        let num_chars_shown = rows
+            .iter()
+            .map(|row| row.char_count_including_newline())
+            .sum::<usize>()
+            - added_overflow_character as usize;
+        let start = job
+            .text
+            .char_indices()
+            .nth(num_chars_shown)
+            .map_or(job.text.len(), |(i, _)| i);
+        elided = Some(start..job.text.len());
+    }
+
+    if bidi.is_some() {
+        for (placed_row, base_level) in rows.iter_mut().zip(base_levels) {
+            reorder_row_visually(placed_row, base_level);
         }
     }
 
@@ -138,32 +280,76 @@ pub fn layout(fonts: &mut FontsImpl, pixels_per_point: f32, job: Arc<LayoutJob>)
         }
     }
 
+    // Outlines and shadows need images of their own in the font atlas:
+    let format_summary = format_summary(&job);
+    let row_halos: Vec<Vec<GlyphHalo>> = if format_summary.any_outline || format_summary.any_shadow
+    {
+        rows.iter()
+            .map(|placed_row| glyph_halos(fonts, &job, &placed_row.row))
+            .collect()
+    } else {
+        vec![]
+    };
+
     // Calculate the Y positions and tessellate the text:
-    galley_from_rows(point_scale, job, rows, elided, intrinsic_size)
+    galley_from_rows(
+        point_scale,
+        job,
+        &section_decorations,
+        &row_halos,
+        rows,
+        elided,
+        intrinsic_size,
+    )
 }
 
 // Ignores the Y coordinate.
+///
+/// Returns where the font of the section puts its decorations (underline etc).
 fn layout_section(
     fonts: &mut FontsImpl,
     pixels_per_point: f32,
     job: &LayoutJob,
+    bidi: Option<&BidiLevels>,
     section_index: u32,
     section: &LayoutSection,
     out_paragraphs: &mut Vec<Paragraph>,
-) {
+) -> DecorationMetrics {
     let LayoutSection {
         leading_space,
         byte_range,
         format,
+        placeholder,
+        link: _,
     } = section;
-    let mut font = fonts.font(&format.font_id.family);
+    let language = format.language.or_else(|| fonts.options().language);
+    let mut font = fonts.font_for_layout(&format.font_id.family, language, job.glyph_rendering);
     let font_size = format.font_id.size;
     let font_metrics = font.scaled_metrics(pixels_per_point, font_size);
     let line_height = section
         .format
         .line_height
         .unwrap_or(font_metrics.row_height);
-    let extra_letter_spacing = section.format.extra_letter_spacing;
+    let vertical = job.writing_mode == WritingMode::VerticalRightToLeft;
+    let section_metrics = SectionMetrics {
+        pixels_per_point,
+        font_size,
+        font_metrics,
+        line_height,
+        extra_letter_spacing_px: section.format.extra_letter_spacing * pixels_per_point,
+        font_features: &section.format.font_features,
+        upright_font_features: if vertical {
+            [FontFeature::enable(*b"vert"), FontFeature::enable(*b"vrt2")]
+                .into_iter()
+                .chain(section.format.font_features.iter().copied())
+                .collect()
+        } else {
+            vec![]
+        },
+        variations: format.font_id.variations,
+        language,
+        section_index,
+    };
 
     let mut paragraph = out_paragraphs.last_mut().unwrap();
     if paragraph.glyphs.is_empty() {
@@ -172,69 +358,464 @@ fn layout_section(
 
     paragraph.cursor_x_px += leading_space * pixels_per_point;
 
+    if let Some(placeholder) = placeholder {
+        for (byte_offset, chr) in job.text[byte_range.clone()].char_indices() {
+            let bidi_level = BidiLevels::level_at(bidi, byte_range.start + byte_offset);
+            paragraph.cursor_x_px = paragraph.cursor_x_px.round(); // Start on a whole pixel
+            paragraph.push_glyph(
+                placeholder_glyph(
+                    &section_metrics,
+                    placeholder,
+                    chr,
+                    paragraph.cursor_x_px,
+                    bidi_level,
+                ),
+                false,
+            );
+            paragraph.cursor_x_px += placeholder.size.x * pixels_per_point;
+        }
+        return font_metrics.decoration;
+    }
+
     let mut last_glyph_id = None;
 
     // Optimization: only recompute `ScaledMetrics` when the concrete `FontImpl` changes.
     let mut current_font = FontFaceKey::INVALID;
     let mut current_font_face_metrics = ScaledMetrics::default();
 
-    for chr in job.text[byte_range.clone()].chars() {
+    // Characters waiting to be shaped together:
+    let mut run = ShapingRun {
+        font_face: FontFaceKey::INVALID,
+        bidi_level: 0,
+        upright: false,
+        chars: vec![],
+    };
+
+    for (byte_offset, chr) in job.text[byte_range.clone()].char_indices() {
+        let byte_index = byte_range.start + byte_offset;
+
         if job.break_on_newline && chr == '\n' {
-            out_paragraphs.push(Paragraph::from_section_index(section_index));
+            run.shape(&mut font, &section_metrics, &mut last_glyph_id, paragraph);
+            out_paragraphs.push(Paragraph::from_section_index(
+                section_index,
+                BidiLevels::paragraph_level_at(bidi, byte_index + 1),
+            ));
             paragraph = out_paragraphs.last_mut().unwrap();
             paragraph.empty_paragraph_height = line_height; // TODO(emilk): replace this hack with actually including `\n` in the glyphs?
+            continue;
+        }
+
+        let bidi_level = BidiLevels::level_at(bidi, byte_index);
+        let (font_id, glyph_info) = font.glyph_info(chr);
+
+        // Tabs, thin spaces and missing glyphs have special widths,
+        // so we lay them out one by one below.
+        let shape = glyph_info.id.is_some()
+            && (font_id, glyph_info) != font.cached_family.replacement_glyph
+            && !matches!(chr, '\t' | '\u{2009}');
+        // Zero-width joiners etc. affect the shaping of the run they are in:
+        let joins_run = invisible_char(chr) && chr != '\r' && run.bidi_level == bidi_level;
+
+        if joins_run && !run.chars.is_empty() {
+            run.chars.push(chr);
+            continue;
+        }
+
+        let upright = vertical && is_upright_in_vertical_text(chr);
+
+        if !run.chars.is_empty()
+            && (!shape
+                || run.font_face != font_id
+                || run.bidi_level != bidi_level
+                || run.upright != upright)
+        {
+            run.shape(&mut font, &section_metrics, &mut last_glyph_id, paragraph);
+        }
+
+        if shape {
+            if run.chars.is_empty() {
+                run.font_face = font_id;
+                run.bidi_level = bidi_level;
+                run.upright = upright;
+            }
+            run.chars.push(chr);
+            continue;
+        }
+
+        // The advance width depends on the axes of a variable font:
+        let (font_id, glyph_info) =
+            font.glyph_info_with_variations(chr, section_metrics.variations);
+        let mut font_face = font.fonts_by_id.get_mut(&font_id);
+        if current_font != font_id {
+            current_font = font_id;
+            current_font_face_metrics = font_face
+                .as_ref()
+                .map(|font_face| {
+                    font_face
+                        .scaled_metrics(pixels_per_point, font_size)
+                        .with_variations(section_metrics.variations)
+                })
+                .unwrap_or_default();
+        }
+
+        if let (Some(font_face), Some(last_glyph_id), Some(glyph_id)) =
+            (&font_face, last_glyph_id, glyph_info.id)
+        {
+            paragraph.cursor_x_px +=
+                font_face.pair_kerning_pixels(&current_font_face_metrics, last_glyph_id, glyph_id);
+
+            // Only apply extra_letter_spacing to glyphs after the first one:
+            paragraph.cursor_x_px += section_metrics.extra_letter_spacing_px;
+        }
+
+        let (glyph_alloc, physical_x) = if let Some(font_face) = font_face.as_mut() {
+            font_face.allocate_glyph(
+                font.atlas,
+                &current_font_face_metrics,
+                glyph_info,
+                chr,
+                paragraph.cursor_x_px,
+            )
+        } else {
+            Default::default()
+        };
+
+        let glyph = section_metrics.glyph(
+            chr,
+            physical_x as f32 / pixels_per_point,
+            glyph_alloc.advance_width_px / pixels_per_point,
+            font_id,
+            &current_font_face_metrics,
+            glyph_alloc.uv_rect,
+            bidi_level,
+        );
+        paragraph.push_glyph(glyph, false);
+
+        paragraph.cursor_x_px += glyph_alloc.advance_width_px;
+        last_glyph_id = Some(glyph_alloc.id);
+    }
+
+    run.shape(&mut font, &section_metrics, &mut last_glyph_id, paragraph);
+
+    font_metrics.decoration
+}
+
+/// A [`Glyph`] that only reserves space for a [`Placeholder`].
+fn placeholder_glyph(
+    section: &SectionMetrics<'_>,
+    placeholder: &Placeholder,
+    chr: char,
+    x_px: f32,
+    bidi_level: u8,
+) -> Glyph {
+    // Leave room for the descent of the text, so that a placeholder standing on the baseline
+    // ends up at the same baseline as the text next to it:
+    let descent = (section.font_metrics.row_height - section.font_metrics.ascent).at_least(0.0);
+    let below_baseline = (placeholder.size.y - placeholder.baseline).at_least(descent);
+    Glyph {
+        chr,
+        pos: pos2(x_px / section.pixels_per_point, f32::NAN),
+        advance_width: placeholder.size.x,
+        line_height: placeholder.baseline + below_baseline,
+        font_face_height: placeholder.size.y,
+        font_face_ascent: placeholder.baseline,
+        font_height: placeholder.size.y,
+        font_ascent: placeholder.baseline,
+        continues_cluster: false,
+        uv_rect: UvRect::default(),
+        section_index: section.section_index,
+        first_vertex: 0, // filled in later
+        bidi_level,
+        font_face: FontFaceKey::PLACEHOLDER,
+    }
+}
+
+/// Move the text after each `\t` to its tab stop (see [`LayoutJob::tab_stops`]).
+///
+/// The tab stops are measured from `origin_x`, the start of the row.
+/// Ignores the Y coordinate.
+fn apply_tab_stops(point_scale: PointScale, job: &LayoutJob, glyphs: &mut [Glyph], origin_x: f32) {
+    let next_tab = |glyphs: &[Glyph], start: usize| {
+        glyphs[start..]
+            .iter()
+            .position(|glyph| glyph.chr == '\t')
+            .map(|i| start + i)
+    };
+
+    let mut tab_index = next_tab(glyphs, 0);
+    while let Some(tab_idx) = tab_index {
+        let text_start = tab_idx + 1;
+        tab_index = next_tab(glyphs, text_start);
+        let text_end = tab_index.unwrap_or(glyphs.len());
+
+        // The text between this tab and the next one:
+        let tab = glyphs[tab_idx];
+        let text = &glyphs[text_start..text_end];
+        let text_x = text.first().map_or(tab.max_x(), |glyph| glyph.pos.x);
+        let text_width = text.last().map_or(0.0, |glyph| glyph.max_x() - text_x);
+
+        let font_size = job.sections[tab.section_index as usize].format.font_id.size;
+        let tab_stops = &job.tab_stops;
+        let tab_x = tab.pos.x - origin_x;
+        let stop = tab_stops
+            .stops()
+            .iter()
+            .filter(|stop| tab_x < stop.offset)
+            .min_by(|a, b| a.offset.total_cmp(&b.offset))
+            .copied()
+            .or_else(|| {
+                let interval = tab_stops.interval_em? * font_size;
+                (0.0 < interval).then(|| TabStop {
+                    offset: ((tab_x / interval).floor() + 1.0) * interval,
+                    align: TabAlign::Left,
+                })
+            });
+
+        let new_text_x = if let Some(TabStop { offset, align }) = stop {
+            let offset = origin_x + offset;
+            let anchor_x = match align {
+                TabAlign::Left => 0.0,
+                TabAlign::Right => text_width,
+                TabAlign::Center => text_width / 2.0,
+                TabAlign::Decimal(separator) => text
+                    .iter()
+                    .find(|glyph| glyph.chr == separator)
+                    .map_or(text_width, |glyph| glyph.pos.x - text_x),
+            };
+            (offset - anchor_x).at_least(tab.pos.x)
+        } else {
+            text_x // The tab keeps its default width.
+        };
+
+        // Move the rest of the paragraph by whole pixels, to keep the glyphs sharp:
+        let shift = point_scale.round_to_pixel(new_text_x - text_x);
+        for glyph in &mut glyphs[text_start..] {
+            glyph.pos.x += shift;
+        }
+        glyphs[tab_idx].advance_width = (text_x + shift - tab.pos.x).at_least(0.0);
+    }
+}
+
+/// What we need to know about a [`LayoutSection`] to create its glyphs.
+struct SectionMetrics<'a> {
+    pixels_per_point: f32,
+    font_size: f32,
+    font_metrics: ScaledMetrics,
+    line_height: f32,
+    extra_letter_spacing_px: f32,
+    font_features: &'a [FontFeature],
+
+    /// The [`Self::font_features`] of upright text in vertical text, with the vertical forms turned on.
+    upright_font_features: Vec<FontFeature>,
+
+    variations: FontVariations,
+    language: Option<Language>,
+    section_index: u32,
+}
+
+impl SectionMetrics<'_> {
+    #[expect(clippy::too_many_arguments)]
+    fn glyph(
+        &self,
+        chr: char,
+        x: f32,
+        advance_width: f32,
+        font_face: FontFaceKey,
+        font_face_metrics: &ScaledMetrics,
+        uv_rect: UvRect,
+        bidi_level: u8,
+    ) -> Glyph {
+        Glyph {
+            chr,
+            pos: pos2(x, f32::NAN),
+            advance_width,
+            line_height: self.line_height,
+            font_face_height: font_face_metrics.row_height,
+            font_face_ascent: font_face_metrics.ascent,
+            font_height: self.font_metrics.row_height,
+            font_ascent: self.font_metrics.ascent,
+            continues_cluster: false,
+            uv_rect,
+            section_index: self.section_index,
+            first_vertex: 0, // filled in later
+            bidi_level,
+            font_face,
+        }
+    }
+}
+
+/// A run of characters in the same section, font face and direction,
+/// that are shaped together.
+struct ShapingRun {
+    font_face: FontFaceKey,
+    bidi_level: u8,
+
+    /// Is this upright text in vertical text? See [`is_upright`].
+    upright: bool,
+
+    chars: Vec<char>,
+}
+
+impl ShapingRun {
+    /// Shape the characters of the run and add them to the paragraph, leaving the run empty.
+    ///
+    /// We still produce one [`Glyph`] per `char`:
+    /// when several characters are shaped into one cluster, each char gets the advance
+    /// of its shaped glyph if there is one glyph per char (e.g. a letter with a combining mark),
+    /// otherwise (e.g. a ligature) they share the width of the cluster evenly.
+    /// The shaped glyphs are attached to the first characters of the cluster,
+    /// with any surplus glyphs in [`Paragraph::extra_glyphs`].
+    fn shape(
+        &mut self,
+        font: &mut Font<'_>,
+        section: &SectionMetrics<'_>,
+        last_glyph_id: &mut Option<skrifa::GlyphId>,
+        paragraph: &mut Paragraph,
+    ) {
+        if self.chars.is_empty() {
+            return;
+        }
+        let chars = std::mem::take(&mut self.chars);
+
+        let Some(font_face) = font.fonts_by_id.get_mut(&self.font_face) else {
+            return;
+        };
+        let ppp = section.pixels_per_point;
+        let metrics = font_face
+            .scaled_metrics(ppp, section.font_size)
+            .with_variations(section.variations);
+        let scale = metrics.px_scale_factor;
+        let rtl = self.bidi_level % 2 == 1;
+
+        let font_features = if self.upright {
+            &section.upright_font_features
         } else {
-            let (font_id, glyph_info) = font.glyph_info(chr);
-            let mut font_face = font.fonts_by_id.get_mut(&font_id);
-            if current_font != font_id {
-                current_font = font_id;
-                current_font_face_metrics = font_face
-                    .as_ref()
-                    .map(|font_face| font_face.scaled_metrics(pixels_per_point, font_size))
-                    .unwrap_or_default();
+            section.font_features
+        };
+        let shaped = font_face.shape(
+            &chars,
+            rtl,
+            font_features,
+            section.variations,
+            section.language,
+        );
+        let infos = shaped.glyph_infos();
+        let positions = shaped.glyph_positions();
+
+        // Group the glyphs into clusters: (first char, glyphs in visual order)
+        let mut clusters: Vec<(usize, std::ops::Range<usize>)> = vec![];
+        for (i, info) in infos.iter().enumerate() {
+            let cluster = info.cluster as usize;
+            match clusters.last_mut() {
+                Some((last_cluster, glyphs)) if *last_cluster == cluster => glyphs.end = i + 1,
+                _ => clusters.push((cluster, i..i + 1)),
             }
+        }
+        if rtl {
+            clusters.reverse(); // logical order
+        }
 
-            if let (Some(font_face), Some(last_glyph_id), Some(glyph_id)) =
-                (&font_face, last_glyph_id, glyph_info.id)
-            {
-                paragraph.cursor_x_px += font_face.pair_kerning_pixels(
-                    &current_font_face_metrics,
-                    last_glyph_id,
-                    glyph_id,
-                );
+        for (cluster_index, (first_char, glyphs)) in clusters.iter().enumerate() {
+            let first_char = if cluster_index == 0 { 0 } else { *first_char };
+            let end_char = clusters
+                .get(cluster_index + 1)
+                .map_or(chars.len(), |(next, _)| *next);
+            let cluster_chars = &chars[first_char..end_char.max(first_char + 1)];
+            let num_chars = cluster_chars.len();
 
+            if last_glyph_id.is_some() {
                 // Only apply extra_letter_spacing to glyphs after the first one:
-                paragraph.cursor_x_px += extra_letter_spacing * pixels_per_point;
+                paragraph.cursor_x_px += section.extra_letter_spacing_px;
             }
+            *last_glyph_id = Some(skrifa::GlyphId::new(infos[glyphs.start].glyph_id));
 
-            let (glyph_alloc, physical_x) = if let Some(font_face) = font_face.as_mut() {
-                font_face.allocate_glyph(
+            let cluster_left_px = paragraph.cursor_x_px;
+            let advances_px: Vec<f32> = positions[glyphs.clone()]
+                .iter()
+                .map(|pos| pos.x_advance as f32 * scale)
+                .collect();
+            let cluster_width_px = advances_px.iter().sum::<f32>();
+
+            // The width of each visual slot of the cluster:
+            let slot_widths_px = if advances_px.len() == num_chars {
+                advances_px
+            } else {
+                vec![cluster_width_px / num_chars as f32; num_chars]
+            };
+
+            // Allocate the glyphs, left-to-right:
+            let mut pieces = vec![];
+            let mut pen_px = cluster_left_px;
+            for (info, pos) in infos[glyphs.clone()].iter().zip(&positions[glyphs.clone()]) {
+                let h_pos = pen_px + pos.x_offset as f32 * scale;
+                pen_px += pos.x_advance as f32 * scale;
+                let glyph_info = GlyphInfo {
+                    id: Some(skrifa::GlyphId::new(info.glyph_id))
+                        .filter(|id| *id != skrifa::GlyphId::NOTDEF),
+                    advance_width_unscaled: (pos.x_advance as f32).into(),
+                };
+                let (alloc, h_pos_round) = font_face.allocate_glyph(
                     font.atlas,
-                    &current_font_face_metrics,
+                    &metrics,
                     glyph_info,
-                    chr,
-                    paragraph.cursor_x_px,
-                )
-            } else {
-                Default::default()
+                    cluster_chars[0],
+                    h_pos,
+                );
+                let mut uv_rect = alloc.uv_rect;
+                uv_rect.offset.y -= pos.y_offset as f32 * scale / ppp;
+                pieces.push((h_pos, h_pos_round, uv_rect));
+            }
+
+            // Keep the position of simple glyphs exactly on the pixel grid that the rasterization assumes:
+            let pos_shift_px = pieces
+                .first()
+                .map_or(0.0, |(h_pos, h_pos_round, _)| *h_pos_round as f32 - *h_pos);
+
+            // The position of the given visual slot of the cluster:
+            let slot_x = |slot: usize| {
+                let offset_px = slot_widths_px[..slot].iter().sum::<f32>();
+                (cluster_left_px + pos_shift_px + offset_px) / ppp
             };
+            // Which char is shown in a visual slot (and vice versa)?
+            let char_in_slot = |slot: usize| if rtl { num_chars - 1 - slot } else { slot };
+
+            let first_glyph_index = paragraph.glyphs.len();
+            // Laid out in logical order here, and reordered later (see `reorder_row_visually`):
+            let mut logical_x = (cluster_left_px + pos_shift_px) / ppp;
+            for (i, &chr) in cluster_chars.iter().enumerate() {
+                let width = slot_widths_px[char_in_slot(i)] / ppp;
+                let glyph = section.glyph(
+                    chr,
+                    logical_x,
+                    width,
+                    self.font_face,
+                    &metrics,
+                    UvRect::default(),
+                    self.bidi_level,
+                );
+                paragraph.push_glyph(glyph, 0 < i);
+                logical_x += width;
+            }
 
-            paragraph.glyphs.push(Glyph {
-                chr,
-                pos: pos2(physical_x as f32 / pixels_per_point, f32::NAN),
-                advance_width: glyph_alloc.advance_width_px / pixels_per_point,
-                line_height,
-                font_face_height: current_font_face_metrics.row_height,
-                font_face_ascent: current_font_face_metrics.ascent,
-                font_height: font_metrics.row_height,
-                font_ascent: font_metrics.ascent,
-                uv_rect: glyph_alloc.uv_rect,
-                section_index,
-                first_vertex: 0, // filled in later
-            });
+            for (piece_index, (_, h_pos_round, mut uv_rect)) in pieces.into_iter().enumerate() {
+                if uv_rect.is_nothing() {
+                    continue;
+                }
+                let slot = piece_index.min(num_chars - 1);
+                uv_rect.offset.x += h_pos_round as f32 / ppp - slot_x(slot);
+                let glyph_index = first_glyph_index + char_in_slot(slot);
+                let glyph = &mut paragraph.glyphs[glyph_index];
+                if piece_index < num_chars && glyph.uv_rect.is_nothing() {
+                    glyph.uv_rect = uv_rect;
+                } else {
+                    paragraph.extra_glyphs.push(ExtraGlyph {
+                        glyph_index: glyph_index as u32,
+                        uv_rect,
+                    });
+                }
+            }
 
-            paragraph.cursor_x_px += glyph_alloc.advance_width_px;
-            last_glyph_id = Some(glyph_alloc.id);
+            paragraph.cursor_x_px += cluster_width_px;
         }
     }
 }
@@ -272,16 +853,21 @@ fn calculate_intrinsic_size(
 }
 
 // Ignores the Y coordinate.
+//
+// Also returns the bidi paragraph level of each row.
 fn rows_from_paragraphs(
+    fonts: &mut FontsImpl,
+    pixels_per_point: f32,
     paragraphs: Vec<Paragraph>,
     job: &LayoutJob,
     elided: &mut bool,
-) -> Vec<PlacedRow> {
+) -> (Vec<PlacedRow>, Vec<u8>) {
     let num_paragraphs = paragraphs.len();
 
     let mut rows = vec![];
+    let mut base_levels = vec![];
 
-    for (i, paragraph) in paragraphs.into_iter().enumerate() {
+    for (i, mut paragraph) in paragraphs.into_iter().enumerate() {
         if job.wrap.max_rows <= rows.len() {
             *elided = true;
             break;
@@ -295,6 +881,7 @@ fn rows_from_paragraphs(
                 row: Arc::new(Row {
                     section_index_at_start: paragraph.section_index_at_start,
                     glyphs: vec![],
+                    extra_glyphs: vec![],
                     visuals: Default::default(),
                     size: vec2(0.0, paragraph.empty_paragraph_height),
                 }),
@@ -309,24 +896,36 @@ fn rows_from_paragraphs(
                     row: Arc::new(Row {
                         section_index_at_start: paragraph.section_index_at_start,
                         glyphs: paragraph.glyphs,
+                        extra_glyphs: paragraph.extra_glyphs,
                         visuals: Default::default(),
                         size: vec2(paragraph_max_x, 0.0),
                     }),
                     ends_with_newline: !is_last_paragraph,
                 });
             } else {
-                line_break(&paragraph, job, &mut rows, elided);
+                line_break(
+                    fonts,
+                    pixels_per_point,
+                    &mut paragraph,
+                    job,
+                    &mut rows,
+                    elided,
+                );
                 let placed_row = rows.last_mut().unwrap();
                 placed_row.ends_with_newline = !is_last_paragraph;
             }
         }
+
+        base_levels.resize(rows.len(), paragraph.base_level);
     }
 
-    rows
+    (rows, base_levels)
 }
 
 fn line_break(
-    paragraph: &Paragraph,
+    fonts: &mut FontsImpl,
+    pixels_per_point: f32,
+    paragraph: &mut Paragraph,
     job: &LayoutJob,
     out_rows: &mut Vec<PlacedRow>,
     elided: &mut bool,
@@ -336,6 +935,15 @@ fn line_break(
     // Keeps track of good places to insert row break if we exceed `wrap_width`.
     let mut row_break_candidates = RowBreakCandidates::default();
 
+    let line_breaks = line_break_opportunities(&paragraph.glyphs);
+    let hyphenation_points = if job.wrap.hyphenate {
+        hyphenation_points(fonts, job, &paragraph.glyphs)
+    } else {
+        vec![]
+    };
+    // The width of the hyphen in each section, looked up the first time we need it:
+    let mut hyphen_widths: Vec<Option<f32>> = vec![None; job.sections.len()];
+
     let mut first_row_indentation = paragraph.glyphs[0].pos.x;
     let mut row_start_x = 0.0;
     let mut row_start_idx = 0;
@@ -361,6 +969,7 @@ fn line_break(
                     row: Arc::new(Row {
                         section_index_at_start: paragraph.section_index_at_start,
                         glyphs: vec![],
+                        extra_glyphs: vec![],
                         visuals: Default::default(),
                         size: Vec2::ZERO,
                     }),
@@ -370,7 +979,7 @@ fn line_break(
                 first_row_indentation = 0.0;
             } else if let Some(last_kept_index) = row_break_candidates.get(job.wrap.break_anywhere)
             {
-                let glyphs: Vec<Glyph> = paragraph.glyphs[row_start_idx..=last_kept_index]
+                let mut glyphs: Vec<Glyph> = paragraph.glyphs[row_start_idx..=last_kept_index]
                     .iter()
                     .copied()
                     .map(|mut glyph| {
@@ -378,6 +987,27 @@ fn line_break(
                         glyph
                     })
                     .collect();
+                let mut extra_glyphs =
+                    paragraph.extra_glyphs_in(row_start_idx..last_kept_index + 1);
+
+                if row_break_candidates.hyphen == Some(last_kept_index) {
+                    // Show a hyphen after the last glyph:
+                    let last_index = glyphs.len() - 1;
+                    let last_glyph = &mut glyphs[last_index];
+                    let (hyphen_width, mut uv_rect) = hyphen_glyph(
+                        fonts,
+                        pixels_per_point,
+                        job,
+                        last_glyph.section_index,
+                        paragraph.glyphs[last_kept_index].max_x(),
+                    );
+                    uv_rect.offset.x += last_glyph.advance_width;
+                    last_glyph.advance_width += hyphen_width;
+                    extra_glyphs.push(ExtraGlyph {
+                        glyph_index: last_index as u32,
+                        uv_rect,
+                    });
+                }
 
                 let section_index_at_start = glyphs[0].section_index;
                 let paragraph_max_x = glyphs.last().unwrap().max_x();
@@ -387,6 +1017,7 @@ fn line_break(
                     row: Arc::new(Row {
                         section_index_at_start,
                         glyphs,
+                        extra_glyphs,
                         visuals: Default::default(),
                         size: vec2(paragraph_max_x, 0.0),
                     }),
@@ -395,6 +1026,13 @@ fn line_break(
 
                 // Start a new row:
                 row_start_idx = last_kept_index + 1;
+                if !paragraph.untabbed_glyphs.is_empty() {
+                    // The tab stops are measured from the start of each row:
+                    let glyphs = &mut paragraph.glyphs[row_start_idx..];
+                    glyphs.copy_from_slice(&paragraph.untabbed_glyphs[row_start_idx..]);
+                    let origin_x = glyphs[0].pos.x;
+                    apply_tab_stops(PointScale::new(pixels_per_point), job, glyphs, origin_x);
+                }
                 row_start_x = paragraph.glyphs[row_start_idx].pos.x;
                 row_break_candidates.forget_before_idx(row_start_idx);
             } else {
@@ -402,7 +1040,27 @@ fn line_break(
             }
         }
 
-        row_break_candidates.add(i, &paragraph.glyphs[i..]);
+        if paragraph.is_cluster_start(i + 1) {
+            let glyph = &paragraph.glyphs[i];
+            let kind = if (glyph.chr == SOFT_HYPHEN || hyphenation_points.get(i) == Some(&true))
+                && glyph.bidi_level % 2 == 0
+            {
+                // Only a candidate if there is room for the hyphen:
+                let hyphen_width =
+                    *hyphen_widths[glyph.section_index as usize].get_or_insert_with(|| {
+                        hyphen_glyph(fonts, pixels_per_point, job, glyph.section_index, 0.0).0
+                    });
+                (glyph.max_x() + hyphen_width - row_start_x <= wrap_width)
+                    .then_some(RowBreakKind::Hyphen)
+            } else if line_breaks.get(i) == Some(&true) {
+                Some(RowBreakKind::LineBreak)
+            } else if glyph.chr.is_ascii_punctuation() {
+                Some(RowBreakKind::Punctuation)
+            } else {
+                None
+            };
+            row_break_candidates.add(i, kind);
+        }
     }
 
     if row_start_idx < paragraph.glyphs.len() {
@@ -429,6 +1087,7 @@ fn line_break(
                 row: Arc::new(Row {
                     section_index_at_start,
                     glyphs,
+                    extra_glyphs: paragraph.extra_glyphs_in(row_start_idx..paragraph.glyphs.len()),
                     visuals: Default::default(),
                     size: vec2(paragraph_max_x - paragraph_min_x, 0.0),
                 }),
@@ -438,17 +1097,142 @@ fn line_break(
     }
 }
 
+/// Leave out the start or the middle of a paragraph that doesn't fit on a single row,
+/// according to [`crate::text::TextWrapping::elision`].
+///
+/// Returns the byte range of the text that was left out.
+/// Returns `None` if nothing was, and the paragraph should be elided at the end as usual.
+fn elide_paragraph(
+    fonts: &mut FontsImpl,
+    pixels_per_point: f32,
+    point_scale: PointScale,
+    job: &LayoutJob,
+    paragraph: &mut Paragraph,
+) -> Option<Range<usize>> {
+    let wrap_width = job.effective_wrap_width();
+    let glyphs = &paragraph.glyphs;
+    let last_glyph = glyphs.last()?;
+    let num_glyphs = glyphs.len();
+    if last_glyph.max_x() <= wrap_width || job.text.chars().count() != num_glyphs {
+        return None;
+    }
+
+    let section_index = glyphs[0].section_index;
+    let overflow_glyph_at = |fonts: &mut FontsImpl, x: f32| {
+        job.wrap.overflow_character.map(|overflow_character| {
+            overflow_glyph(
+                fonts,
+                pixels_per_point,
+                job,
+                overflow_character,
+                section_index,
+                x,
+                paragraph.base_level,
+            )
+        })
+    };
+    let overflow_width = overflow_glyph_at(fonts, 0.0).map_or(0.0, |glyph| glyph.advance_width);
+
+    // Does the row fit if we leave out `glyphs[a..b]`?
+    let fits = |a: usize, b: usize| {
+        let end_of_overflow = glyphs[a].pos.x + overflow_width;
+        match glyphs.get(b) {
+            Some(first_kept) => {
+                let shift = point_scale.round_to_pixel(end_of_overflow - first_kept.pos.x);
+                last_glyph.max_x() + shift <= wrap_width
+            }
+            None => end_of_overflow <= wrap_width,
+        }
+    };
+    // We never cut a shaping cluster in two:
+    let is_boundary = |i: usize| i == num_glyphs || paragraph.is_cluster_start(i);
+    let first_fitting_end = |a: usize| (a + 1..=num_glyphs).find(|&b| is_boundary(b) && fits(a, b));
+    let middle = || {
+        // Give half of the width to the start of the text, and the rest to the end:
+        let start_x = glyphs[0].pos.x;
+        let half_width = 0.5 * (wrap_width - start_x - overflow_width);
+        let a = (0..num_glyphs)
+            .rev()
+            .find(|&a| is_boundary(a) && glyphs[a].pos.x - start_x <= half_width)?;
+        Some((a, first_fitting_end(a)?))
+    };
+
+    let (a, b) = match job.wrap.elision {
+        Elision::End => None,
+        Elision::Start => first_fitting_end(0).map(|b| (0, b)),
+        Elision::Middle => middle(),
+        Elision::Path => glyphs
+            .iter()
+            .rposition(|glyph| matches!(glyph.chr, '/' | '\\'))
+            .filter(|&separator| 0 < separator && is_boundary(separator))
+            .and_then(|separator| {
+                // Keep the separator and the file name, and as much as possible before it:
+                let a = (0..separator)
+                    .rev()
+                    .find(|&a| is_boundary(a) && fits(a, separator))?;
+                Some((a, separator))
+            })
+            .or_else(middle),
+    }?;
+
+    let mut new_glyphs = glyphs[..a].to_vec();
+    let mut x = glyphs[a].pos.x;
+    if let Some(overflow_glyph) = overflow_glyph_at(fonts, x) {
+        x = overflow_glyph.max_x();
+        new_glyphs.push(overflow_glyph);
+    }
+    let first_kept_index = new_glyphs.len();
+    if let Some(first_kept) = glyphs.get(b) {
+        let shift = point_scale.round_to_pixel(x - first_kept.pos.x);
+        new_glyphs.extend(glyphs[b..].iter().map(|glyph| Glyph {
+            pos: pos2(glyph.pos.x + shift, glyph.pos.y),
+            ..*glyph
+        }));
+    }
+
+    paragraph.extra_glyphs = paragraph
+        .extra_glyphs
+        .iter()
+        .filter_map(|extra| {
+            let index = extra.glyph_index as usize;
+            let new_index = if index < a {
+                index
+            } else if b <= index {
+                index - b + first_kept_index
+            } else {
+                return None; // Elided
+            };
+            Some(ExtraGlyph {
+                glyph_index: new_index as u32,
+                ..*extra
+            })
+        })
+        .collect();
+    paragraph.glyphs = new_glyphs;
+
+    let byte_offset = |char_index: usize| {
+        job.text
+            .char_indices()
+            .nth(char_index)
+            .map_or(job.text.len(), |(i, _)| i)
+    };
+    Some(byte_offset(a)..byte_offset(b))
+}
+
 /// Trims the last glyphs in the row and replaces it with an overflow character (e.g. `…`).
 ///
+/// Returns `false` if there is no [`crate::text::TextWrapping::overflow_character`].
+///
 /// Called before we have any Y coordinates.
 fn replace_last_glyph_with_overflow_character(
     fonts: &mut FontsImpl,
     pixels_per_point: f32,
     job: &LayoutJob,
     row: &mut Row,
-) {
+    base_level: u8,
+) -> bool {
     let Some(overflow_character) = job.wrap.overflow_character else {
-        return;
+        return false;
     };
 
     let mut section_index = row
@@ -459,14 +1243,22 @@ fn replace_last_glyph_with_overflow_character(
     loop {
         let section = &job.sections[section_index as usize];
         let extra_letter_spacing = section.format.extra_letter_spacing;
-        let mut font = fonts.font(&section.format.font_id.family);
+        let language = section.format.language.or_else(|| fonts.options().language);
+        let mut font = fonts.font_for_layout(
+            &section.format.font_id.family,
+            language,
+            job.glyph_rendering,
+        );
         let font_size = section.format.font_id.size;
 
-        let (font_id, glyph_info) = font.glyph_info(overflow_character);
+        let (font_id, _) = font.glyph_info(overflow_character);
         let mut font_face = font.fonts_by_id.get_mut(&font_id);
         let font_face_metrics = font_face
             .as_mut()
-            .map(|f| f.scaled_metrics(pixels_per_point, font_size))
+            .map(|f| {
+                f.scaled_metrics(pixels_per_point, font_size)
+                    .with_variations(section.format.font_id.variations)
+            })
             .unwrap_or_default();
 
         let overflow_glyph_x = if let Some(prev_glyph) = row.glyphs.last() {
@@ -475,8 +1267,12 @@ fn replace_last_glyph_with_overflow_character(
                 .as_mut()
                 .map(|font_face| {
                     if let (Some(prev_glyph_id), Some(overflow_glyph_id)) = (
-                        font_face.glyph_info(prev_glyph.chr).and_then(|g| g.id),
-                        font_face.glyph_info(overflow_character).and_then(|g| g.id),
+                        font_face
+                            .glyph_info(prev_glyph.chr, FontVariations::DEFAULT)
+                            .and_then(|g| g.id),
+                        font_face
+                            .glyph_info(overflow_character, FontVariations::DEFAULT)
+                            .and_then(|g| g.id),
                     ) {
                         font_face.pair_kerning(&font_face_metrics, prev_glyph_id, overflow_glyph_id)
                     } else {
@@ -492,7 +1288,7 @@ fn replace_last_glyph_with_overflow_character(
 
         let replacement_glyph_width = font_face
             .as_mut()
-            .and_then(|f| f.glyph_info(overflow_character))
+            .and_then(|f| f.glyph_info(overflow_character, section.format.font_id.variations))
             .map(|i| i.advance_width_unscaled.0 * font_face_metrics.px_scale_factor)
             .unwrap_or_default();
 
@@ -502,39 +1298,20 @@ fn replace_last_glyph_with_overflow_character(
         {
             // we are done
 
-            let (replacement_glyph_alloc, physical_x) = font_face
-                .as_mut()
-                .map(|f| {
-                    f.allocate_glyph(
-                        font.atlas,
-                        &font_face_metrics,
-                        glyph_info,
-                        overflow_character,
-                        overflow_glyph_x * pixels_per_point,
-                    )
-                })
-                .unwrap_or_default();
+            let num_glyphs = row.glyphs.len() as u32;
+            row.extra_glyphs
+                .retain(|extra| extra.glyph_index < num_glyphs);
 
-            let font_metrics = font.scaled_metrics(pixels_per_point, font_size);
-            let line_height = section
-                .format
-                .line_height
-                .unwrap_or(font_metrics.row_height);
-
-            row.glyphs.push(Glyph {
-                chr: overflow_character,
-                pos: pos2(physical_x as f32 / pixels_per_point, f32::NAN),
-                advance_width: replacement_glyph_alloc.advance_width_px / pixels_per_point,
-                line_height,
-                font_face_height: font_face_metrics.row_height,
-                font_face_ascent: font_face_metrics.ascent,
-                font_height: font_metrics.row_height,
-                font_ascent: font_metrics.ascent,
-                uv_rect: replacement_glyph_alloc.uv_rect,
+            row.glyphs.push(overflow_glyph(
+                fonts,
+                pixels_per_point,
+                job,
+                overflow_character,
                 section_index,
-                first_vertex: 0, // filled in later
-            });
-            return;
+                overflow_glyph_x,
+                base_level,
+            ));
+            return true;
         }
 
         // We didn't fit - pop the last glyph and try again.
@@ -546,6 +1323,141 @@ fn replace_last_glyph_with_overflow_character(
     }
 }
 
+/// The overflow character (e.g. `…`) at `x`, in the format of the given section.
+fn overflow_glyph(
+    fonts: &mut FontsImpl,
+    pixels_per_point: f32,
+    job: &LayoutJob,
+    overflow_character: char,
+    section_index: u32,
+    x: f32,
+    bidi_level: u8,
+) -> Glyph {
+    let section = &job.sections[section_index as usize];
+    let language = section.format.language.or_else(|| fonts.options().language);
+    let mut font = fonts.font_for_layout(
+        &section.format.font_id.family,
+        language,
+        job.glyph_rendering,
+    );
+    let font_size = section.format.font_id.size;
+
+    let (font_id, glyph_info) =
+        font.glyph_info_with_variations(overflow_character, section.format.font_id.variations);
+    let mut font_face = font.fonts_by_id.get_mut(&font_id);
+    let font_face_metrics = font_face
+        .as_mut()
+        .map(|f| {
+            f.scaled_metrics(pixels_per_point, font_size)
+                .with_variations(section.format.font_id.variations)
+        })
+        .unwrap_or_default();
+
+    let (replacement_glyph_alloc, physical_x) = font_face
+        .as_mut()
+        .map(|f| {
+            f.allocate_glyph(
+                font.atlas,
+                &font_face_metrics,
+                glyph_info,
+                overflow_character,
+                x * pixels_per_point,
+            )
+        })
+        .unwrap_or_default();
+
+    let font_metrics = font.scaled_metrics(pixels_per_point, font_size);
+    let line_height = section
+        .format
+        .line_height
+        .unwrap_or(font_metrics.row_height);
+
+    Glyph {
+        chr: overflow_character,
+        pos: pos2(physical_x as f32 / pixels_per_point, f32::NAN),
+        advance_width: replacement_glyph_alloc.advance_width_px / pixels_per_point,
+        line_height,
+        font_face_height: font_face_metrics.row_height,
+        font_face_ascent: font_face_metrics.ascent,
+        font_height: font_metrics.row_height,
+        font_ascent: font_metrics.ascent,
+        continues_cluster: false,
+        uv_rect: replacement_glyph_alloc.uv_rect,
+        section_index,
+        first_vertex: 0, // filled in later
+        bidi_level,
+        font_face: font_id,
+    }
+}
+
+/// Reorder the glyphs of a row with right-to-left text according to the
+/// Unicode Bidirectional Algorithm (rules L1 and L2), by changing their x positions.
+///
+/// The glyphs stay in logical order in [`Row::glyphs`].
+/// Called before we have any Y coordinates.
+fn reorder_row_visually(placed_row: &mut PlacedRow, base_level: u8) {
+    let row = Arc::make_mut(&mut placed_row.row);
+    let Some(first_glyph) = row.glyphs.first() else {
+        return;
+    };
+    let start_x = first_glyph.pos.x;
+
+    // L1: trailing whitespace goes to the paragraph level:
+    for glyph in row.glyphs.iter_mut().rev() {
+        if !glyph.chr.is_whitespace() {
+            break;
+        }
+        glyph.bidi_level = base_level;
+    }
+
+    if base_level == 0 && !row.has_rtl() {
+        return;
+    }
+
+    // The logical width of each glyph, including kerning and letter spacing:
+    let widths: Vec<f32> = row
+        .glyphs
+        .windows(2)
+        .map(|pair| pair[1].pos.x - pair[0].pos.x)
+        .chain(row.glyphs.last().map(|glyph| glyph.advance_width))
+        .collect();
+
+    // L2: reverse every run at each level, from the highest level down to the lowest odd level:
+    let levels: Vec<u8> = row.glyphs.iter().map(|g| g.bidi_level).collect();
+    let mut order: Vec<usize> = (0..row.glyphs.len()).collect();
+    let max_level = levels.iter().copied().max().unwrap_or_default();
+    let min_odd_level = levels.iter().map(|level| level | 1).min().unwrap_or(1);
+    for reverse_level in (min_odd_level..=max_level).rev() {
+        let mut i = 0;
+        while i < order.len() {
+            if levels[order[i]] < reverse_level {
+                i += 1;
+                continue;
+            }
+            let run_start = i;
+            while i < order.len() && reverse_level <= levels[order[i]] {
+                i += 1;
+            }
+            order[run_start..i].reverse();
+        }
+    }
+
+    let mut x = start_x;
+    for index in order {
+        row.glyphs[index].pos.x = x;
+        x += widths[index];
+    }
+}
+
+/// Indices into [`Row::glyphs`], sorted left-to-right.
+fn visual_order(row: &Row) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..row.glyphs.len()).collect();
+    if row.has_rtl() {
+        order.sort_by(|&a, &b| row.glyphs[a].pos.x.total_cmp(&row.glyphs[b].pos.x));
+    }
+    order
+}
+
 /// Horizontally aligned the text on a row.
 ///
 /// Ignores the Y coordinate.
@@ -556,29 +1468,27 @@ fn halign_and_justify_row(
     wrap_width: f32,
     justify: bool,
 ) {
-    #![expect(clippy::useless_let_if_seq)] // False positive
-
     let row = Arc::make_mut(&mut placed_row.row);
 
     if row.glyphs.is_empty() {
         return;
     }
 
-    let num_leading_spaces = row
-        .glyphs
+    let order = visual_order(row);
+
+    let num_leading_spaces = order
         .iter()
-        .take_while(|glyph| glyph.chr.is_whitespace())
+        .take_while(|&&i| row.glyphs[i].chr.is_whitespace())
         .count();
 
     let glyph_range = if num_leading_spaces == row.glyphs.len() {
         // There is only whitespace
         (0, row.glyphs.len())
     } else {
-        let num_trailing_spaces = row
-            .glyphs
+        let num_trailing_spaces = order
             .iter()
             .rev()
-            .take_while(|glyph| glyph.chr.is_whitespace())
+            .take_while(|&&i| row.glyphs[i].chr.is_whitespace())
             .count();
 
         (num_leading_spaces, row.glyphs.len() - num_trailing_spaces)
@@ -586,8 +1496,8 @@ fn halign_and_justify_row(
     let num_glyphs_in_range = glyph_range.1 - glyph_range.0;
     assert!(num_glyphs_in_range > 0, "Should have at least one glyph");
 
-    let original_min_x = row.glyphs[glyph_range.0].logical_rect().min.x;
-    let original_max_x = row.glyphs[glyph_range.1 - 1].logical_rect().max.x;
+    let original_min_x = row.glyphs[order[glyph_range.0]].logical_rect().min.x;
+    let original_max_x = row.glyphs[order[glyph_range.1 - 1]].logical_rect().max.x;
     let original_width = original_max_x - original_min_x;
 
     let target_width = if justify && num_glyphs_in_range > 1 {
@@ -602,40 +1512,36 @@ fn halign_and_justify_row(
         Align::RIGHT => (-target_width, 0.0),
     };
 
-    let num_spaces_in_range = row.glyphs[glyph_range.0..glyph_range.1]
-        .iter()
-        .filter(|glyph| glyph.chr.is_whitespace())
-        .count();
+    // Where to put the extra space: after word separators, and next to CJK characters.
+    // Only if there are none of those do we spread it out between all the glyphs.
+    let mut is_expandable_gap: Vec<bool> = order[glyph_range.0..glyph_range.1]
+        .windows(2)
+        .map(|pair| {
+            let (left, right) = (row.glyphs[pair[0]].chr, row.glyphs[pair[1]].chr);
+            left.is_whitespace() || is_cjk(left) || is_cjk(right)
+        })
+        .collect();
+    if !is_expandable_gap.contains(&true) {
+        is_expandable_gap.fill(true);
+    }
+    let num_expandable_gaps = is_expandable_gap.iter().filter(|&&gap| gap).count();
 
-    let mut extra_x_per_glyph = if num_glyphs_in_range == 1 {
+    let extra_x_per_gap = if num_expandable_gaps == 0 {
         0.0
     } else {
-        (target_width - original_width) / (num_glyphs_in_range as f32 - 1.0)
+        ((target_width - original_width) / num_expandable_gaps as f32).at_least(0.0) // Don't contract
     };
-    extra_x_per_glyph = extra_x_per_glyph.at_least(0.0); // Don't contract
-
-    let mut extra_x_per_space = 0.0;
-    if 0 < num_spaces_in_range && num_spaces_in_range < num_glyphs_in_range {
-        // Add an integral number of pixels between each glyph,
-        // and add the balance to the spaces:
-
-        extra_x_per_glyph = point_scale.floor_to_pixel(extra_x_per_glyph);
-
-        extra_x_per_space = (target_width
-            - original_width
-            - extra_x_per_glyph * (num_glyphs_in_range as f32 - 1.0))
-            / (num_spaces_in_range as f32);
-    }
 
     placed_row.pos.x = point_scale.round_to_pixel(target_min_x);
-    let mut translate_x = -original_min_x - extra_x_per_glyph * glyph_range.0 as f32;
+    let mut translate_x = -original_min_x;
 
-    for glyph in &mut row.glyphs {
+    for (visual_index, i) in order.into_iter().enumerate() {
+        let glyph = &mut row.glyphs[i];
         glyph.pos.x += translate_x;
         glyph.pos.x = point_scale.round_to_pixel(glyph.pos.x);
-        translate_x += extra_x_per_glyph;
-        if glyph.chr.is_whitespace() {
-            translate_x += extra_x_per_space;
+        let gap_index = visual_index.wrapping_sub(glyph_range.0);
+        if is_expandable_gap.get(gap_index) == Some(&true) {
+            translate_x += extra_x_per_gap;
         }
     }
 
@@ -647,8 +1553,10 @@ fn halign_and_justify_row(
 fn galley_from_rows(
     point_scale: PointScale,
     job: Arc<LayoutJob>,
+    section_decorations: &[DecorationMetrics],
+    row_halos: &[Vec<GlyphHalo>],
     mut rows: Vec<PlacedRow>,
-    elided: bool,
+    elided: Option<Range<usize>>,
     intrinsic_size: Vec2,
 ) -> Galley {
     let mut first_row_min_height = job.first_row_min_height;
@@ -694,11 +1602,19 @@ fn galley_from_rows(
     let mut num_vertices = 0;
     let mut num_indices = 0;
 
-    for placed_row in &mut rows {
+    for (row_index, placed_row) in rows.iter_mut().enumerate() {
         rect |= placed_row.rect();
 
         let row = Arc::make_mut(&mut placed_row.row);
-        row.visuals = tessellate_row(point_scale, &job, &format_summary, row);
+        let halos = row_halos.get(row_index).map_or(&[][..], Vec::as_slice);
+        row.visuals = tessellate_row(
+            point_scale,
+            &job,
+            section_decorations,
+            &format_summary,
+            halos,
+            row,
+        );
 
         mesh_bounds |= row.visuals.mesh_bounds.translate(placed_row.pos.to_vec2());
         num_vertices += row.visuals.mesh.vertices.len();
@@ -726,14 +1642,91 @@ fn galley_from_rows(
         galley.round_output_to_gui();
     }
 
+    if galley.is_vertical() {
+        // The rows are turned 90° clockwise into columns, see `Galley::pos_from_row_space`:
+        let rect = galley.rect;
+        galley.rect = Rect::from_x_y_ranges(0.0..=rect.max.y, rect.x_range());
+        if galley.mesh_bounds.is_positive() {
+            galley.mesh_bounds = galley.rect_from_row_space(galley.mesh_bounds);
+        }
+        galley.intrinsic_size = vec2(galley.intrinsic_size.y, galley.intrinsic_size.x);
+    }
+
     galley
 }
 
+/// An outline or shadow of a glyph, painted under the glyphs.
+struct GlyphHalo {
+    /// Index into [`Row::glyphs`].
+    glyph_index: u32,
+
+    /// See [`FontsImpl::glyph_halo`].
+    uv_rect: UvRect,
+
+    color: Color32,
+
+    offset: Vec2,
+}
+
+/// The outlines ([`TextFormat::outline`]) and shadows ([`TextFormat::shadow`]) of the glyphs in a row,
+/// in the order they should be painted.
+fn glyph_halos(fonts: &mut FontsImpl, job: &LayoutJob, row: &Row) -> Vec<GlyphHalo> {
+    let mut halos = vec![];
+
+    // All shadows go under all outlines:
+    for is_shadow in [true, false] {
+        let mut extra_glyphs = row.extra_glyphs.iter().peekable();
+        for (glyph_index, glyph) in row.glyphs.iter().enumerate() {
+            let format = &job.sections[glyph.section_index as usize].format;
+            let (radius, blur, color, offset) = if is_shadow {
+                let Shadow {
+                    offset: [offset_x, offset_y],
+                    blur,
+                    spread,
+                    color,
+                } = format.shadow;
+                let offset = vec2(offset_x as f32, offset_y as f32);
+                (spread as f32, blur as f32, color, offset)
+            } else if format.outline.is_empty() {
+                (0.0, 0.0, Color32::TRANSPARENT, Vec2::ZERO)
+            } else {
+                (format.outline.width, 0.0, format.outline.color, Vec2::ZERO)
+            };
+
+            let uv_rects = std::iter::once(glyph.uv_rect).chain(std::iter::from_fn(|| {
+                extra_glyphs
+                    .next_if(|extra| extra.glyph_index as usize == glyph_index)
+                    .map(|extra| extra.uv_rect)
+            }));
+            for uv_rect in uv_rects {
+                // Go through all extra glyphs of this glyph, even if we don't use them:
+                if color == Color32::TRANSPARENT || uv_rect.colored {
+                    continue;
+                }
+                let uv_rect = fonts.glyph_halo(job.glyph_rendering, uv_rect, radius, blur);
+                if !uv_rect.is_nothing() {
+                    halos.push(GlyphHalo {
+                        glyph_index: glyph_index as u32,
+                        uv_rect,
+                        color,
+                        offset,
+                    });
+                }
+            }
+        }
+    }
+
+    halos
+}
+
 #[derive(Default)]
 struct FormatSummary {
     any_background: bool,
     any_underline: bool,
     any_strikethrough: bool,
+    any_overline: bool,
+    any_outline: bool,
+    any_shadow: bool,
 }
 
 fn format_summary(job: &LayoutJob) -> FormatSummary {
@@ -742,6 +1735,9 @@ fn format_summary(job: &LayoutJob) -> FormatSummary {
         format_summary.any_background |= section.format.background != Color32::TRANSPARENT;
         format_summary.any_underline |= section.format.underline != Stroke::NONE;
         format_summary.any_strikethrough |= section.format.strikethrough != Stroke::NONE;
+        format_summary.any_overline |= section.format.overline != Stroke::NONE;
+        format_summary.any_outline |= !section.format.outline.is_empty();
+        format_summary.any_shadow |= section.format.shadow.color != Color32::TRANSPARENT;
     }
     format_summary
 }
@@ -749,7 +1745,9 @@ fn format_summary(job: &LayoutJob) -> FormatSummary {
 fn tessellate_row(
     point_scale: PointScale,
     job: &LayoutJob,
+    section_decorations: &[DecorationMetrics],
     format_summary: &FormatSummary,
+    halos: &[GlyphHalo],
     row: &mut Row,
 ) -> RowVisuals {
     if row.glyphs.is_empty() {
@@ -765,27 +1763,44 @@ fn tessellate_row(
         add_row_backgrounds(point_scale, job, row, &mut mesh);
     }
 
+    add_row_halos(point_scale, job, row, halos, &mut mesh);
+
     let glyph_index_start = mesh.indices.len();
     let glyph_vertex_start = mesh.vertices.len();
-    tessellate_glyphs(point_scale, job, row, &mut mesh);
+    let color_glyph_vertex_ranges = tessellate_glyphs(point_scale, job, row, &mut mesh);
     let glyph_vertex_end = mesh.vertices.len();
 
     if format_summary.any_underline {
-        add_row_hline(point_scale, row, &mut mesh, |glyph| {
-            let format = &job.sections[glyph.section_index as usize].format;
-            let stroke = format.underline;
-            let y = glyph.logical_rect().bottom();
-            (stroke, y)
-        });
+        add_row_decoration(
+            point_scale,
+            job,
+            section_decorations,
+            row,
+            &mut mesh,
+            Decoration::Underline,
+        );
     }
 
     if format_summary.any_strikethrough {
-        add_row_hline(point_scale, row, &mut mesh, |glyph| {
-            let format = &job.sections[glyph.section_index as usize].format;
-            let stroke = format.strikethrough;
-            let y = glyph.logical_rect().center().y;
-            (stroke, y)
-        });
+        add_row_decoration(
+            point_scale,
+            job,
+            section_decorations,
+            row,
+            &mut mesh,
+            Decoration::Strikethrough,
+        );
+    }
+
+    if format_summary.any_overline {
+        add_row_decoration(
+            point_scale,
+            job,
+            section_decorations,
+            row,
+            &mut mesh,
+            Decoration::Overline,
+        );
     }
 
     let mesh_bounds = mesh.calc_bounds();
@@ -795,6 +1810,7 @@ fn tessellate_row(
         mesh_bounds,
         glyph_index_start,
         glyph_vertex_range: glyph_vertex_start..glyph_vertex_end,
+        color_glyph_vertex_ranges,
     }
 }
 
@@ -817,7 +1833,8 @@ fn add_row_backgrounds(point_scale: PointScale, job: &LayoutJob, row: &Row, mesh
     let mut run_start = None;
     let mut last_rect = Rect::NAN;
 
-    for glyph in &row.glyphs {
+    for i in visual_order(row) {
+        let glyph = &row.glyphs[i];
         let format = &job.sections[glyph.section_index as usize].format;
         let color = format.background;
         let rect = glyph.logical_rect();
@@ -845,96 +1862,293 @@ fn add_row_backgrounds(point_scale: PointScale, job: &LayoutJob, row: &Row, mesh
     end_run(run_start.take(), last_rect.right());
 }
 
-fn tessellate_glyphs(point_scale: PointScale, job: &LayoutJob, row: &mut Row, mesh: &mut Mesh) {
-    for glyph in &mut row.glyphs {
+/// Paint the outlines and shadows of the glyphs under them, see [`glyph_halos`].
+fn add_row_halos(
+    point_scale: PointScale,
+    job: &LayoutJob,
+    row: &Row,
+    halos: &[GlyphHalo],
+    mesh: &mut Mesh,
+) {
+    let snap_to_pixels = job.glyph_rendering == GlyphRendering::Bitmap;
+
+    for halo in halos {
+        let glyph = &row.glyphs[halo.glyph_index as usize];
+        let format = &job.sections[glyph.section_index as usize].format;
+
+        let vertex_start = mesh.vertices.len();
+        add_glyph_quad(
+            point_scale,
+            format,
+            glyph.pos,
+            halo.uv_rect,
+            snap_to_pixels,
+            mesh,
+        );
+        let vertices = &mut mesh.vertices[vertex_start..];
+        if is_upright(job, glyph) {
+            turn_glyph_upright(point_scale, glyph, vertices, snap_to_pixels);
+        }
+        for vertex in vertices {
+            vertex.pos += halo.offset;
+            vertex.color = halo.color;
+        }
+    }
+}
+
+/// Returns the vertex ranges of the color glyphs (e.g. emojis).
+fn tessellate_glyphs(
+    point_scale: PointScale,
+    job: &LayoutJob,
+    row: &mut Row,
+    mesh: &mut Mesh,
+) -> Vec<std::ops::Range<usize>> {
+    let mut extra_glyphs = row.extra_glyphs.iter().peekable();
+    let mut color_glyph_vertex_ranges: Vec<std::ops::Range<usize>> = vec![];
+
+    // Distance field glyphs are positioned with subpixel precision, since they may be zoomed in:
+    let snap_to_pixels = job.glyph_rendering == GlyphRendering::Bitmap;
+
+    let mut add_quad = |format: &super::TextFormat, pos: Pos2, uv_rect: UvRect, mesh: &mut Mesh| {
+        let vertex_start = mesh.vertices.len();
+        add_glyph_quad(point_scale, format, pos, uv_rect, snap_to_pixels, mesh);
+        if uv_rect.colored && vertex_start < mesh.vertices.len() {
+            match color_glyph_vertex_ranges.last_mut() {
+                Some(last) if last.end == vertex_start => last.end = mesh.vertices.len(),
+                _ => color_glyph_vertex_ranges.push(vertex_start..mesh.vertices.len()),
+            }
+        }
+    };
+
+    for (glyph_index, glyph) in row.glyphs.iter_mut().enumerate() {
         glyph.first_vertex = mesh.vertices.len() as u32;
-        let uv_rect = glyph.uv_rect;
-        if !uv_rect.is_nothing() {
-            let mut left_top = glyph.pos + uv_rect.offset;
-            left_top.x = point_scale.round_to_pixel(left_top.x);
-            left_top.y = point_scale.round_to_pixel(left_top.y);
-
-            let rect = Rect::from_min_max(left_top, left_top + uv_rect.size);
-            let uv = Rect::from_min_max(
-                pos2(uv_rect.min[0] as f32, uv_rect.min[1] as f32),
-                pos2(uv_rect.max[0] as f32, uv_rect.max[1] as f32),
-            );
+        let format = &job.sections[glyph.section_index as usize].format;
 
-            let format = &job.sections[glyph.section_index as usize].format;
+        add_quad(format, glyph.pos, glyph.uv_rect, mesh);
 
-            let color = format.color;
+        // Extra glyphs come right after their glyph, so that the vertices of each glyph are contiguous:
+        while let Some(extra) =
+            extra_glyphs.next_if(|extra| extra.glyph_index as usize == glyph_index)
+        {
+            add_quad(format, glyph.pos, extra.uv_rect, mesh);
+        }
 
-            if format.italics {
-                let idx = mesh.vertices.len() as u32;
-                mesh.add_triangle(idx, idx + 1, idx + 2);
-                mesh.add_triangle(idx + 2, idx + 1, idx + 3);
+        if is_upright(job, glyph) {
+            let vertices = &mut mesh.vertices[glyph.first_vertex as usize..];
+            turn_glyph_upright(point_scale, glyph, vertices, snap_to_pixels);
+        }
+    }
 
-                let top_offset = rect.height() * 0.25 * Vec2::X;
+    color_glyph_vertex_ranges
+}
 
-                mesh.vertices.push(Vertex {
-                    pos: rect.left_top() + top_offset,
-                    uv: uv.left_top(),
-                    color,
-                });
-                mesh.vertices.push(Vertex {
-                    pos: rect.right_top() + top_offset,
-                    uv: uv.right_top(),
-                    color,
-                });
-                mesh.vertices.push(Vertex {
-                    pos: rect.left_bottom(),
-                    uv: uv.left_bottom(),
-                    color,
-                });
-                mesh.vertices.push(Vertex {
-                    pos: rect.right_bottom(),
-                    uv: uv.right_bottom(),
-                    color,
-                });
-            } else {
-                mesh.add_rect_with_uv(rect, uv, color);
-            }
+/// Is this glyph kept upright in vertical text ([`WritingMode::VerticalRightToLeft`]),
+/// e.g. a CJK character, rather than turned with the text?
+fn is_upright(job: &LayoutJob, glyph: &Glyph) -> bool {
+    job.writing_mode == WritingMode::VerticalRightToLeft
+        && glyph.font_face != FontFaceKey::PLACEHOLDER
+        && is_upright_in_vertical_text(glyph.chr)
+}
+
+/// Turn the quads of an upright glyph in vertical text 90° counter-clockwise around its center,
+/// so that it ends up upright when the row is turned 90° clockwise.
+fn turn_glyph_upright(
+    point_scale: PointScale,
+    glyph: &Glyph,
+    vertices: &mut [Vertex],
+    snap_to_pixels: bool,
+) {
+    let center = pos2(
+        glyph.pos.x + 0.5 * glyph.advance_width,
+        glyph.pos.y - glyph.font_ascent + 0.5 * glyph.font_height,
+    );
+
+    let mut min = Pos2::new(f32::INFINITY, f32::INFINITY);
+    for vertex in &mut *vertices {
+        let offset = vertex.pos - center;
+        vertex.pos = center + vec2(offset.y, -offset.x);
+        min = min.min(vertex.pos);
+    }
+
+    if snap_to_pixels {
+        let snapped = pos2(
+            point_scale.round_to_pixel(min.x),
+            point_scale.round_to_pixel(min.y),
+        );
+        for vertex in vertices {
+            vertex.pos += snapped - min;
+        }
+    }
+}
+
+fn add_glyph_quad(
+    point_scale: PointScale,
+    format: &super::TextFormat,
+    pos: Pos2,
+    uv_rect: UvRect,
+    snap_to_pixels: bool,
+    mesh: &mut Mesh,
+) {
+    if uv_rect.is_nothing() {
+        return;
+    }
+
+    let mut left_top = pos + uv_rect.offset;
+    if snap_to_pixels {
+        left_top.x = point_scale.round_to_pixel(left_top.x);
+        left_top.y = point_scale.round_to_pixel(left_top.y);
+    }
+
+    let rect = Rect::from_min_max(left_top, left_top + uv_rect.size);
+    let uv = Rect::from_min_max(
+        pos2(uv_rect.min[0] as f32, uv_rect.min[1] as f32),
+        pos2(uv_rect.max[0] as f32, uv_rect.max[1] as f32),
+    );
+
+    let color = if uv_rect.colored {
+        // Color glyphs keep their own colors, but respect the opacity of the text:
+        if format.color == Color32::PLACEHOLDER {
+            Color32::WHITE
+        } else {
+            Color32::from_white_alpha(format.color.a())
+        }
+    } else {
+        format.color
+    };
+
+    if format.italics {
+        let idx = mesh.vertices.len() as u32;
+        mesh.add_triangle(idx, idx + 1, idx + 2);
+        mesh.add_triangle(idx + 2, idx + 1, idx + 3);
+
+        let top_offset = rect.height() * 0.25 * Vec2::X;
+
+        mesh.vertices.push(Vertex {
+            pos: rect.left_top() + top_offset,
+            uv: uv.left_top(),
+            color,
+        });
+        mesh.vertices.push(Vertex {
+            pos: rect.right_top() + top_offset,
+            uv: uv.right_top(),
+            color,
+        });
+        mesh.vertices.push(Vertex {
+            pos: rect.left_bottom(),
+            uv: uv.left_bottom(),
+            color,
+        });
+        mesh.vertices.push(Vertex {
+            pos: rect.right_bottom(),
+            uv: uv.right_bottom(),
+            color,
+        });
+    } else {
+        mesh.add_rect_with_uv(rect, uv, color);
+    }
+}
+
+/// The lines we can draw under, through and over text.
+#[derive(Clone, Copy)]
+enum Decoration {
+    Underline,
+    Strikethrough,
+    Overline,
+}
+
+impl Decoration {
+    /// The stroke to use for a glyph, with the thickness the [`TextFormat`] asks for.
+    fn stroke(self, format: &TextFormat, metrics: &DecorationMetrics) -> Stroke {
+        let stroke = match self {
+            Self::Underline => format.underline,
+            Self::Strikethrough => format.strikethrough,
+            Self::Overline => format.overline,
+        };
+        if format.decoration_thickness_from_font && !stroke.is_empty() {
+            let thickness = match self {
+                Self::Underline | Self::Overline => metrics.underline_thickness,
+                Self::Strikethrough => metrics.strikethrough_thickness,
+            };
+            Stroke::new(thickness, stroke.color)
+        } else {
+            stroke
+        }
+    }
+
+    /// Where to center the line for this glyph.
+    ///
+    /// The decorations follow the font of the [`TextFormat`], not the font of each glyph,
+    /// so that e.g. an emoji in the middle of underlined text doesn't move the underline.
+    fn y(self, glyph: &Glyph, metrics: &DecorationMetrics, thickness: f32) -> f32 {
+        let baseline = glyph.pos.y;
+        match self {
+            Self::Underline => baseline + metrics.underline_offset,
+            Self::Strikethrough => baseline - metrics.strikethrough_offset,
+            Self::Overline => baseline - glyph.font_ascent + 0.5 * thickness,
+        }
+    }
+
+    /// Offsets of the two lines of [`DecorationStyle::Double`] from [`Self::y`].
+    ///
+    /// The second line goes away from the text for the underline,
+    /// and into the text for the overline, to stay within the row.
+    fn double_offsets(self, thickness: f32) -> [f32; 2] {
+        match self {
+            Self::Underline | Self::Overline => [0.0, 2.0 * thickness],
+            Self::Strikethrough => [-thickness, thickness],
         }
     }
 }
 
-/// Add a horizontal line over a row of glyphs with a stroke and y decided by a callback.
-fn add_row_hline(
+/// Add a decoration line over a row of glyphs, merging neighboring glyphs with the same line.
+fn add_row_decoration(
     point_scale: PointScale,
+    job: &LayoutJob,
+    section_decorations: &[DecorationMetrics],
     row: &Row,
     mesh: &mut Mesh,
-    stroke_and_y: impl Fn(&Glyph) -> (Stroke, f32),
+    decoration: Decoration,
 ) {
     let mut path = crate::tessellator::Path::default(); // reusing path to avoid re-allocations.
 
-    let mut end_line = |start: Option<(Stroke, Pos2)>, stop_x: f32| {
-        if let Some((stroke, start)) = start {
+    let mut end_line = |start: Option<(Stroke, DecorationStyle, Pos2)>, stop_x: f32| {
+        if let Some((stroke, style, start)) = start {
             let stop = pos2(stop_x, start.y);
-            path.clear();
-            path.add_line_segment([start, stop]);
-            let feathering = 1.0 / point_scale.pixels_per_point();
-            path.stroke_open(feathering, &PathStroke::from(stroke), mesh);
+            add_decoration_line(
+                point_scale,
+                &mut path,
+                mesh,
+                decoration,
+                [start, stop],
+                stroke,
+                style,
+            );
         }
     };
 
     let mut line_start = None;
     let mut last_right_x = f32::NAN;
 
-    for glyph in &row.glyphs {
-        let (stroke, mut y) = stroke_and_y(glyph);
+    for i in visual_order(row) {
+        let glyph = &row.glyphs[i];
+        let format = &job.sections[glyph.section_index as usize].format;
+        let metrics = &section_decorations[glyph.section_index as usize];
+        let stroke = decoration.stroke(format, metrics);
+        let style = format.decoration_style;
+        let mut y = decoration.y(glyph, metrics, stroke.width);
         stroke.round_center_to_pixel(point_scale.pixels_per_point, &mut y);
 
         if stroke.is_empty() {
             end_line(line_start.take(), last_right_x);
-        } else if let Some((existing_stroke, start)) = line_start {
-            if existing_stroke == stroke && start.y == y {
+        } else if let Some((existing_stroke, existing_style, start)) = line_start {
+            if existing_stroke == stroke && existing_style == style && start.y == y {
                 // continue the same line
             } else {
                 end_line(line_start.take(), last_right_x);
-                line_start = Some((stroke, pos2(glyph.pos.x, y)));
+                line_start = Some((stroke, style, pos2(glyph.pos.x, y)));
             }
         } else {
-            line_start = Some((stroke, pos2(glyph.pos.x, y)));
+            line_start = Some((stroke, style, pos2(glyph.pos.x, y)));
         }
 
         last_right_x = glyph.max_x();
@@ -943,25 +2157,219 @@ fn add_row_hline(
     end_line(line_start.take(), last_right_x);
 }
 
+/// Tessellate one horizontal decoration line in the given style.
+fn add_decoration_line(
+    point_scale: PointScale,
+    path: &mut crate::tessellator::Path,
+    mesh: &mut Mesh,
+    decoration: Decoration,
+    [start, stop]: [Pos2; 2],
+    stroke: Stroke,
+    style: DecorationStyle,
+) {
+    let feathering = 1.0 / point_scale.pixels_per_point();
+    // Make sure dots, dashes and waves are visible even for hairlines:
+    let thickness = stroke
+        .width
+        .at_least(point_scale.pixels_per_point().recip());
+
+    let mut shapes = vec![];
+    match style {
+        DecorationStyle::Solid => {
+            shapes.push(Shape::line_segment([start, stop], stroke));
+        }
+        DecorationStyle::Double => {
+            for offset in decoration.double_offsets(thickness) {
+                let offset = point_scale.round_to_pixel(offset) * Vec2::Y;
+                shapes.push(Shape::line_segment([start + offset, stop + offset], stroke));
+            }
+        }
+        DecorationStyle::Dashed => {
+            Shape::dashed_line_many(
+                &[start, stop],
+                stroke,
+                3.0 * thickness,
+                2.0 * thickness,
+                &mut shapes,
+            );
+        }
+        DecorationStyle::Dotted => {
+            shapes = Shape::dotted_line(
+                &[start, stop],
+                stroke.color,
+                2.0 * thickness,
+                0.5 * thickness,
+            );
+        }
+        DecorationStyle::Wavy => {
+            let points = wavy_line(start, stop.x, thickness, feathering);
+            if 2 <= points.len() {
+                path.clear();
+                path.add_open_points(&points);
+                path.stroke_open(feathering, &PathStroke::from(stroke), mesh);
+            }
+        }
+    }
+
+    for shape in shapes {
+        match shape {
+            Shape::LineSegment { points, stroke } => {
+                path.clear();
+                path.add_line_segment(points);
+                path.stroke_open(feathering, &PathStroke::from(stroke), mesh);
+            }
+            Shape::Circle(circle) => {
+                path.clear();
+                path.add_circle(circle.center, circle.radius);
+                path.fill(feathering, circle.fill, mesh);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Points along a wave from `start` to `stop_x`, made of quadratic bezier half-waves.
+fn wavy_line(start: Pos2, stop_x: f32, thickness: f32, tolerance: f32) -> Vec<Pos2> {
+    let amplitude = thickness;
+    let half_wavelength = 3.0 * amplitude;
+
+    let mut points = vec![start];
+    let mut x = start.x;
+    let mut up = true;
+    while x < stop_x {
+        let next_x = (x + half_wavelength).at_most(stop_x);
+        // The peak of a quadratic bezier is halfway to its control point:
+        let control_y = if up { -2.0 } else { 2.0 } * amplitude;
+        let half_wave = QuadraticBezierShape::from_points_stroke(
+            [
+                pos2(x, start.y),
+                pos2(0.5 * (x + next_x), start.y + control_y),
+                pos2(next_x, start.y),
+            ],
+            false,
+            Color32::TRANSPARENT,
+            Stroke::NONE,
+        );
+        points.extend(half_wave.flatten(Some(tolerance)).into_iter().skip(1));
+        x = next_x;
+        up = !up;
+    }
+    points
+}
+
 // ----------------------------------------------------------------------------
 
+/// Invisible, unless a row is broken there.
+const SOFT_HYPHEN: char = '\u{AD}';
+
+/// What we show at the end of a row that was broken at a hyphenation point.
+const HYPHEN: char = '-';
+
+/// For each glyph: may we break the row after it,
+/// according to the Unicode Line Breaking Algorithm ([UAX #14](https://www.unicode.org/reports/tr14/))?
+///
+/// This allows breaking after spaces and dashes, and between most CJK characters, for instance.
+fn line_break_opportunities(glyphs: &[Glyph]) -> Vec<bool> {
+    let text: String = glyphs.iter().map(|glyph| glyph.chr).collect();
+    let mut breaks = unicode_linebreak::linebreaks(&text).peekable();
+    text.char_indices()
+        .map(|(byte_index, chr)| {
+            let end = byte_index + chr.len_utf8();
+            while breaks.next_if(|(index, _)| *index < end).is_some() {}
+            breaks.peek().is_some_and(|(index, _)| *index == end)
+        })
+        .collect()
+}
+
+/// For each glyph: may we break the row after it with a hyphen,
+/// according to the hyphenation dictionary of its language?
+fn hyphenation_points(fonts: &FontsImpl, job: &LayoutJob, glyphs: &[Glyph]) -> Vec<bool> {
+    let mut points = vec![false; glyphs.len()];
+    let mut word_start = 0;
+    while word_start < glyphs.len() {
+        let section_index = glyphs[word_start].section_index;
+        let word_len = glyphs[word_start..]
+            .iter()
+            .take_while(|glyph| glyph.chr.is_alphabetic() && glyph.section_index == section_index)
+            .count();
+        if word_len == 0 {
+            word_start += 1;
+            continue;
+        }
+
+        let format = &job.sections[section_index as usize].format;
+        let language = format.language.or_else(|| fonts.options().language);
+        if let Some(hyphenator) = fonts.hyphenator(language) {
+            let word: Vec<char> = glyphs[word_start..word_start + word_len]
+                .iter()
+                .map(|glyph| glyph.chr)
+                .collect();
+            for point in hyphenator.hyphenate_chars(&word) {
+                points[word_start + point - 1] = true;
+            }
+        }
+        word_start += word_len;
+    }
+    points
+}
+
+/// The hyphen to show at the end of a row that ends at `x` (in points).
+///
+/// Returns its width in points, and its [`UvRect`] relative to `x`.
+fn hyphen_glyph(
+    fonts: &mut FontsImpl,
+    pixels_per_point: f32,
+    job: &LayoutJob,
+    section_index: u32,
+    x: f32,
+) -> (f32, UvRect) {
+    let format = &job.sections[section_index as usize].format;
+    let language = format.language.or_else(|| fonts.options().language);
+    let mut font = fonts.font_for_layout(&format.font_id.family, language, job.glyph_rendering);
+    let (font_id, glyph_info) = font.glyph_info_with_variations(HYPHEN, format.font_id.variations);
+    let Some(font_face) = font.fonts_by_id.get_mut(&font_id) else {
+        return (0.0, UvRect::default());
+    };
+    let font_face_metrics = font_face
+        .scaled_metrics(pixels_per_point, format.font_id.size)
+        .with_variations(format.font_id.variations);
+    let (glyph_alloc, physical_x) = font_face.allocate_glyph(
+        font.atlas,
+        &font_face_metrics,
+        glyph_info,
+        HYPHEN,
+        x * pixels_per_point,
+    );
+    let mut uv_rect = glyph_alloc.uv_rect;
+    uv_rect.offset.x += physical_x as f32 / pixels_per_point - x;
+    (glyph_alloc.advance_width_px / pixels_per_point, uv_rect)
+}
+
+/// Why we could break a row after a glyph.
+#[derive(Clone, Copy)]
+enum RowBreakKind {
+    /// See [`line_break_opportunities`].
+    LineBreak,
+
+    /// At a soft hyphen, or a hyphenation point (see [`hyphenation_points`]).
+    Hyphen,
+
+    /// After some punctuation.
+    Punctuation,
+}
+
 /// Keeps track of good places to break a long row of text.
-/// Will focus primarily on spaces, secondarily on things like `-`
+/// Will focus primarily on the line break opportunities of UAX #14,
+/// secondarily on things like `.` and `/`
 #[derive(Clone, Copy, Default)]
 struct RowBreakCandidates {
-    /// Breaking at ` ` or other whitespace
+    /// Breaking after a space, between CJK characters etc.
     /// is always the primary candidate.
-    space: Option<usize>,
-
-    /// Logograms (single character representing a whole word) or kana (Japanese hiragana and katakana) are good candidates for line break.
-    cjk: Option<usize>,
-
-    /// Breaking anywhere before a CJK character is acceptable too.
-    pre_cjk: Option<usize>,
+    line_break: Option<usize>,
 
-    /// Breaking at a dash is a super-
-    /// good idea.
-    dash: Option<usize>,
+    /// Breaking within a word and showing a hyphen is just as good,
+    /// as long as there is room for the hyphen.
+    hyphen: Option<usize>,
 
     /// This is nicer for things like URLs, e.g. www.
     /// example.com.
@@ -973,28 +2381,18 @@ struct RowBreakCandidates {
 }
 
 impl RowBreakCandidates {
-    fn add(&mut self, index: usize, glyphs: &[Glyph]) {
-        let chr = glyphs[0].chr;
-        const NON_BREAKING_SPACE: char = '\u{A0}';
-        if chr.is_whitespace() && chr != NON_BREAKING_SPACE {
-            self.space = Some(index);
-        } else if is_cjk(chr) && (glyphs.len() == 1 || is_cjk_break_allowed(glyphs[1].chr)) {
-            self.cjk = Some(index);
-        } else if chr == '-' {
-            self.dash = Some(index);
-        } else if chr.is_ascii_punctuation() {
-            self.punctuation = Some(index);
-        } else if glyphs.len() > 1 && is_cjk(glyphs[1].chr) {
-            self.pre_cjk = Some(index);
+    fn add(&mut self, index: usize, kind: Option<RowBreakKind>) {
+        match kind {
+            Some(RowBreakKind::LineBreak) => self.line_break = Some(index),
+            Some(RowBreakKind::Hyphen) => self.hyphen = Some(index),
+            Some(RowBreakKind::Punctuation) => self.punctuation = Some(index),
+            None => {}
         }
         self.any = Some(index);
     }
 
     fn word_boundary(&self) -> Option<usize> {
-        [self.space, self.cjk, self.pre_cjk]
-            .into_iter()
-            .max()
-            .flatten()
+        self.line_break.max(self.hyphen)
     }
 
     fn has_good_candidate(&self, break_anywhere: bool) -> bool {
@@ -1009,39 +2407,21 @@ impl RowBreakCandidates {
         if break_anywhere {
             self.any
         } else {
-            self.word_boundary()
-                .or(self.dash)
-                .or(self.punctuation)
-                .or(self.any)
+            self.word_boundary().or(self.punctuation).or(self.any)
         }
     }
 
     fn forget_before_idx(&mut self, index: usize) {
         let Self {
-            space,
-            cjk,
-            pre_cjk,
-            dash,
+            line_break,
+            hyphen,
             punctuation,
             any,
         } = self;
-        if space.is_some_and(|s| s < index) {
-            *space = None;
-        }
-        if cjk.is_some_and(|s| s < index) {
-            *cjk = None;
-        }
-        if pre_cjk.is_some_and(|s| s < index) {
-            *pre_cjk = None;
-        }
-        if dash.is_some_and(|s| s < index) {
-            *dash = None;
-        }
-        if punctuation.is_some_and(|s| s < index) {
-            *punctuation = None;
-        }
-        if any.is_some_and(|s| s < index) {
-            *any = None;
+        for candidate in [line_break, hyphen, punctuation, any] {
+            if candidate.is_some_and(|s| s < index) {
+                *candidate = None;
+            }
         }
     }
 }
@@ -1052,6 +2432,7 @@ impl RowBreakCandidates {
 mod tests {
 
     use super::{super::*, *};
+    use crate::text::cursor::CCursor;
 
     #[test]
     fn test_zero_max_width() {
@@ -1086,7 +2467,7 @@ mod tests {
 
                     let galley = layout(&mut fonts, pixels_per_point, layout_job.into());
 
-                    assert!(galley.elided);
+                    assert!(galley.elided.is_some());
                     assert_eq!(galley.rows.len(), 1);
                     let row_text = galley.rows[0].text();
                     assert!(
@@ -1105,13 +2486,287 @@ mod tests {
 
             let galley = layout(&mut fonts, pixels_per_point, layout_job.into());
 
-            assert!(galley.elided);
+            assert!(galley.elided.is_some());
             assert_eq!(galley.rows.len(), 1);
             let row_text = galley.rows[0].text();
             assert_eq!(row_text, "Hello…");
         }
     }
 
+    #[test]
+    fn test_elision() {
+        let mut fonts = FontsImpl::new(TextOptions::default(), FontDefinitions::default());
+        let format = TextFormat {
+            font_id: FontId::monospace(12.0),
+            ..Default::default()
+        };
+        let dir = "/home/user/projects/egui";
+        let file_name = "/main.rs";
+        let text = format!("{dir}{file_name}");
+
+        let mut job = LayoutJob::default();
+        job.append(dir, 0.0, format.clone());
+        job.append(file_name, 0.0, format);
+        let char_width =
+            layout(&mut fonts, 1.0, Arc::new(job.clone())).rows[0].glyphs[0].advance_width;
+
+        let mut elide = |elision: Elision, max_width_in_chars: f32| {
+            job.wrap = TextWrapping::truncate_at_width(max_width_in_chars * char_width);
+            job.wrap.elision = elision;
+            let galley = layout(&mut fonts, 1.0, Arc::new(job.clone()));
+            assert_eq!(galley.rows.len(), 1);
+            assert!(galley.rect.width() <= job.wrap.max_width);
+            let elided = galley.elided.clone().unwrap();
+            let row_text = galley.rows[0].text();
+            assert_eq!(
+                row_text,
+                format!("{}…{}", &text[..elided.start], &text[elided.end..]),
+                "{elision:?}"
+            );
+            (row_text, galley)
+        };
+
+        let (end, _) = elide(Elision::End, 20.5);
+        assert_eq!(end, "/home/user/projects…");
+
+        let (start, _) = elide(Elision::Start, 20.5);
+        assert_eq!(start, "…ojects/egui/main.rs");
+
+        let (middle, _) = elide(Elision::Middle, 20.5);
+        assert_eq!(middle, "/home/use…ui/main.rs");
+
+        let (path, galley) = elide(Elision::Path, 20.5);
+        assert_eq!(path, "/home/user/…/main.rs");
+        let file_name_rects = galley.section_rects(1);
+        assert_eq!(file_name_rects.len(), 1);
+        assert!((file_name_rects[0].max.x - galley.rows[0].rect().max.x).abs() < 0.1);
+        assert!((file_name_rects[0].width() - 8.0 * char_width).abs() < 1.0);
+
+        // A file name that doesn't fit is elided in the middle:
+        let (path, _) = elide(Elision::Path, 5.5);
+        assert_eq!(path, "/h…rs");
+    }
+
+    #[test]
+    fn test_elision_cursors() {
+        let mut fonts = FontsImpl::new(TextOptions::default(), FontDefinitions::default());
+        let text = "/home/user/projects/egui/main.rs";
+        let mut job = LayoutJob::single_section(
+            text.to_owned(),
+            TextFormat::simple(FontId::monospace(12.0), Color32::WHITE),
+        );
+        let char_width =
+            layout(&mut fonts, 1.0, Arc::new(job.clone())).rows[0].glyphs[0].advance_width;
+
+        for (elision, row_text) in [
+            (Elision::Start, "…ojects/egui/main.rs"),
+            (Elision::Middle, "/home/use…ui/main.rs"),
+        ] {
+            job.wrap = TextWrapping::truncate_at_width(20.5 * char_width);
+            job.wrap.elision = elision;
+            let galley = layout(&mut fonts, 1.0, Arc::new(job.clone()));
+            assert_eq!(galley.rows[0].text(), row_text);
+
+            let elided = galley.elided.clone().unwrap();
+            let ellipsis = row_text.chars().position(|c| c == '…').unwrap();
+            assert_eq!(galley.end().index, row_text.chars().count());
+
+            // Every shown char maps back to the same char in the text:
+            for (index, chr) in row_text.chars().enumerate() {
+                let cursor = CCursor::new(index);
+                let text_index = galley.text_char_index(cursor);
+                if index == ellipsis {
+                    assert_eq!(text_index, ellipsis, "{elision:?}");
+                    assert_eq!(galley.text_char_index(CCursor::new(index + 1)), elided.end);
+                } else {
+                    assert_eq!(text.chars().nth(text_index), Some(chr), "{elision:?}");
+                }
+                assert_eq!(galley.layout_from_cursor(cursor).column, index);
+            }
+            assert_eq!(galley.text_char_index(galley.end()), text.chars().count());
+
+            // Cursors move left to right over the shown chars:
+            let xs: Vec<f32> = (0..=galley.end().index)
+                .map(|index| galley.pos_from_cursor(CCursor::new(index)).min.x)
+                .collect();
+            assert!(xs.is_sorted_by(|a, b| a < b), "{elision:?}: {xs:?}");
+        }
+    }
+
+    #[test]
+    fn test_decorations() {
+        let mut fonts = FontsImpl::new(TextOptions::default(), FontDefinitions::default());
+        let mut vertex_count = |format: TextFormat| {
+            let job = LayoutJob::single_section("Hello decorations".into(), format);
+            let decoration = fonts
+                .font(&FontFamily::Proportional)
+                .scaled_metrics(1.0, job.sections[0].format.font_id.size)
+                .decoration;
+            let galley = layout(&mut fonts, 1.0, Arc::new(job));
+            let glyph = galley.rows[0].glyphs[0];
+            assert!(0.0 < decoration.underline_offset, "{decoration:?}");
+            assert!(0.0 < decoration.underline_thickness, "{decoration:?}");
+            assert!(0.0 < decoration.strikethrough_offset, "{decoration:?}");
+            assert!(
+                decoration.strikethrough_offset < glyph.font_ascent,
+                "{decoration:?}"
+            );
+            assert!(0.0 < decoration.strikethrough_thickness, "{decoration:?}");
+            galley.rows[0].visuals.mesh.vertices.len()
+        };
+
+        let plain = vertex_count(TextFormat::default());
+        let solid = vertex_count(TextFormat {
+            underline: Stroke::new(1.0, Color32::WHITE),
+            ..Default::default()
+        });
+        assert!(plain < solid);
+
+        for decoration_style in [
+            DecorationStyle::Double,
+            DecorationStyle::Dotted,
+            DecorationStyle::Dashed,
+            DecorationStyle::Wavy,
+        ] {
+            for format in [
+                TextFormat {
+                    underline: Stroke::new(1.0, Color32::WHITE),
+                    decoration_style,
+                    ..Default::default()
+                },
+                TextFormat {
+                    overline: Stroke::new(1.0, Color32::WHITE),
+                    decoration_style,
+                    decoration_thickness_from_font: true,
+                    ..Default::default()
+                },
+            ] {
+                // All of these are made of more than one line segment:
+                assert!(solid < vertex_count(format), "{decoration_style:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_outline_and_shadow() {
+        let mut fonts = FontsImpl::new(TextOptions::default(), FontDefinitions::default());
+        let mut layout_row = |glyph_rendering: GlyphRendering, format: TextFormat| {
+            let mut job = LayoutJob::single_section("Hi 😀".into(), format);
+            job.glyph_rendering = glyph_rendering;
+            let galley = layout(&mut fonts, 1.0, Arc::new(job));
+            galley.rows[0].visuals.clone()
+        };
+
+        let outline = Stroke::new(2.0, Color32::BLACK);
+        let shadow = Shadow {
+            offset: [1, 1],
+            blur: 4,
+            spread: 0,
+            color: Color32::from_black_alpha(128),
+        };
+
+        for glyph_rendering in [GlyphRendering::Bitmap, GlyphRendering::DistanceField] {
+            let plain = layout_row(glyph_rendering, TextFormat::default());
+            let outlined = layout_row(
+                glyph_rendering,
+                TextFormat {
+                    outline,
+                    ..Default::default()
+                },
+            );
+            let shadowed = layout_row(
+                glyph_rendering,
+                TextFormat {
+                    shadow,
+                    ..Default::default()
+                },
+            );
+
+            for (visuals, color) in [(&outlined, outline.color), (&shadowed, shadow.color)] {
+                // One quad for each of "Hi" and the (monochrome) emoji goes under the glyphs:
+                assert_eq!(
+                    visuals.glyph_vertex_range.start - plain.glyph_vertex_range.start,
+                    3 * 4
+                );
+                assert_eq!(
+                    plain.glyph_vertex_range.len(),
+                    visuals.glyph_vertex_range.len()
+                );
+                assert!(plain.mesh_bounds.area() < visuals.mesh_bounds.area());
+                assert!(
+                    visuals.mesh.vertices[..visuals.glyph_vertex_range.start]
+                        .iter()
+                        .all(|vertex| vertex.color == color)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_vertical_text() {
+        let mut fonts = FontsImpl::new(TextOptions::default(), FontDefinitions::default());
+        let text = "日本語とEnglish、混在";
+        let mut job = LayoutJob::single_section(text.into(), TextFormat::default());
+        job.writing_mode = WritingMode::VerticalRightToLeft;
+        job.wrap.max_width = 60.0;
+        let galley = layout(&mut fonts, 1.0, Arc::new(job));
+
+        assert!(galley.is_vertical());
+        assert!(1 < galley.rows.len());
+        assert_eq!(galley.rect.left(), 0.0);
+        assert!(galley.rect.height() <= 60.0);
+        let column_widths: f32 = galley.rows.iter().map(|row| row.height()).sum();
+        assert!((galley.rect.width() - column_widths).abs() < 1.0);
+
+        let glyphs: Vec<&Glyph> = galley.rows.iter().flat_map(|row| &row.glyphs).collect();
+        for glyph in glyphs {
+            assert_eq!(
+                is_upright(&galley.job, glyph),
+                !glyph.chr.is_ascii(),
+                "{:?} should be upright unless it is Latin",
+                glyph.chr
+            );
+        }
+
+        // Brackets and long vowel marks are turned with the text,
+        // while other punctuation gets its vertical form from the `vert` feature of the font:
+        for chr in ['（', '」', 'ー', '〜', '：'] {
+            assert!(!is_upright_in_vertical_text(chr), "{chr:?}");
+        }
+        for chr in ['。', '、', '！', 'カ', '한'] {
+            assert!(is_upright_in_vertical_text(chr), "{chr:?}");
+        }
+
+        // The columns go from right to left, and each column from top to bottom:
+        let num_chars = text.chars().count();
+        let first = galley.pos_from_cursor(CCursor::new(0));
+        let second = galley.pos_from_cursor(CCursor::new(1));
+        let last = galley.pos_from_cursor(CCursor::new(num_chars));
+        assert!(
+            first.height() < first.width(),
+            "The cursor goes across the column"
+        );
+        assert!(first.center().y < second.center().y);
+        assert!(last.center().x < first.center().x);
+        assert!((first.right() - galley.rect.right()).abs() < 1.0);
+
+        for index in 0..num_chars {
+            let rect = galley.pos_from_cursor(CCursor::new(index));
+            let cursor = galley.cursor_from_pos(rect.center().to_vec2());
+            assert_eq!(cursor.index, index);
+        }
+
+        // The tessellator turns the rows into columns:
+        let mut tessellator =
+            crate::Tessellator::new(1.0, Default::default(), [1024, 1024], vec![]);
+        let mut mesh = Mesh::default();
+        let text_shape =
+            crate::TextShape::new(Pos2::ZERO, Arc::new(galley.clone()), Color32::WHITE);
+        tessellator.tessellate_text(&text_shape, &mut mesh);
+        assert!(!mesh.is_empty());
+        assert!(galley.rect.expand(1.0).contains_rect(mesh.calc_bounds()));
+    }
+
     #[test]
     fn test_cjk() {
         let pixels_per_point = 1.0;
@@ -1154,7 +2809,7 @@ mod tests {
         layout_job.wrap.max_rows = 1;
         layout_job.round_output_to_gui = false;
         let galley = layout(&mut fonts, pixels_per_point, layout_job.into());
-        assert!(galley.elided);
+        assert!(galley.elided.is_some());
         assert_eq!(
             galley.rows.iter().map(|row| row.text()).collect::<Vec<_>>(),
             vec!["# DNA…"]
@@ -1229,4 +2884,673 @@ mod tests {
             "Unexpected intrinsic size"
         );
     }
+
+    #[test]
+    fn test_bidi_reordering() {
+        let pixels_per_point = 1.0;
+        let mut fonts = FontsImpl::new(TextOptions::default(), FontDefinitions::default());
+
+        let glyph_x = |galley: &Galley, chr: char| {
+            let glyph = galley.rows[0]
+                .glyphs
+                .iter()
+                .find(|glyph| glyph.chr == chr)
+                .unwrap();
+            glyph.pos.x
+        };
+
+        // Left-to-right paragraph with some Hebrew in it:
+        let job = LayoutJob::simple_singleline(
+            "ab \u{5d0}\u{5d1}".to_owned(),
+            FontId::default(),
+            Color32::WHITE,
+        );
+        let galley = layout(&mut fonts, pixels_per_point, job.into());
+        assert_eq!(galley.rows.len(), 1);
+        assert_eq!(galley.rows[0].glyphs.len(), 5, "Still one glyph per char");
+        assert!(glyph_x(&galley, 'a') < glyph_x(&galley, 'b'));
+        assert!(glyph_x(&galley, 'b') < glyph_x(&galley, '\u{5d1}'));
+        assert!(glyph_x(&galley, '\u{5d1}') < glyph_x(&galley, '\u{5d0}'));
+
+        // Right-to-left paragraph with some English in it:
+        let job = LayoutJob::simple_singleline(
+            "\u{5d0}\u{5d1} ab".to_owned(),
+            FontId::default(),
+            Color32::WHITE,
+        );
+        let galley = layout(&mut fonts, pixels_per_point, job.into());
+        assert!(glyph_x(&galley, 'a') < glyph_x(&galley, 'b'));
+        assert!(glyph_x(&galley, 'b') < glyph_x(&galley, '\u{5d1}'));
+        assert!(glyph_x(&galley, '\u{5d1}') < glyph_x(&galley, '\u{5d0}'));
+
+        // The start of the text is on the right:
+        let right_edge = galley.rows[0].rect().right_center();
+        let cursor = galley.cursor_from_pos(right_edge.to_vec2() - vec2(1.0, 0.0));
+        assert_eq!(cursor.index, 0);
+    }
+
+    #[test]
+    fn test_bidi_cursor_movement() {
+        let pixels_per_point = 1.0;
+        let mut fonts = FontsImpl::new(TextOptions::default(), FontDefinitions::default());
+
+        let job = LayoutJob::simple_singleline(
+            "\u{5d0}\u{5d1}\u{5d2}".to_owned(),
+            FontId::default(),
+            Color32::WHITE,
+        );
+        let galley = layout(&mut fonts, pixels_per_point, job.into());
+
+        // Moving left in right-to-left text moves forward in the text:
+        let start = cursor::CCursor::new(0);
+        let moved = galley.cursor_left_one_character(&start);
+        assert_eq!(moved.index, 1);
+        assert_eq!(galley.cursor_right_one_character(&moved).index, 0);
+
+        // …but the logical movement is unaffected:
+        assert_eq!(galley.cursor_next_character(&start).index, 1);
+
+        // The cursor is drawn on the right side of the first char:
+        let first_rect = galley.pos_from_cursor(start);
+        let second_rect = galley.pos_from_cursor(moved);
+        assert!(second_rect.center().x < first_rect.center().x);
+    }
+
+    #[test]
+    fn test_no_row_break_within_cluster() {
+        let pixels_per_point = 1.0;
+        let mut fonts = FontsImpl::new(TextOptions::default(), FontDefinitions::default());
+
+        // A zero width joiner is shaped in the same cluster as the char before it:
+        let mut job = LayoutJob::simple(
+            "a\u{200D}a\u{200D}a\u{200D}".to_owned(),
+            FontId::default(),
+            Color32::WHITE,
+            1.0,
+        );
+        job.wrap.break_anywhere = true;
+        let galley = layout(&mut fonts, pixels_per_point, job.into());
+        for row in &galley.rows {
+            assert_eq!(row.glyphs.len(), 2, "Broke a row within a cluster");
+        }
+    }
+
+    #[test]
+    fn test_cluster_glyph_advances() {
+        let mut fonts = FontsImpl::new(TextOptions::default(), FontDefinitions::default());
+        let mut layout = |text: &str| {
+            let job =
+                LayoutJob::simple_singleline(text.to_owned(), FontId::default(), Color32::WHITE);
+            layout(&mut fonts, 1.0, job.into())
+        };
+
+        // One shaped glyph per char, so each char gets the advance of its own glyph,
+        // rather than an even share of the cluster:
+        let single = layout("a");
+        let joined = layout("a\u{200D}");
+        let glyphs = &joined.rows[0].glyphs;
+        assert_eq!(glyphs.len(), 2);
+        assert!(!glyphs[0].continues_cluster);
+        assert!(glyphs[1].continues_cluster);
+        assert_eq!(
+            glyphs[0].advance_width,
+            single.rows[0].glyphs[0].advance_width
+        );
+        assert_eq!(glyphs[1].advance_width, 0.0);
+    }
+
+    #[test]
+    fn test_font_features() {
+        let mut fonts = Fonts::new(TextOptions::default(), FontDefinitions::default());
+        let mut fonts = fonts.with_pixels_per_point(1.0);
+
+        let mut layout = |font_features: Vec<FontFeature>| {
+            let format = TextFormat {
+                font_features,
+                ..Default::default()
+            };
+            fonts.layout_job(LayoutJob::single_section("fi".to_owned(), format))
+        };
+
+        // The default font has an `fi` ligature:
+        let with_ligatures = layout(vec![]);
+        let without_ligatures = layout(FontFeature::NO_LIGATURES.to_vec());
+
+        // The galley cache must not mix them up:
+        assert_ne!(with_ligatures.size(), without_ligatures.size());
+
+        // We still have one glyph per char:
+        assert_eq!(with_ligatures.rows[0].glyphs.len(), 2);
+        assert_eq!(without_ligatures.rows[0].glyphs.len(), 2);
+        assert!(
+            with_ligatures.rows[0].glyphs[1].uv_rect.is_nothing(),
+            "The ligature should be drawn with the first char"
+        );
+        assert!(!without_ligatures.rows[0].glyphs[1].uv_rect.is_nothing());
+    }
+
+    #[test]
+    fn test_stylistic_set_tag() {
+        assert_eq!(FontFeature::stylistic_set(1).tag_str(), "ss01");
+        assert_eq!(FontFeature::stylistic_set(20).tag_str(), "ss20");
+        assert_eq!(FontFeature::TABULAR_NUMBERS.to_string(), "+tnum");
+    }
+
+    #[test]
+    fn test_variations_on_static_font() {
+        let mut fonts = Fonts::new(TextOptions::default(), FontDefinitions::default());
+        let mut fonts = fonts.with_pixels_per_point(1.0);
+
+        let regular = FontId::proportional(14.0);
+        let bold = regular.clone().bold();
+        assert_eq!(bold.variations.weight, Some(FontVariations::BOLD));
+
+        let regular_galley = fonts.layout_no_wrap("Hello".to_owned(), regular, Color32::WHITE);
+        let bold_galley = fonts.layout_no_wrap("Hello".to_owned(), bold, Color32::WHITE);
+
+        // Different galleys in the cache…
+        assert!(!Arc::ptr_eq(&regular_galley, &bold_galley));
+        // …but the built-in fonts don't have a weight axis, so the variations are ignored:
+        assert_eq!(regular_galley.size(), bold_galley.size());
+    }
+
+    #[test]
+    fn test_variations_on_variable_font() {
+        let mut definitions = FontDefinitions::empty();
+        definitions.font_data.insert(
+            "Cantarell".to_owned(),
+            Arc::new(FontData::from_static(include_bytes!(
+                "../../tests/fonts/Cantarell-VF.otf"
+            ))),
+        );
+        definitions
+            .families
+            .insert(FontFamily::Proportional, vec!["Cantarell".to_owned()]);
+        let mut fonts = Fonts::new(TextOptions::default(), definitions);
+        let mut fonts = fonts.with_pixels_per_point(1.0);
+
+        let mut width = |text: &str, weight: Option<f32>| {
+            let mut font_id = FontId::proportional(20.0);
+            font_id.variations.weight = weight;
+            fonts
+                .layout_no_wrap(text.to_owned(), font_id, Color32::WHITE)
+                .size()
+                .x
+        };
+
+        let regular = width("Hello", None);
+        let bold = width("Hello", Some(FontVariations::BOLD));
+        assert!(regular < bold, "Bold should be wider: {regular} vs {bold}");
+
+        // Tabs are laid out without shaping.
+        // Their width must not come from whatever weight was laid out first:
+        let light_tab = width("\t", Some(100.0));
+        let bold_tab = width("\t", Some(FontVariations::BOLD));
+        assert_ne!(light_tab, bold_tab);
+    }
+
+    #[test]
+    fn test_emoji_sequences() {
+        let mut fonts = Fonts::new(TextOptions::default(), FontDefinitions::default());
+        let mut fonts = fonts.with_pixels_per_point(1.0);
+
+        // A family emoji: man, ZWJ, woman, ZWJ, girl.
+        // Depending on the font this is one glyph or several, but it should never be broken apart:
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        let mut job = LayoutJob::single_section(
+            family.to_owned(),
+            TextFormat::simple(FontId::proportional(20.0), Color32::WHITE),
+        );
+        job.wrap.max_width = 1.0;
+        let galley = fonts.layout_job(job);
+        assert_eq!(galley.rows.len(), 1);
+        assert_eq!(galley.rows[0].glyphs.len(), family.chars().count());
+
+        // The emoji presentation selector is invisible, and not a replacement character:
+        let galley = fonts.layout_no_wrap(
+            "\u{2764}\u{FE0F}".to_owned(),
+            FontId::proportional(20.0),
+            Color32::WHITE,
+        );
+        let glyphs = &galley.rows[0].glyphs;
+        assert_eq!(glyphs.len(), 2);
+        assert!(!glyphs[0].uv_rect.is_nothing());
+        assert!(glyphs[1].uv_rect.is_nothing());
+
+        // The built-in emoji font is monochrome, so it should be tinted like any other text:
+        assert!(!glyphs[0].uv_rect.colored);
+        assert!(galley.rows[0].visuals.color_glyph_vertex_ranges.is_empty());
+    }
+
+    #[test]
+    fn test_color_glyphs() {
+        let mut definitions = FontDefinitions::empty();
+        definitions.font_data.insert(
+            "NotoColorEmoji".to_owned(),
+            Arc::new(FontData::from_static(include_bytes!(
+                "../../tests/fonts/NotoColorEmoji-Partial.ttf"
+            ))),
+        );
+        definitions.font_data.insert(
+            "Cantarell".to_owned(),
+            Arc::new(FontData::from_static(include_bytes!(
+                "../../tests/fonts/Cantarell-VF.otf"
+            ))),
+        );
+        definitions.families.insert(
+            FontFamily::Proportional,
+            vec!["NotoColorEmoji".to_owned(), "Cantarell".to_owned()],
+        );
+        let mut fonts = Fonts::new(TextOptions::default(), definitions);
+        let mut fonts = fonts.with_pixels_per_point(1.0);
+
+        // The copyright sign is a PNG glyph in the emoji font, the x comes from Cantarell:
+        let galley = fonts.layout_no_wrap(
+            "\u{A9}x\u{A9}".to_owned(),
+            FontId::proportional(20.0),
+            Color32::WHITE,
+        );
+        let row = &galley.rows[0];
+        let is_colored: Vec<bool> = row.glyphs.iter().map(|g| g.uv_rect.colored).collect();
+        assert_eq!(is_colored, [true, false, true]);
+
+        let visuals = &row.visuals;
+        assert_eq!(visuals.color_glyph_vertex_ranges.len(), 2);
+        for glyph in &row.glyphs {
+            let first_vertex = glyph.first_vertex as usize;
+            for vertex_index in first_vertex..first_vertex + 4 {
+                assert_eq!(
+                    visuals.is_color_glyph_vertex(vertex_index),
+                    glyph.uv_rect.colored
+                );
+            }
+        }
+        assert!(!visuals.is_color_glyph_vertex(visuals.glyph_vertex_range.end));
+    }
+
+    #[test]
+    fn test_language_and_script_fallbacks() {
+        let mut definitions = FontDefinitions::default();
+        definitions
+            .language_fallbacks
+            .insert("ja".to_owned(), vec!["Hack".to_owned()]);
+        definitions.families.insert(
+            FontFamily::Name("emoji-first".into()),
+            vec!["NotoEmoji-Regular".to_owned(), "Ubuntu-Light".to_owned()],
+        );
+        definitions
+            .script_fallbacks
+            .insert("Latn".to_owned(), vec!["Hack".to_owned()]);
+
+        let font_name = |options: TextOptions, language: Option<&str>, family: FontFamily| {
+            let mut fonts = Fonts::new(options, definitions.clone());
+            let mut fonts = fonts.with_pixels_per_point(1.0);
+            let mut format = TextFormat::simple(FontId::new(14.0, family), Color32::WHITE);
+            format.language = language.map(Language::new);
+            let galley =
+                fonts.layout_job(LayoutJob::single_section("\u{2194}a".to_owned(), format));
+            fonts
+                .glyph_font_names(&galley)
+                .into_iter()
+                .map(|(_, name)| name.unwrap_or_default().to_owned())
+                .collect::<Vec<_>>()
+        };
+
+        // The arrow is missing in the primary font (Ubuntu-Light), so we use a fallback:
+        let options = TextOptions::default();
+        let proportional = FontFamily::Proportional;
+        assert_eq!(
+            font_name(options, None, proportional.clone()),
+            ["NotoEmoji-Regular", "Ubuntu-Light"]
+        );
+        assert_eq!(
+            font_name(options, Some("ja-JP"), proportional.clone()),
+            ["Hack", "Ubuntu-Light"]
+        );
+        assert_eq!(
+            font_name(options, Some("zh"), proportional.clone()),
+            ["NotoEmoji-Regular", "Ubuntu-Light"]
+        );
+
+        let japanese = TextOptions {
+            language: Some(Language::new("ja")),
+            ..Default::default()
+        };
+        assert_eq!(
+            font_name(japanese, None, proportional),
+            ["Hack", "Ubuntu-Light"]
+        );
+
+        // The arrow is not Latin, but the `a` is:
+        assert_eq!(
+            font_name(options, None, FontFamily::Name("emoji-first".into())),
+            ["NotoEmoji-Regular", "Hack"]
+        );
+    }
+
+    #[test]
+    fn test_language_tag() {
+        let language = Language::new("zh_Hant-TW");
+        assert_eq!(language.as_str(), "zh-hant-tw");
+        assert_eq!(
+            language.fallback_chain().collect::<Vec<_>>(),
+            ["zh-hant-tw", "zh-hant", "zh"]
+        );
+        assert!(language.matches("zh-Hant"));
+        assert!(!language.matches("zh-Hans"));
+    }
+
+    fn row_texts(galley: &Galley) -> Vec<String> {
+        galley.rows.iter().map(|row| row.text()).collect()
+    }
+
+    #[test]
+    fn test_unicode_line_breaks() {
+        let mut fonts = FontsImpl::new(TextOptions::default(), FontDefinitions::default());
+        let text = "Breaking (after) «punctuation» well-known words.";
+        for max_width in [100.0, 130.0, 160.0, 200.0] {
+            let mut layout_job = LayoutJob::single_section(text.into(), TextFormat::default());
+            layout_job.wrap.max_width = max_width;
+            let galley = layout(&mut fonts, 1.0, layout_job.into());
+            for row in row_texts(&galley) {
+                assert!(
+                    !row.starts_with([')', '»', '-', '.']),
+                    "Bad row {row:?} at max_width {max_width}"
+                );
+                assert!(
+                    !row.trim_end().ends_with(['(', '«']),
+                    "Bad row {row:?} at max_width {max_width}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_soft_hyphen() {
+        let mut fonts = FontsImpl::new(TextOptions::default(), FontDefinitions::default());
+        let text = "extra\u{AD}ordinary";
+        let unbroken = layout(
+            &mut fonts,
+            1.0,
+            LayoutJob::single_section(text.into(), TextFormat::default()).into(),
+        );
+        assert_eq!(row_texts(&unbroken), [text]);
+        assert!(unbroken.rows[0].extra_glyphs.is_empty(), "No hyphen shown");
+
+        let mut layout_job = LayoutJob::single_section(text.into(), TextFormat::default());
+        layout_job.wrap.max_width = unbroken.size().x * 0.75;
+        let galley = layout(&mut fonts, 1.0, layout_job.into());
+        assert_eq!(row_texts(&galley), ["extra\u{AD}", "ordinary"]);
+
+        // The hyphen is shown after the soft hyphen, which becomes as wide as it:
+        let first_row = &galley.rows[0];
+        let soft_hyphen = first_row.glyphs.last().unwrap();
+        assert_eq!(first_row.extra_glyphs.len(), 1);
+        assert_eq!(first_row.extra_glyphs[0].glyph_index, 5);
+        assert!(0.0 < soft_hyphen.advance_width);
+        assert!((first_row.size.x - soft_hyphen.max_x()).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_hyphenation() {
+        let mut definitions = FontDefinitions::default();
+        definitions.hyphenation.insert(
+            "en".to_owned(),
+            Arc::new(Hyphenator::new(
+                "hy3ph he2n hena4 hen5at 1na n2at 1tio 2io o2n",
+            )),
+        );
+        let mut fonts = FontsImpl::new(TextOptions::default(), definitions);
+
+        let format = TextFormat {
+            language: Some(Language::new("en-US")),
+            ..Default::default()
+        };
+        let mut width_of = |text: &str| {
+            let job = LayoutJob::single_section(text.into(), format.clone());
+            layout(&mut fonts, 1.0, job.into()).size().x
+        };
+        let hyphenated_width = width_of("Long hyphen-");
+        assert!(width_of("hyphenation") < hyphenated_width);
+
+        for hyphenate in [false, true] {
+            let mut layout_job =
+                LayoutJob::single_section("Long hyphenation".into(), format.clone());
+            layout_job.wrap.max_width = hyphenated_width + 1.0;
+            layout_job.wrap.hyphenate = hyphenate;
+            let galley = layout(&mut fonts, 1.0, layout_job.into());
+            if hyphenate {
+                assert_eq!(row_texts(&galley), ["Long hyphen", "ation"]);
+                assert!((galley.rows[0].size.x - hyphenated_width).abs() < 1.0);
+            } else {
+                assert_eq!(row_texts(&galley), ["Long ", "hyphenation"]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_justify_between_words() {
+        let mut fonts = FontsImpl::new(TextOptions::default(), FontDefinitions::default());
+        let text = "Justified text is stretched between its words, not between its letters.";
+        let mut layout_job = LayoutJob::single_section(text.into(), TextFormat::default());
+        layout_job.wrap.max_width = 120.0;
+        layout_job.justify = true;
+        let justified = layout(&mut fonts, 1.0, layout_job.into());
+
+        let mut layout_job = LayoutJob::single_section(text.into(), TextFormat::default());
+        layout_job.wrap.max_width = 120.0;
+        let ragged = layout(&mut fonts, 1.0, layout_job.into());
+
+        assert_eq!(row_texts(&justified), row_texts(&ragged));
+        let first_row = &justified.rows[0];
+        assert_eq!(first_row.size.x, 120.0);
+        let first_word_len = first_row.text().find(' ').unwrap();
+        let distance = |row: &Row, i: usize| row.glyphs[i + 1].pos.x - row.glyphs[i].pos.x;
+        for i in 0..first_word_len - 1 {
+            // Only rounded to pixels:
+            assert!((distance(first_row, i) - distance(&ragged.rows[0], i)).abs() < 1.0);
+        }
+        let space = first_word_len;
+        assert!(distance(&ragged.rows[0], space) + 2.0 < distance(first_row, space));
+    }
+
+    #[test]
+    fn test_tab_stops() {
+        let mut fonts = FontsImpl::new(TextOptions::default(), FontDefinitions::default());
+        let mut layout_with_tab_stops = |text: &str, tab_stops: TabStops| {
+            let mut job = LayoutJob::single_section(text.into(), TextFormat::default());
+            job.tab_stops = tab_stops;
+            layout(&mut fonts, 1.0, job.into())
+        };
+        let glyph_x = |galley: &Galley, chr: char| {
+            let glyph = galley.rows[0].glyphs.iter().find(|g| g.chr == chr).unwrap();
+            glyph.pos.x
+        };
+
+        // By default, a tab is as wide as four spaces:
+        let default_tabs = layout_with_tab_stops("a\tb", TabStops::default());
+        let galley = layout_with_tab_stops("a\tb", TabStops::every_em(100.0 / 14.0));
+        assert!((glyph_x(&galley, 'b') - 100.0).abs() < 1.0);
+        assert!(glyph_x(&default_tabs, 'b') < glyph_x(&galley, 'b'));
+
+        let stops = TabStops::every_em(100.0 / 14.0)
+            .with_stop(170.0, TabAlign::Left)
+            .with_stop(120.0, TabAlign::Left);
+        assert_eq!(stops.stops()[0].offset, 120.0, "Sorted");
+        let galley = layout_with_tab_stops("a\tb\tc\td", stops);
+        assert!((glyph_x(&galley, 'b') - 120.0).abs() < 1.0);
+        assert!((glyph_x(&galley, 'c') - 170.0).abs() < 1.0);
+        assert!(
+            (glyph_x(&galley, 'd') - 200.0).abs() < 1.0,
+            "After the last stop, every interval"
+        );
+    }
+
+    #[test]
+    fn test_tab_stop_alignment() {
+        let mut fonts = FontsImpl::new(TextOptions::default(), FontDefinitions::default());
+        let mut layout_at_stop = |text: &str, align: TabAlign| {
+            let mut job = LayoutJob::single_section(text.into(), TextFormat::default());
+            job.tab_stops = TabStops::default().with_stop(100.0, align);
+            layout(&mut fonts, 1.0, job.into())
+        };
+        // The x range of the text after the tab:
+        let text_range = |galley: &Galley| {
+            let glyphs = &galley.rows[0].glyphs;
+            let tab = glyphs.iter().position(|g| g.chr == '\t').unwrap();
+            (glyphs[tab + 1].pos.x, glyphs.last().unwrap().max_x())
+        };
+
+        for text in ["x\tabc", "x\tMMMMM"] {
+            let (min_x, _) = text_range(&layout_at_stop(text, TabAlign::Left));
+            assert!((min_x - 100.0).abs() < 1.0, "Left {text:?}: {min_x}");
+
+            let (_, max_x) = text_range(&layout_at_stop(text, TabAlign::Right));
+            assert!((max_x - 100.0).abs() < 1.0, "Right {text:?}: {max_x}");
+
+            let (min_x, max_x) = text_range(&layout_at_stop(text, TabAlign::Center));
+            let center = f32::midpoint(min_x, max_x);
+            assert!((center - 100.0).abs() < 1.0, "Center {text:?}: {center}");
+        }
+
+        for number in ["1.25", "100.5", "7"] {
+            let galley = layout_at_stop(&format!("x\t{number}"), TabAlign::Decimal('.'));
+            let separator_x = galley.rows[0]
+                .glyphs
+                .iter()
+                .find(|g| g.chr == '.')
+                .map_or_else(|| text_range(&galley).1, |g| g.pos.x);
+            assert!((separator_x - 100.0).abs() < 1.0, "{number}: {separator_x}");
+        }
+
+        // A tab after the last stop keeps its default width:
+        let galley = layout_at_stop("A long first column\tx", TabAlign::Right);
+        let glyphs = &galley.rows[0].glyphs;
+        let tab = glyphs.iter().find(|g| g.chr == '\t').unwrap();
+        assert!(100.0 < tab.pos.x);
+        assert_eq!(text_range(&galley).0, tab.max_x());
+    }
+
+    #[test]
+    fn test_tab_stops_wrapped() {
+        let mut fonts = FontsImpl::new(TextOptions::default(), FontDefinitions::default());
+        let mut job = LayoutJob::single_section("a\tb word\tc".into(), TextFormat::default());
+        job.tab_stops = TabStops::default().with_stop(50.0, TabAlign::Left);
+        job.wrap.max_width = 80.0;
+        let galley = layout(&mut fonts, 1.0, job.into());
+
+        assert_eq!(galley.rows.len(), 2);
+        for (row, chr) in [(0, 'b'), (1, 'c')] {
+            let glyph = galley.rows[row].glyphs.iter().find(|g| g.chr == chr);
+            let x = glyph.unwrap().pos.x;
+            assert!(
+                (x - 50.0).abs() < 1.0,
+                "The stop is measured from the start of each row: {chr:?} at {x}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_placeholders() {
+        let mut fonts = FontsImpl::new(TextOptions::default(), FontDefinitions::default());
+        let mut job = LayoutJob::default();
+        job.append("Some text", 0.0, TextFormat::default());
+        let icon = job.append_placeholder(Placeholder::new([20.0, 30.0]), TextFormat::default());
+        job.append("after the icon", 0.0, TextFormat::default());
+        job.append("\n", 0.0, TextFormat::default());
+        let hanging = job.append_placeholder(
+            Placeholder::new([10.0, 10.0]).with_baseline(5.0),
+            TextFormat::default(),
+        );
+
+        let galley = layout(&mut fonts, 1.0, Arc::new(job.clone()));
+        let rects = galley.placeholder_rects();
+        assert_eq!(rects.len(), 2);
+
+        let (section_index, rect) = rects[0];
+        assert_eq!(section_index, icon);
+        assert_eq!(rect.size(), vec2(20.0, 30.0));
+        let first_row = &galley.rows[0];
+        assert!(30.0 <= first_row.height());
+        assert_eq!(rect.min.x, first_row.glyphs[9].pos.x);
+        assert_eq!(first_row.glyphs[10].pos.x, rect.max.x);
+
+        // Standing on the same baseline as the text:
+        let baseline = first_row.pos.y + first_row.glyphs[0].pos.y;
+        assert!((rect.max.y - baseline).abs() <= 1.0);
+
+        let (section_index, rect) = rects[1];
+        assert_eq!(section_index, hanging);
+        let baseline = galley.rows[1].pos.y + galley.rows[1].glyphs[0].pos.y;
+        assert_eq!(rect.center().y, baseline);
+
+        // Wrapping moves the placeholder to the next row:
+        job.wrap.max_width = first_row.glyphs[9].pos.x + 10.0;
+        let galley = layout(&mut fonts, 1.0, Arc::new(job));
+        let (_, rect) = galley.placeholder_rects()[0];
+        assert_eq!(rect.min.x, 0.0);
+        assert_eq!(
+            rect.min.y,
+            galley.rows[1].pos.y + galley.rows[1].glyphs[0].pos.y - 30.0
+        );
+
+        // Only placeholders get a rect, not text that happens to contain the placeholder char:
+        let mut job = LayoutJob::default();
+        let text = format!("{} office ", Placeholder::CHAR);
+        job.append(&text, 0.0, TextFormat::default());
+        let icon = job.append_placeholder(Placeholder::new([20.0, 30.0]), TextFormat::default());
+        let galley = layout(&mut fonts, 1.0, Arc::new(job));
+        let rects = galley.placeholder_rects();
+        assert_eq!(rects.len(), 1);
+        let (section_index, rect) = rects[0];
+        assert_eq!(section_index, icon);
+        assert_eq!(rect.min.x, galley.rows[0].glyphs.last().unwrap().pos.x);
+    }
+
+    #[test]
+    fn test_section_rects() {
+        let mut fonts = FontsImpl::new(TextOptions::default(), FontDefinitions::default());
+        let mut job = LayoutJob::default();
+        job.append("See ", 0.0, TextFormat::default());
+        let link = job.append_link(
+            "the documentation",
+            TextFormat::default(),
+            "https://docs.rs/egui",
+        );
+        job.append(" for more.", 0.0, TextFormat::default());
+        assert_eq!(
+            job.sections[link].link.as_deref(),
+            Some("https://docs.rs/egui")
+        );
+
+        let galley = layout(&mut fonts, 1.0, Arc::new(job.clone()));
+        let rects = galley.section_rects(link);
+        assert_eq!(rects.len(), 1);
+        let row = &galley.rows[0];
+        assert_eq!(rects[0].min.x, row.glyphs[4].pos.x);
+        assert!((rects[0].max.x - row.glyphs[21].pos.x).abs() < 1.0);
+        assert_eq!(rects[0].y_range(), row.rect().y_range());
+
+        // A link that is wrapped is on two rows:
+        job.wrap.max_width = row.glyphs[15].pos.x;
+        let galley = layout(&mut fonts, 1.0, Arc::new(job));
+        let rects = galley.section_rects(link);
+        assert_eq!(rects.len(), 2);
+        assert!(rects[0].max.y <= rects[1].min.y);
+        assert!(galley.section_rects(100).is_empty());
+        assert_eq!(galley.link_rects(), vec![(link..link + 1, rects)]);
+
+        // A link with a word in bold is still one link:
+        let mut job = LayoutJob::default();
+        job.append_link("the ", TextFormat::default(), "https://docs.rs");
+        job.append_link("bold", TextFormat::default(), "https://docs.rs");
+        job.append_link(" other", TextFormat::default(), "https://egui.rs");
+        let galley = layout(&mut fonts, 1.0, Arc::new(job));
+        let links: Vec<_> = galley
+            .link_rects()
+            .into_iter()
+            .map(|(link, _)| link)
+            .collect();
+        assert_eq!(links, [0..2, 2..3]);
+    }
 }
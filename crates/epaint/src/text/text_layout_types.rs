@@ -8,7 +8,10 @@ use super::{
     cursor::{CCursor, LayoutCursor},
     font::UvRect,
 };
-use crate::{Color32, FontId, Mesh, Stroke, text::FontsView};
+use crate::{
+    Color32, FontId, Mesh, Shadow, Stroke,
+    text::{FontFaceKey, FontFeature, FontsView, Language},
+};
 use emath::{Align, GuiRounding as _, NumExt as _, OrderedFloat, Pos2, Rect, Vec2, pos2, vec2};
 
 /// Describes the task of laying out text.
@@ -80,6 +83,24 @@ pub struct LayoutJob {
 
     /// Round output sizes using [`emath::GuiRounding`], to avoid rounding errors in layout code.
     pub round_output_to_gui: bool,
+
+    /// How to rasterize the glyphs.
+    ///
+    /// Use [`GlyphRendering::DistanceField`] for text that will be zoomed,
+    /// e.g. in a [`crate::Shape::Text`] with a large scale transform.
+    ///
+    /// Default: [`GlyphRendering::Bitmap`].
+    pub glyph_rendering: GlyphRendering,
+
+    /// Where each `\t` moves the text that follows it.
+    ///
+    /// By default a `\t` is as wide as [`crate::text::TAB_SIZE`] spaces.
+    pub tab_stops: TabStops,
+
+    /// Horizontal rows, or vertical columns?
+    ///
+    /// Default: [`WritingMode::Horizontal`].
+    pub writing_mode: WritingMode,
 }
 
 impl Default for LayoutJob {
@@ -94,6 +115,9 @@ impl Default for LayoutJob {
             halign: Align::LEFT,
             justify: false,
             round_output_to_gui: true,
+            glyph_rendering: GlyphRendering::Bitmap,
+            tab_stops: TabStops::default(),
+            writing_mode: WritingMode::Horizontal,
         }
     }
 }
@@ -103,11 +127,10 @@ impl LayoutJob {
     #[inline]
     pub fn simple(text: String, font_id: FontId, color: Color32, wrap_width: f32) -> Self {
         Self {
-            sections: vec![LayoutSection {
-                leading_space: 0.0,
-                byte_range: 0..text.len(),
-                format: TextFormat::simple(font_id, color),
-            }],
+            sections: vec![LayoutSection::new(
+                0..text.len(),
+                TextFormat::simple(font_id, color),
+            )],
             text,
             wrap: TextWrapping {
                 max_width: wrap_width,
@@ -122,11 +145,7 @@ impl LayoutJob {
     #[inline]
     pub fn simple_format(text: String, format: TextFormat) -> Self {
         Self {
-            sections: vec![LayoutSection {
-                leading_space: 0.0,
-                byte_range: 0..text.len(),
-                format,
-            }],
+            sections: vec![LayoutSection::new(0..text.len(), format)],
             text,
             break_on_newline: true,
             ..Default::default()
@@ -137,11 +156,10 @@ impl LayoutJob {
     #[inline]
     pub fn simple_singleline(text: String, font_id: FontId, color: Color32) -> Self {
         Self {
-            sections: vec![LayoutSection {
-                leading_space: 0.0,
-                byte_range: 0..text.len(),
-                format: TextFormat::simple(font_id, color),
-            }],
+            sections: vec![LayoutSection::new(
+                0..text.len(),
+                TextFormat::simple(font_id, color),
+            )],
             text,
             wrap: Default::default(),
             break_on_newline: false,
@@ -152,11 +170,7 @@ impl LayoutJob {
     #[inline]
     pub fn single_section(text: String, format: TextFormat) -> Self {
         Self {
-            sections: vec![LayoutSection {
-                leading_space: 0.0,
-                byte_range: 0..text.len(),
-                format,
-            }],
+            sections: vec![LayoutSection::new(0..text.len(), format)],
             text,
             wrap: Default::default(),
             break_on_newline: true,
@@ -176,11 +190,48 @@ impl LayoutJob {
         let byte_range = start..self.text.len();
         self.sections.push(LayoutSection {
             leading_space,
-            byte_range,
-            format,
+            ..LayoutSection::new(byte_range, format)
         });
     }
 
+    /// Add a section of text that the user can click, e.g. a hyperlink.
+    ///
+    /// `link` is stored in [`LayoutSection::link`], and can be a URL or any tag you like.
+    ///
+    /// Returns the index of the new section.
+    pub fn append_link(
+        &mut self,
+        text: &str,
+        format: TextFormat,
+        link: impl Into<String>,
+    ) -> usize {
+        let start = self.text.len();
+        self.text += text;
+        let byte_range = start..self.text.len();
+        self.sections.push(LayoutSection {
+            link: Some(link.into()),
+            ..LayoutSection::new(byte_range, format)
+        });
+        self.sections.len() - 1
+    }
+
+    /// Reserve space for something else in the text, like an image or a widget.
+    ///
+    /// The space is represented by the OBJECT REPLACEMENT CHARACTER (`U+FFFC`) in [`Self::text`].
+    /// The `format` is used for the background and underline, and to find the baseline.
+    ///
+    /// Returns the index of the new section, which identifies the placeholder in [`Galley::placeholder_rects`].
+    pub fn append_placeholder(&mut self, placeholder: Placeholder, format: TextFormat) -> usize {
+        let start = self.text.len();
+        self.text.push(Placeholder::CHAR);
+        let byte_range = start..self.text.len();
+        self.sections.push(LayoutSection {
+            placeholder: Some(placeholder),
+            ..LayoutSection::new(byte_range, format)
+        });
+        self.sections.len() - 1
+    }
+
     /// The height of the tallest font used in the job.
     ///
     /// Returns a value rounded to [`emath::GUI_ROUNDING`].
@@ -217,6 +268,9 @@ impl std::hash::Hash for LayoutJob {
             halign,
             justify,
             round_output_to_gui,
+            glyph_rendering,
+            tab_stops,
+            writing_mode,
         } = self;
 
         text.hash(state);
@@ -227,11 +281,179 @@ impl std::hash::Hash for LayoutJob {
         halign.hash(state);
         justify.hash(state);
         round_output_to_gui.hash(state);
+        glyph_rendering.hash(state);
+        tab_stops.hash(state);
+        writing_mode.hash(state);
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// The direction text flows in, see [`LayoutJob::writing_mode`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum WritingMode {
+    /// Rows of text from top to bottom, like in English.
+    #[default]
+    Horizontal,
+
+    /// Columns of text from top to bottom, with the columns going from right to left,
+    /// like in traditional Japanese and Chinese.
+    ///
+    /// CJK characters stay upright, while other text (e.g. Latin) is turned 90° clockwise.
+    /// Punctuation uses its vertical forms (e.g. `︒` for `。`) if the font has them.
+    ///
+    /// [`TextWrapping::max_width`] limits the height of the columns.
+    ///
+    /// The [`Galley::rows`] of vertical text are laid out as if the text was horizontal,
+    /// and turned 90° clockwise when painted.
+    /// Use [`Galley::pos_from_row_space`] to find out where something in a row ends up.
+    VerticalRightToLeft,
+}
+
+// ----------------------------------------------------------------------------
+
+/// How the glyphs of a [`LayoutJob`] are rasterized into the font atlas.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum GlyphRendering {
+    /// Rasterize each glyph at the size it is shown at, with hinting and subpixel positioning.
+    ///
+    /// This gives the sharpest text, but it gets blurry if the text is scaled up afterwards
+    /// (e.g. in a zoomed-in `egui::Scene`).
+    #[default]
+    Bitmap,
+
+    /// Store a signed distance field of each glyph, and let the renderer find the edges.
+    ///
+    /// The glyphs stay crisp at any scale, and each glyph is only stored once for all font sizes,
+    /// but small text is a bit softer than with [`Self::Bitmap`].
+    /// Color glyphs (e.g. emojis) are drawn with their monochrome outlines, if they have any.
+    ///
+    /// The glyphs are painted with [`crate::TextureId::DISTANCE_FIELD_FONT`],
+    /// which requires support from the backend (see [`crate::textures::TextureOptions::distance_field`]).
+    DistanceField,
+}
+
+// ----------------------------------------------------------------------------
+
+/// The tab stops of a [`LayoutJob`], used to align text into columns.
+///
+/// Each `\t` moves the text after it (up to the next `\t` or the end of the paragraph)
+/// to the first tab stop to the right of the `\t`.
+///
+/// The tab stops are measured from the start of each row, so wrapped rows get their own columns.
+///
+/// ```
+/// # use epaint::text::{TabAlign, TabStops};
+/// // Right-align the numbers at 200 points, and put a tab stop every 4 ems after that:
+/// let tab_stops = TabStops::every_em(4.0).with_stop(200.0, TabAlign::Right);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct TabStops {
+    /// Tab stops at specific positions, sorted by [`TabStop::offset`].
+    ///
+    /// Private, so that [`Self::with_stop`] can keep it sorted.
+    stops: Vec<TabStop>,
+
+    /// After the last of [`Self::stops()`], there is a left-aligned tab stop every this many ems,
+    /// where an em is the font size of the `\t`.
+    ///
+    /// If `None` (default), a `\t` after the last of [`Self::stops()`] is as wide as [`crate::text::TAB_SIZE`] spaces.
+    pub interval_em: Option<f32>,
+}
+
+impl std::hash::Hash for TabStops {
+    #[inline]
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let Self { stops, interval_em } = self;
+        stops.hash(state);
+        interval_em.map(emath::OrderedFloat).hash(state);
+    }
+}
+
+impl TabStops {
+    /// A left-aligned tab stop every `interval_em` ems.
+    ///
+    /// With a monospace font and `interval_em = 4.0 * char_width_in_em`, this is
+    /// how tabs usually work in code editors.
+    #[inline]
+    pub fn every_em(interval_em: f32) -> Self {
+        Self {
+            stops: vec![],
+            interval_em: Some(interval_em),
+        }
+    }
+
+    /// Add a tab stop `offset` points from the start of the row.
+    #[inline]
+    pub fn with_stop(mut self, offset: f32, align: TabAlign) -> Self {
+        let index = self.stops.partition_point(|stop| stop.offset <= offset);
+        self.stops.insert(index, TabStop { offset, align });
+        self
+    }
+
+    /// The tab stops added with [`Self::with_stop`], sorted by [`TabStop::offset`].
+    #[inline]
+    pub fn stops(&self) -> &[TabStop] {
+        &self.stops
+    }
+
+    /// No tab stops: a `\t` is as wide as [`crate::text::TAB_SIZE`] spaces.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.stops.is_empty() && self.interval_em.is_none()
+    }
+}
+
+/// See [`TabStops`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct TabStop {
+    /// Position of the tab stop in points, from the start of the row.
+    pub offset: f32,
+
+    /// How the text after the `\t` is aligned to [`Self::offset`].
+    pub align: TabAlign,
+}
+
+impl std::hash::Hash for TabStop {
+    #[inline]
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let Self { offset, align } = self;
+        emath::OrderedFloat(*offset).hash(state);
+        align.hash(state);
     }
 }
 
+/// How the text after a `\t` is aligned to its [`TabStop`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum TabAlign {
+    /// The text starts at the tab stop.
+    #[default]
+    Left,
+
+    /// The text ends at the tab stop.
+    Right,
+
+    /// The text is centered on the tab stop.
+    Center,
+
+    /// The given decimal separator (e.g. `.`) is placed at the tab stop,
+    /// so that a column of numbers lines up.
+    ///
+    /// Text without the separator ends at the tab stop, like with [`Self::Right`].
+    Decimal(char),
+}
+
 // ----------------------------------------------------------------------------
 
+/// A range of the text in a [`LayoutJob`], and how to lay it out.
+///
+/// Prefer creating these with [`Self::new`] (or the `append` methods of [`LayoutJob`])
+/// over a struct literal, since more fields may be added in the future.
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct LayoutSection {
@@ -242,6 +464,32 @@ pub struct LayoutSection {
     pub byte_range: Range<usize>,
 
     pub format: TextFormat,
+
+    /// If set, each `char` of this section is laid out as an empty space of this size,
+    /// to be filled with something else.
+    ///
+    /// See [`LayoutJob::append_placeholder`].
+    pub placeholder: Option<Placeholder>,
+
+    /// If set, this section is interactive, e.g. a hyperlink.
+    ///
+    /// This can be a URL or any other tag identifying the span.
+    /// A `Label` reports which section is hovered and clicked,
+    /// and shows a pointing hand cursor over sections with a link.
+    pub link: Option<String>,
+}
+
+impl LayoutSection {
+    /// A section of plain text, without leading space, placeholder or link.
+    pub fn new(byte_range: Range<usize>, format: TextFormat) -> Self {
+        Self {
+            leading_space: 0.0,
+            byte_range,
+            format,
+            placeholder: None,
+            link: None,
+        }
+    }
 }
 
 impl std::hash::Hash for LayoutSection {
@@ -251,10 +499,64 @@ impl std::hash::Hash for LayoutSection {
             leading_space,
             byte_range,
             format,
+            placeholder,
+            link,
         } = self;
         OrderedFloat(*leading_space).hash(state);
         byte_range.hash(state);
         format.hash(state);
+        placeholder.hash(state);
+        link.hash(state);
+    }
+}
+
+/// Space reserved in the text of a [`LayoutJob`] for an inline image, widget, etc.
+///
+/// The placeholder is placed on the rows like a glyph,
+/// so it moves along with the text when it is wrapped.
+/// Use [`Galley::placeholder_rects`] to find where it ended up.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Placeholder {
+    /// The size of the placeholder, in points.
+    pub size: Vec2,
+
+    /// Distance from the top of the placeholder down to the baseline of the text.
+    ///
+    /// By default this is `size.y`, so the placeholder stands on the baseline, like an inline image in HTML.
+    /// Use a smaller value to have it hang below the baseline.
+    pub baseline: f32,
+}
+
+impl std::hash::Hash for Placeholder {
+    #[inline]
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let Self { size, baseline } = self;
+        OrderedFloat(size.x).hash(state);
+        OrderedFloat(size.y).hash(state);
+        OrderedFloat(*baseline).hash(state);
+    }
+}
+
+impl Placeholder {
+    /// The character used for placeholders by [`LayoutJob::append_placeholder`]: OBJECT REPLACEMENT CHARACTER.
+    pub const CHAR: char = '\u{FFFC}';
+
+    /// A placeholder standing on the baseline.
+    #[inline]
+    pub fn new(size: impl Into<Vec2>) -> Self {
+        let size = size.into();
+        Self {
+            size,
+            baseline: size.y,
+        }
+    }
+
+    /// Set the distance from the top of the placeholder down to the baseline of the text.
+    #[inline]
+    pub fn with_baseline(mut self, baseline: f32) -> Self {
+        self.baseline = baseline;
+        self
     }
 }
 
@@ -292,10 +594,39 @@ pub struct TextFormat {
 
     pub italics: bool,
 
+    /// A line under the text, where the font suggests (from its `post` table).
     pub underline: Stroke,
 
+    /// A line through the text, where the font suggests (from its `OS/2` table).
     pub strikethrough: Stroke,
 
+    /// A line above the text, at the ascent of the font.
+    pub overline: Stroke,
+
+    /// How to draw the [`Self::underline`], [`Self::strikethrough`] and [`Self::overline`].
+    ///
+    /// Default: [`DecorationStyle::Solid`].
+    pub decoration_style: DecorationStyle,
+
+    /// If `true`, the [`Self::underline`], [`Self::strikethrough`] and [`Self::overline`]
+    /// are as thick as the font suggests, and the width of their strokes is ignored.
+    ///
+    /// Default: `false`.
+    pub decoration_thickness_from_font: bool,
+
+    /// An outline around each glyph, to keep the text readable on top of busy backgrounds
+    /// like images or maps.
+    ///
+    /// Color glyphs (e.g. emojis) get no outline.
+    pub outline: Stroke,
+
+    /// A drop shadow under each glyph.
+    ///
+    /// With [`GlyphRendering::DistanceField`] the shadow has a sharp edge in the middle of the blur.
+    ///
+    /// Color glyphs (e.g. emojis) get no shadow.
+    pub shadow: Shadow,
+
     /// If you use a small font and [`Align::TOP`] you
     /// can get the effect of raised text.
     ///
@@ -306,6 +637,21 @@ pub struct TextFormat {
     /// around a common center-line, which is nice when mixining emojis
     /// and normal text in e.g. a button.
     pub valign: Align,
+
+    /// OpenType features to apply when shaping this text, e.g. [`FontFeature::TABULAR_NUMBERS`].
+    ///
+    /// These are applied after any [`crate::text::FontTweak::font_features`] of the font.
+    ///
+    /// Default: empty.
+    pub font_features: Vec<FontFeature>,
+
+    /// The language of the text, e.g. `"ja"` or `"zh-Hant"`.
+    ///
+    /// This decides which [`crate::text::FontDefinitions::language_fallbacks`] are used,
+    /// and lets fonts pick language-specific glyph variants.
+    ///
+    /// Default: `None`, meaning [`crate::text::TextOptions::language`] is used.
+    pub language: Option<Language>,
 }
 
 impl Default for TextFormat {
@@ -321,7 +667,14 @@ impl Default for TextFormat {
             italics: false,
             underline: Stroke::NONE,
             strikethrough: Stroke::NONE,
+            overline: Stroke::NONE,
+            decoration_style: DecorationStyle::Solid,
+            decoration_thickness_from_font: false,
+            outline: Stroke::NONE,
+            shadow: Shadow::NONE,
             valign: Align::BOTTOM,
+            font_features: Vec::new(),
+            language: None,
         }
     }
 }
@@ -339,7 +692,14 @@ impl std::hash::Hash for TextFormat {
             italics,
             underline,
             strikethrough,
+            overline,
+            decoration_style,
+            decoration_thickness_from_font,
+            outline,
+            shadow,
             valign,
+            font_features,
+            language,
         } = self;
         font_id.hash(state);
         emath::OrderedFloat(*extra_letter_spacing).hash(state);
@@ -352,7 +712,14 @@ impl std::hash::Hash for TextFormat {
         italics.hash(state);
         underline.hash(state);
         strikethrough.hash(state);
+        overline.hash(state);
+        decoration_style.hash(state);
+        decoration_thickness_from_font.hash(state);
+        outline.hash(state);
+        shadow.hash(state);
         valign.hash(state);
+        font_features.hash(state);
+        language.hash(state);
     }
 }
 
@@ -367,6 +734,44 @@ impl TextFormat {
     }
 }
 
+/// How to draw the lines under, through and above text, see [`TextFormat::decoration_style`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum DecorationStyle {
+    /// A single straight line.
+    #[default]
+    Solid,
+
+    /// Two straight lines.
+    Double,
+
+    /// A row of dots.
+    Dotted,
+
+    /// A line of dashes.
+    Dashed,
+
+    /// A wavy line, e.g. for marking spelling mistakes.
+    Wavy,
+}
+
+/// Where a font suggests putting the underline and strikethrough.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct DecorationMetrics {
+    /// Distance from the baseline down to the center of the underline, in points.
+    pub underline_offset: f32,
+
+    /// Thickness of the underline, in points.
+    pub underline_thickness: f32,
+
+    /// Distance from the baseline up to the center of the strikethrough, in points.
+    pub strikethrough_offset: f32,
+
+    /// Thickness of the strikethrough, in points.
+    pub strikethrough_thickness: f32,
+}
+
 // ----------------------------------------------------------------------------
 
 /// How to wrap and elide text.
@@ -432,6 +837,25 @@ pub struct TextWrapping {
     ///
     /// If not set, no character will be used (but the text will still be elided).
     pub overflow_character: Option<char>,
+
+    /// Which part of the text to leave out when it doesn't fit on a single row.
+    ///
+    /// Only used when [`Self::max_rows`] is `1`.
+    /// Text with more than one paragraph is always elided at the end.
+    ///
+    /// Default: [`Elision::End`].
+    pub elision: Elision,
+
+    /// If `true`: break long words with a hyphen when that makes for better filled rows.
+    ///
+    /// This uses the hyphenation dictionary for the language of the text
+    /// (see [`crate::text::FontDefinitions::hyphenation`]), so does nothing if there is none.
+    ///
+    /// Regardless of this, rows may always be broken at a soft hyphen (`U+00AD`),
+    /// which then shows as a hyphen.
+    ///
+    /// Default: `false`.
+    pub hyphenate: bool,
 }
 
 impl std::hash::Hash for TextWrapping {
@@ -442,11 +866,15 @@ impl std::hash::Hash for TextWrapping {
             max_rows,
             break_anywhere,
             overflow_character,
+            elision,
+            hyphenate,
         } = self;
         emath::OrderedFloat(*max_width).hash(state);
         max_rows.hash(state);
         break_anywhere.hash(state);
         overflow_character.hash(state);
+        elision.hash(state);
+        hyphenate.hash(state);
     }
 }
 
@@ -457,10 +885,36 @@ impl Default for TextWrapping {
             max_rows: usize::MAX,
             break_anywhere: false,
             overflow_character: Some('…'),
+            elision: Elision::End,
+            hyphenate: false,
         }
     }
 }
 
+/// Where to elide text that doesn't fit, see [`TextWrapping::elision`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Elision {
+    /// Keep the start of the text: `"A long sent…"`
+    #[default]
+    End,
+
+    /// Keep the end of the text: `"…ong sentence"`
+    Start,
+
+    /// Keep both the start and the end of the text: `"A lon…ntence"`
+    ///
+    /// Useful for long identifiers.
+    Middle,
+
+    /// Keep the file name at the end of a path, and as much of the start as fits:
+    /// `"/home/…/main.rs"`
+    ///
+    /// Both `/` and `\` are recognized as path separators.
+    /// Works like [`Self::Middle`] if the file name doesn't fit.
+    Path,
+}
+
 impl TextWrapping {
     /// Create a [`TextWrapping`] from a [`TextWrapMode`] and an available width.
     pub fn from_wrap_mode_and_width(mode: TextWrapMode, max_width: f32) -> Self {
@@ -524,14 +978,19 @@ pub struct Galley {
     /// Rows of text, from top to bottom, and their offsets.
     ///
     /// The number of characters in all rows sum up to `job.text.chars().count()`
-    /// unless [`Self::elided`] is `true`.
+    /// unless some text was [`Self::elided`].
     ///
     /// Note that a paragraph (a piece of text separated with `\n`)
     /// can be split up into multiple rows.
     pub rows: Vec<PlacedRow>,
 
-    /// Set to true the text was truncated due to [`TextWrapping::max_rows`].
-    pub elided: bool,
+    /// If the text was truncated due to [`TextWrapping::max_rows`],
+    /// this is the byte range of [`LayoutJob::text`] that was left out.
+    ///
+    /// Where the text was cut depends on [`TextWrapping::elision`].
+    /// The left out text is replaced with [`TextWrapping::overflow_character`], if any.
+    /// Use [`Self::text_char_index`] to find where a cursor is in the text.
+    pub elided: Option<Range<usize>>,
 
     /// Bounding rect.
     ///
@@ -541,6 +1000,9 @@ pub struct Galley {
     /// * [`Align::LEFT`]: `rect.left() == 0.0`
     /// * [`Align::Center`]: `rect.center() == 0.0`
     /// * [`Align::RIGHT`]: `rect.right() == 0.0`
+    ///
+    /// For vertical text ([`WritingMode::VerticalRightToLeft`]) this is the rect of the turned text,
+    /// so `rect.left()` is always 0.0 instead, and the alignment is vertical.
     pub rect: Rect,
 
     /// Tight bounding box around all the meshes in all the rows.
@@ -615,9 +1077,18 @@ pub struct Row {
     /// adjust `section_index` when concatting.
     pub(crate) section_index_at_start: u32,
 
-    /// One for each `char`.
+    /// One for each `char`, in logical order.
+    ///
+    /// For right-to-left text the glyphs are positioned right-to-left,
+    /// so use [`Glyph::pos`] rather than the order of this `Vec` for anything visual.
     pub glyphs: Vec<Glyph>,
 
+    /// Glyphs that text shaping produced in addition to one per `char`,
+    /// e.g. for a Tamil vowel sign that is drawn in two parts.
+    ///
+    /// Sorted by [`ExtraGlyph::glyph_index`].
+    pub extra_glyphs: Vec<ExtraGlyph>,
+
     /// Logical size based on font heights etc.
     /// Includes leading and trailing whitespace.
     pub size: Vec2,
@@ -648,6 +1119,13 @@ pub struct RowVisuals {
     ///
     /// The glyph vertices comes after backgrounds (if any), but before any underlines and strikethrough.
     pub glyph_vertex_range: Range<usize>,
+
+    /// The ranges of vertices in the mesh that contain color glyphs (e.g. emojis).
+    ///
+    /// These are all within [`Self::glyph_vertex_range`],
+    /// sorted and non-overlapping.
+    /// Color glyphs are drawn with their own colors, so they should not be tinted by any text color.
+    pub color_glyph_vertex_ranges: Vec<Range<usize>>,
 }
 
 impl Default for RowVisuals {
@@ -657,10 +1135,23 @@ impl Default for RowVisuals {
             mesh_bounds: Rect::NOTHING,
             glyph_index_start: 0,
             glyph_vertex_range: 0..0,
+            color_glyph_vertex_ranges: vec![],
         }
     }
 }
 
+impl RowVisuals {
+    /// Is this vertex part of a color glyph (e.g. an emoji)?
+    #[inline]
+    pub fn is_color_glyph_vertex(&self, vertex_index: usize) -> bool {
+        let ranges = &self.color_glyph_vertex_ranges;
+        let i = ranges.partition_point(|range| range.end <= vertex_index);
+        ranges
+            .get(i)
+            .is_some_and(|range| range.contains(&vertex_index))
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Glyph {
@@ -692,6 +1183,12 @@ pub struct Glyph {
     /// The row/line height of the sub-font within the font (`FontFace`).
     pub font_face_height: f32,
 
+    /// Is this part of the same shaping cluster as the glyph before it in [`Row::glyphs`],
+    /// e.g. the second letter of a ligature?
+    ///
+    /// Rows are never broken in the middle of a cluster.
+    pub continues_cluster: bool,
+
     /// Position and size of the glyph in the font texture, in texels.
     pub uv_rect: UvRect,
 
@@ -704,9 +1201,25 @@ pub struct Glyph {
 
     /// Which is our first vertex in [`RowVisuals::mesh`].
     pub first_vertex: u32,
+
+    /// The embedding level from the Unicode Bidirectional Algorithm.
+    ///
+    /// Even levels are left-to-right, odd levels are right-to-left.
+    pub bidi_level: u8,
+
+    /// The font face this glyph was rendered with.
+    ///
+    /// Useful for debugging font fallback, see [`crate::text::FontsView::font_face_name`].
+    pub font_face: FontFaceKey,
 }
 
 impl Glyph {
+    /// Is this part of right-to-left text, e.g. Arabic or Hebrew?
+    #[inline]
+    pub fn is_rtl(&self) -> bool {
+        self.bidi_level % 2 == 1
+    }
+
     #[inline]
     pub fn size(&self) -> Vec2 {
         Vec2::new(self.advance_width, self.line_height)
@@ -724,6 +1237,19 @@ impl Glyph {
     }
 }
 
+/// An additional glyph belonging to a [`Glyph`], see [`Row::extra_glyphs`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ExtraGlyph {
+    /// Index of the [`Glyph`] in [`Row::glyphs`] this is drawn together with.
+    ///
+    /// [`UvRect::offset`] is relative to the position of that glyph.
+    pub glyph_index: u32,
+
+    /// Position and size of the glyph in the font texture, in texels.
+    pub uv_rect: UvRect,
+}
+
 // ----------------------------------------------------------------------------
 
 impl Row {
@@ -738,9 +1264,33 @@ impl Row {
         self.glyphs.len()
     }
 
+    /// Does this row contain any right-to-left text?
+    ///
+    /// If so, the glyphs are not in visual order.
+    pub fn has_rtl(&self) -> bool {
+        self.glyphs.iter().any(Glyph::is_rtl)
+    }
+
     /// Closest char at the desired x coordinate in row-relative coordinates.
     /// Returns something in the range `[0, char_count_excluding_newline()]`.
     pub fn char_at(&self, desired_x: f32) -> usize {
+        if self.has_rtl() {
+            // Find the closest glyph, then pick the side of it that is closest:
+            let closest = self.glyphs.iter().enumerate().min_by(|(_, a), (_, b)| {
+                let a = (a.logical_rect().center().x - desired_x).abs();
+                let b = (b.logical_rect().center().x - desired_x).abs();
+                a.total_cmp(&b)
+            });
+            if let Some((i, glyph)) = closest {
+                let left_half = desired_x < glyph.logical_rect().center().x;
+                return if left_half == glyph.is_rtl() {
+                    i + 1
+                } else {
+                    i
+                };
+            }
+        }
+
         for (i, glyph) in self.glyphs.iter().enumerate() {
             if desired_x < glyph.logical_rect().center().x {
                 return i;
@@ -749,9 +1299,20 @@ impl Row {
         self.char_count_excluding_newline()
     }
 
+    /// The x coordinate of a cursor placed before the char at `column`.
+    ///
+    /// For right-to-left text, this is on the right side of that char.
     pub fn x_offset(&self, column: usize) -> f32 {
         if let Some(glyph) = self.glyphs.get(column) {
-            glyph.pos.x
+            if glyph.is_rtl() {
+                glyph.max_x()
+            } else {
+                glyph.pos.x
+            }
+        } else if let Some(last) = self.glyphs.last()
+            && last.is_rtl()
+        {
+            last.pos.x
         } else {
             self.size.x
         }
@@ -813,6 +1374,244 @@ impl Galley {
         }
     }
 
+    /// Where the [`Placeholder`]s of the job ended up, relative to the galley position.
+    ///
+    /// Returns the section index of each placeholder (see [`LayoutJob::append_placeholder`])
+    /// together with its rectangle, in the order of the text.
+    /// Placeholders that were elided are left out.
+    pub fn placeholder_rects(&self) -> Vec<(usize, Rect)> {
+        let mut placeholder_sections = self
+            .job
+            .sections
+            .iter()
+            .enumerate()
+            .filter_map(|(index, section)| Some((index, section, section.placeholder?)))
+            .peekable();
+        if placeholder_sections.peek().is_none() {
+            return vec![];
+        }
+
+        // The placeholder glyphs, in the order of the text:
+        let mut glyphs = self.rows.iter().flat_map(|placed_row| {
+            placed_row
+                .glyphs
+                .iter()
+                .filter(|glyph| glyph.font_face == FontFaceKey::PLACEHOLDER)
+                .map(move |glyph| (placed_row, glyph))
+        });
+
+        let mut rects = vec![];
+        for (section_index, section, placeholder) in placeholder_sections {
+            let section_text = &self.job.text[section.byte_range.clone()];
+            for (offset, _) in section_text.char_indices() {
+                let byte_index = section.byte_range.start + offset;
+                if self
+                    .elided
+                    .as_ref()
+                    .is_some_and(|elided| elided.contains(&byte_index))
+                {
+                    continue; // No glyph
+                }
+                let Some((placed_row, glyph)) = glyphs.next() else {
+                    break; // Elided at the end
+                };
+                let min = placed_row.pos + vec2(glyph.pos.x, glyph.pos.y - placeholder.baseline);
+                let rect = Rect::from_min_size(min, placeholder.size);
+                rects.push((section_index, self.rect_from_row_space(rect)));
+            }
+        }
+        rects
+    }
+
+    /// The area covered by a section of the job, relative to the galley position.
+    ///
+    /// Returns one rectangle for each row the section is on, spanning the height of the row.
+    /// Parts of the section that were elided are left out.
+    pub fn section_rects(&self, section_index: usize) -> Vec<Rect> {
+        let Some(section) = self.job.sections.get(section_index) else {
+            return vec![];
+        };
+
+        let glyphs = self.glyph_of_each_char();
+        let first_char_index = self.job.text[..section.byte_range.start].chars().count();
+        self.rects_of_chars(&glyphs, first_char_index, section.byte_range.clone())
+    }
+
+    /// The links of the text, in order: the sections of each link,
+    /// and one rectangle for each row it is on (like [`Self::section_rects`]).
+    ///
+    /// Consecutive sections with the same [`LayoutSection::link`] make up one link,
+    /// e.g. a link with a word in bold.
+    ///
+    /// This is faster than calling [`Self::section_rects`] for each link.
+    pub fn link_rects(&self) -> Vec<(Range<usize>, Vec<Rect>)> {
+        let sections = &self.job.sections;
+        let mut links: Vec<Range<usize>> = vec![];
+        for (section_index, section) in sections.iter().enumerate() {
+            if section.link.is_none() {
+                continue;
+            }
+            match links.last_mut() {
+                Some(link)
+                    if link.end == section_index
+                        && sections[section_index - 1].link == section.link
+                        && sections[section_index - 1].byte_range.end
+                            == section.byte_range.start =>
+                {
+                    link.end += 1;
+                }
+                _ => links.push(section_index..section_index + 1),
+            }
+        }
+        if links.is_empty() {
+            return vec![];
+        }
+
+        let glyphs = self.glyph_of_each_char();
+        let text = &self.job.text;
+        let (mut byte_index, mut char_index) = (0, 0);
+        links
+            .into_iter()
+            .map(|link| {
+                let start = sections[link.start].byte_range.start;
+                let end = sections[link.end - 1].byte_range.end;
+                if start < byte_index {
+                    (byte_index, char_index) = (0, 0); // The sections are out of order
+                }
+                char_index += text[byte_index..start].chars().count();
+                byte_index = start;
+                let rects = self.rects_of_chars(&glyphs, char_index, start..end);
+                (link, rects)
+            })
+            .collect()
+    }
+
+    /// One rectangle for each row that the chars in `byte_range` are on.
+    fn rects_of_chars(
+        &self,
+        glyphs: &[Option<(&PlacedRow, &Glyph)>],
+        first_char_index: usize,
+        byte_range: Range<usize>,
+    ) -> Vec<Rect> {
+        let section_text = &self.job.text[byte_range];
+
+        let mut rects: Vec<(&PlacedRow, Rect)> = vec![];
+        for (i, chr) in section_text.chars().enumerate() {
+            let Some(Some((placed_row, glyph))) = glyphs.get(first_char_index + i) else {
+                continue;
+            };
+            if glyph.chr != chr {
+                continue; // Elided
+            }
+            let rect = Rect::from_x_y_ranges(
+                placed_row.pos.x + glyph.pos.x..=placed_row.pos.x + glyph.max_x(),
+                placed_row.min_y()..=placed_row.max_y(),
+            );
+            match rects.last_mut() {
+                Some((last_row, last_rect)) if std::ptr::eq(*last_row, *placed_row) => {
+                    *last_rect = last_rect.union(rect);
+                }
+                _ => rects.push((placed_row, rect)),
+            }
+        }
+        rects
+            .into_iter()
+            .map(|(_, rect)| self.rect_from_row_space(rect))
+            .collect()
+    }
+
+    /// Is this text laid out in vertical columns?
+    #[inline]
+    pub fn is_vertical(&self) -> bool {
+        self.job.writing_mode == WritingMode::VerticalRightToLeft
+    }
+
+    /// Where a position in the coordinate system of the [`Self::rows`] ends up,
+    /// relative to the galley position.
+    ///
+    /// This is the same position, except for vertical text ([`WritingMode::VerticalRightToLeft`]),
+    /// where the rows are turned 90° clockwise into columns.
+    #[inline]
+    pub fn pos_from_row_space(&self, pos: Pos2) -> Pos2 {
+        if self.is_vertical() {
+            pos2(self.rect.max.x - pos.y, pos.x)
+        } else {
+            pos
+        }
+    }
+
+    /// Like [`Self::pos_from_row_space`], but for a rectangle.
+    #[inline]
+    pub fn rect_from_row_space(&self, rect: Rect) -> Rect {
+        if self.is_vertical() {
+            Rect::from_two_pos(
+                self.pos_from_row_space(rect.min),
+                self.pos_from_row_space(rect.max),
+            )
+        } else {
+            rect
+        }
+    }
+
+    /// The inverse of [`Self::pos_from_row_space`].
+    #[inline]
+    fn row_space_from_pos(&self, pos: Pos2) -> Pos2 {
+        if self.is_vertical() {
+            pos2(pos.y, self.rect.max.x - pos.x)
+        } else {
+            pos
+        }
+    }
+
+    /// The row and glyph of each char of the text,
+    /// with `None` for the implicit newlines and the elided chars.
+    fn glyph_of_each_char(&self) -> Vec<Option<(&PlacedRow, &Glyph)>> {
+        let mut glyphs = self.rows.iter().flat_map(|placed_row| {
+            let glyphs = placed_row.glyphs.iter();
+            glyphs
+                .map(move |glyph| Some((placed_row, glyph)))
+                .chain(placed_row.ends_with_newline.then_some(None))
+        });
+
+        let Some((elided_chars, shows_overflow_character)) = self.elided_chars() else {
+            return glyphs.collect();
+        };
+
+        let num_chars = self.job.text.chars().count();
+
+        (0..num_chars)
+            .map(|char_index| {
+                if char_index == elided_chars.start && shows_overflow_character {
+                    glyphs.next(); // The overflow character does not belong to any char of the text
+                }
+                if elided_chars.contains(&char_index) {
+                    None
+                } else {
+                    glyphs.next().flatten()
+                }
+            })
+            .collect()
+    }
+
+    /// The char range of [`Self::elided`] in [`LayoutJob::text`],
+    /// and whether the [`TextWrapping::overflow_character`] is shown in its place.
+    fn elided_chars(&self) -> Option<(Range<usize>, bool)> {
+        let elided = self.elided.as_ref()?;
+        let text = &self.job.text;
+        let start = text[..elided.start].chars().count();
+        let end = start + text[elided.clone()].chars().count();
+
+        let shows_overflow_character = self.job.wrap.overflow_character.is_some_and(|chr| {
+            let mut chars = self.rows.iter().flat_map(|placed_row| {
+                let glyphs = placed_row.glyphs.iter().map(|glyph| Some(glyph.chr));
+                glyphs.chain(placed_row.ends_with_newline.then_some(None))
+            });
+            chars.nth(start).flatten() == Some(chr)
+        });
+
+        Some((start..end, shows_overflow_character))
+    }
+
     pub(crate) fn round_output_to_gui(&mut self) {
         for placed_row in &mut self.rows {
             // Optimization: only call `make_mut` if necessary (can cause a deep clone)
@@ -848,7 +1647,7 @@ impl Galley {
         let mut merged_galley = Self {
             job,
             rows: Vec::new(),
-            elided: false,
+            elided: None,
             rect: Rect::ZERO,
             mesh_bounds: Rect::NOTHING,
             num_vertices: 0,
@@ -857,6 +1656,7 @@ impl Galley {
             intrinsic_size: Vec2::ZERO,
         };
 
+        let mut byte_offset = 0;
         for (i, galley) in galleys.iter().enumerate() {
             let current_y_offset = merged_galley.rect.height();
             let is_last_galley = i + 1 == galleys.len();
@@ -883,9 +1683,13 @@ impl Galley {
 
             merged_galley.num_vertices += galley.num_vertices;
             merged_galley.num_indices += galley.num_indices;
-            // Note that if `galley.elided` is true this will be the last `Galley` in
+            // Note that if `galley.elided` is set this will be the last `Galley` in
             // the vector and the loop will end.
-            merged_galley.elided |= galley.elided;
+            if let Some(elided) = &galley.elided {
+                merged_galley.elided =
+                    Some(byte_offset + elided.start..merged_galley.job.text.len());
+            }
+            byte_offset += galley.job.text.len() + 1; // Skip the `\n` between the paragraphs
             merged_galley.intrinsic_size.x =
                 f32::max(merged_galley.intrinsic_size.x, galley.intrinsic_size.x);
             merged_galley.intrinsic_size.y += galley.intrinsic_size.y;
@@ -947,8 +1751,10 @@ impl Galley {
     }
 
     /// Returns a 0-width Rect.
+    ///
+    /// For vertical text ([`WritingMode::VerticalRightToLeft`]) the rect is 0-height instead.
     pub fn pos_from_cursor(&self, cursor: CCursor) -> Rect {
-        self.pos_from_layout_cursor(&self.layout_from_cursor(cursor))
+        self.rect_from_row_space(self.pos_from_layout_cursor(&self.layout_from_cursor(cursor)))
     }
 
     /// Cursor at the given position within the galley.
@@ -958,10 +1764,13 @@ impl Galley {
     /// and a cursor below the galley is considered
     /// same as a cursor at the end.
     /// This allows implementing text-selection by dragging above/below the galley.
+    /// For vertical text, the same goes for the right and left of the galley.
     pub fn cursor_from_pos(&self, pos: Vec2) -> CCursor {
         // Vertical margin around galley improves text selection UX
         const VMARGIN: f32 = 5.0;
 
+        let pos = self.row_space_from_pos(pos.to_pos2());
+
         if let Some(first_row) = self.rows.first()
             && pos.y < first_row.min_y() - VMARGIN
         {
@@ -1074,6 +1883,22 @@ impl Galley {
         }
     }
 
+    /// The index into the chars of [`LayoutJob::text`] that a cursor is at.
+    ///
+    /// Cursors count the chars that are shown. This is the same as [`CCursor::index`],
+    /// except when text was [`Self::elided`] at the start or in the middle:
+    /// then the shown overflow character stands for all of the elided text.
+    pub fn text_char_index(&self, cursor: CCursor) -> usize {
+        match self.elided_chars() {
+            Some((elided_chars, shows_overflow_character)) if elided_chars.start < cursor.index => {
+                let after_elided = cursor.index - elided_chars.start;
+                elided_chars.end + after_elided
+                    - usize::from(shows_overflow_character).min(after_elided)
+            }
+            _ => cursor.index,
+        }
+    }
+
     fn cursor_from_layout(&self, layout_cursor: LayoutCursor) -> CCursor {
         if layout_cursor.row >= self.rows.len() {
             return self.end();
@@ -1102,8 +1927,69 @@ impl Galley {
 
 /// ## Cursor positions
 impl Galley {
-    #[expect(clippy::unused_self)]
+    /// Move the cursor one character to the left.
+    ///
+    /// On rows with right-to-left text this moves visually left,
+    /// which may be forward in the text.
     pub fn cursor_left_one_character(&self, cursor: &CCursor) -> CCursor {
+        if let Some(cursor) = self.cursor_move_visually(cursor, false) {
+            return cursor;
+        }
+        self.cursor_previous_character(cursor)
+    }
+
+    /// Move the cursor one character to the right.
+    ///
+    /// On rows with right-to-left text this moves visually right,
+    /// which may be backwards in the text.
+    pub fn cursor_right_one_character(&self, cursor: &CCursor) -> CCursor {
+        if let Some(cursor) = self.cursor_move_visually(cursor, true) {
+            return cursor;
+        }
+        self.cursor_next_character(cursor)
+    }
+
+    /// Move the cursor to the next cursor position to the left or right on the same row.
+    ///
+    /// Returns `None` if the row has no right-to-left text,
+    /// in which case visual and logical order are the same.
+    fn cursor_move_visually(&self, cursor: &CCursor, right: bool) -> Option<CCursor> {
+        let layout_cursor = self.layout_from_cursor(*cursor);
+        let row = self.rows.get(layout_cursor.row)?;
+        if !row.has_rtl() {
+            return None;
+        }
+
+        let x = row.x_offset(layout_cursor.column);
+        let closest_column = (0..=row.char_count_excluding_newline())
+            .map(|column| (column, row.x_offset(column) - x))
+            .filter(|(_, dx)| if right { 0.0 < *dx } else { *dx < 0.0 })
+            .min_by(|(_, a), (_, b)| a.abs().total_cmp(&b.abs()))
+            .map(|(column, _)| column);
+
+        if let Some(column) = closest_column {
+            Some(self.cursor_from_layout(LayoutCursor {
+                row: layout_cursor.row,
+                column,
+            }))
+        } else {
+            // We're at the edge of the row, so continue onto the neighboring row:
+            let edge_glyph = if right {
+                row.glyphs.iter().max_by(|a, b| a.pos.x.total_cmp(&b.pos.x))
+            } else {
+                row.glyphs.iter().min_by(|a, b| a.pos.x.total_cmp(&b.pos.x))
+            }?;
+            Some(if edge_glyph.is_rtl() == right {
+                self.cursor_previous_character(cursor)
+            } else {
+                self.cursor_next_character(cursor)
+            })
+        }
+    }
+
+    /// Move the cursor one character backwards in the text.
+    #[expect(clippy::unused_self)]
+    pub fn cursor_previous_character(&self, cursor: &CCursor) -> CCursor {
         if cursor.index == 0 {
             Default::default()
         } else {
@@ -1114,7 +2000,8 @@ impl Galley {
         }
     }
 
-    pub fn cursor_right_one_character(&self, cursor: &CCursor) -> CCursor {
+    /// Move the cursor one character forward in the text.
+    pub fn cursor_next_character(&self, cursor: &CCursor) -> CCursor {
         CCursor {
             index: (cursor.index + 1).min(self.end().index),
             prefer_next_row: true, // default to this when navigating. It is more often useful to put cursor at the beginning of a row than at the end.
@@ -1,5 +1,5 @@
 use ecolor::Color32;
-use emath::{Rect, remap_clamp};
+use emath::{NumExt as _, Rect, remap_clamp};
 
 use crate::{ColorImage, ImageDelta, TextOptions};
 
@@ -76,6 +76,9 @@ pub struct TextureAtlas {
 
     /// Controls how to convert glyph coverage to alpha.
     options: TextOptions,
+
+    /// Does this atlas store signed distance fields instead of coverage?
+    distance_field: bool,
 }
 
 impl TextureAtlas {
@@ -89,6 +92,7 @@ impl TextureAtlas {
             overflowed: false,
             discs: vec![], // will be filled in below
             options,
+            distance_field: false,
         };
 
         // Make the top left pixel fully white for `WHITE_UV`, i.e. painting something with solid color:
@@ -138,6 +142,44 @@ impl TextureAtlas {
         atlas
     }
 
+    /// A fixed-size atlas for the signed distance fields of glyphs,
+    /// used for [`crate::text::GlyphRendering::DistanceField`].
+    ///
+    /// The top left pixel is fully inside, so [`crate::WHITE_UV`] works as usual.
+    /// There are no prepared discs.
+    pub fn new_distance_field(options: TextOptions) -> Self {
+        let size = Self::DISTANCE_FIELD_SIZE;
+        let mut atlas = Self {
+            image: ColorImage::filled([size, size], Color32::TRANSPARENT),
+            dirty: Rectu::EVERYTHING,
+            cursor: (0, 0),
+            row_height: 0,
+            overflowed: false,
+            discs: vec![],
+            options,
+            distance_field: true,
+        };
+        let (pos, image) = atlas.allocate((1, 1));
+        image[pos] = Color32::WHITE;
+        atlas
+    }
+
+    /// The width and height of [`Self::new_distance_field`] atlases.
+    ///
+    /// These never grow, so that the uv coordinates of distance field text can be normalized with this.
+    pub const DISTANCE_FIELD_SIZE: usize = 1024;
+
+    /// How far from the edges of a glyph the distance field reaches, in distance field texels.
+    ///
+    /// A texel value of 0.5 is on the edge, 1.0 is this far inside and 0.0 this far outside.
+    pub const DISTANCE_FIELD_SPREAD: f32 = 4.0;
+
+    /// Does this atlas store signed distance fields (see [`Self::new_distance_field`])?
+    #[inline]
+    pub fn is_distance_field(&self) -> bool {
+        self.distance_field
+    }
+
     pub fn options(&self) -> &TextOptions {
         &self.options
     }
@@ -199,7 +241,10 @@ impl TextureAtlas {
 
     /// Call to get the change to the image since last call.
     pub fn take_delta(&mut self) -> Option<ImageDelta> {
-        let texture_options = Self::texture_options();
+        let texture_options = crate::textures::TextureOptions {
+            distance_field: self.distance_field,
+            ..Self::texture_options()
+        };
 
         let dirty = std::mem::replace(&mut self.dirty, Rectu::NOTHING);
         if dirty == Rectu::NOTHING {
@@ -246,7 +291,7 @@ impl TextureAtlas {
 
             self.cursor = (0, self.image.height() / 3); // Restart a bit down - the top of the atlas has too many important things in it
             self.overflowed = true; // this will signal the user that we need to recreate the texture atlas next frame.
-        } else if resize_to_min_height(&mut self.image, required_height) {
+        } else if !self.distance_field && resize_to_min_height(&mut self.image, required_height) {
             self.dirty = Rectu::EVERYTHING;
         }
 
@@ -260,6 +305,165 @@ impl TextureAtlas {
 
         (pos, &mut self.image)
     }
+
+    /// Store a "halo" around the glyph at `min..max` in this atlas, for glyph outlines and shadows.
+    ///
+    /// The halo is the glyph grown by `radius` texels, with an edge that fades out over `blur` texels.
+    /// The blur is ignored in distance field atlases, where all edges are sharp.
+    ///
+    /// Returns where the halo ended up, and how many texels it reaches beyond the glyph on each side.
+    pub fn allocate_halo(
+        &mut self,
+        min: [u16; 2],
+        max: [u16; 2],
+        radius: f32,
+        blur: f32,
+    ) -> ((usize, usize), (usize, usize), usize) {
+        let spread = Self::DISTANCE_FIELD_SPREAD;
+        let (min_x, min_y) = (min[0] as usize, min[1] as usize);
+        let (width, height) = (max[0] as usize - min_x, max[1] as usize - min_y);
+
+        // The signed distance to the edge of the glyph, positive inside:
+        let signed_distance = |value: f32| {
+            if self.distance_field {
+                (value - 0.5) * 2.0 * spread
+            } else {
+                value - 0.5 // Coverage is a good enough estimate on a per-texel basis
+            }
+        };
+        let values: Vec<f32> = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| self.image[(min_x + x, min_y + y)].a() as f32 / 255.0)
+            .collect();
+
+        // Measure from all covered texels, but only close to the edge in a distance field,
+        // since that is where it is accurate:
+        let sources: Vec<(f32, f32, f32)> = values
+            .iter()
+            .enumerate()
+            .filter(|&(_, &value)| {
+                0.0 < value && (!self.distance_field || signed_distance(value).abs() <= 1.0)
+            })
+            .map(|(i, &value)| {
+                let (x, y) = (i % width, i / width);
+                (x as f32, y as f32, signed_distance(value))
+            })
+            .collect();
+
+        let blur = blur.at_least(1.0);
+        let pad = if self.distance_field {
+            radius + spread
+        } else {
+            radius + 0.5 * blur + 1.0
+        }
+        .ceil() as usize;
+        let (halo_width, halo_height) = (width + 2 * pad, height + 2 * pad);
+
+        let distance_to_source = |x: usize, y: usize, source: usize| {
+            let (source_x, source_y, source_distance) = sources[source];
+            let (glyph_x, glyph_y) = (x as f32 - pad as f32, y as f32 - pad as f32);
+            emath::vec2(glyph_x - source_x, glyph_y - source_y).length() - source_distance
+        };
+        let mut nearest_sources = vec![None; halo_width * halo_height];
+        for (source, &(x, y, _)) in sources.iter().enumerate() {
+            nearest_sources[(y as usize + pad) * halo_width + x as usize + pad] = Some(source);
+        }
+        sweep_nearest_sources(
+            &mut nearest_sources,
+            halo_width,
+            halo_height,
+            distance_to_source,
+        );
+
+        let mut halo = Vec::with_capacity(halo_width * halo_height);
+        for y in 0..halo_height {
+            for x in 0..halo_width {
+                let inside = (pad..pad + width).contains(&x) && (pad..pad + height).contains(&y);
+                let mut distance = f32::INFINITY;
+                if inside {
+                    let value = values[(y - pad) * width + x - pad];
+                    if 0.0 < value {
+                        distance = -signed_distance(value);
+                    }
+                }
+                if let Some(source) = nearest_sources[y * halo_width + x] {
+                    distance = distance.min(distance_to_source(x, y, source));
+                }
+
+                halo.push(if self.distance_field {
+                    let value = (0.5 + 0.5 * (radius - distance) / spread).clamp(0.0, 1.0);
+                    Color32::from_white_alpha((value * 255.0).round() as u8)
+                } else {
+                    let coverage = ((radius - distance) / blur + 0.5).clamp(0.0, 1.0);
+                    self.options
+                        .alpha_from_coverage
+                        .color_from_coverage(coverage)
+                });
+            }
+        }
+
+        let (pos, image) = self.allocate((halo_width, halo_height));
+        for y in 0..halo_height {
+            for x in 0..halo_width {
+                image[(pos.0 + x, pos.1 + y)] = halo[y * halo_width + x];
+            }
+        }
+        (pos, (halo_width, halo_height), pad)
+    }
+}
+
+/// Give each cell of a `width` x `height` grid the nearest of the sources of its neighbors,
+/// so that every cell ends up with (approximately) the nearest source of the whole grid.
+///
+/// This is the two-pass sweep of 8SSEDT (8-point sequential signed Euclidean distance transform),
+/// which is linear in the number of cells.
+fn sweep_nearest_sources(
+    nearest_sources: &mut [Option<usize>],
+    width: usize,
+    height: usize,
+    distance_to_source: impl Fn(usize, usize, usize) -> f32,
+) {
+    let relax =
+        |nearest_sources: &mut [Option<usize>], x: usize, y: usize, [dx, dy]: [isize; 2]| {
+            let (Some(neighbor_x), Some(neighbor_y)) =
+                (x.checked_add_signed(dx), y.checked_add_signed(dy))
+            else {
+                return;
+            };
+            if width <= neighbor_x || height <= neighbor_y {
+                return;
+            }
+            let Some(candidate) = nearest_sources[neighbor_y * width + neighbor_x] else {
+                return;
+            };
+            let nearest = &mut nearest_sources[y * width + x];
+            if nearest.is_none_or(|nearest| {
+                distance_to_source(x, y, candidate) < distance_to_source(x, y, nearest)
+            }) {
+                *nearest = Some(candidate);
+            }
+        };
+
+    for y in 0..height {
+        for x in 0..width {
+            for neighbor in [[-1, 0], [-1, -1], [0, -1], [1, -1]] {
+                relax(nearest_sources, x, y, neighbor);
+            }
+        }
+        for x in (0..width).rev() {
+            relax(nearest_sources, x, y, [1, 0]);
+        }
+    }
+    for y in (0..height).rev() {
+        for x in (0..width).rev() {
+            for neighbor in [[1, 0], [1, 1], [0, 1], [-1, 1]] {
+                relax(nearest_sources, x, y, neighbor);
+            }
+        }
+        for x in 0..width {
+            relax(nearest_sources, x, y, [-1, 0]);
+        }
+    }
 }
 
 fn resize_to_min_height(image: &mut ColorImage, required_height: usize) -> bool {
@@ -276,3 +480,37 @@ fn resize_to_min_height(image: &mut ColorImage, required_height: usize) -> bool
         false
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sweep_nearest_sources() {
+        let (width, height) = (31, 23);
+        let sources: Vec<(usize, usize)> = (0..9)
+            .map(|i| ((i * 11) % width, (i * 7) % height))
+            .collect();
+        let distance_to_source = |x: usize, y: usize, source: usize| {
+            let (source_x, source_y) = sources[source];
+            emath::vec2(x as f32 - source_x as f32, y as f32 - source_y as f32).length()
+        };
+
+        let mut nearest_sources = vec![None; width * height];
+        for (source, &(x, y)) in sources.iter().enumerate() {
+            nearest_sources[y * width + x] = Some(source);
+        }
+        sweep_nearest_sources(&mut nearest_sources, width, height, distance_to_source);
+
+        // Same as a brute-force search:
+        for y in 0..height {
+            for x in 0..width {
+                let nearest = (0..sources.len())
+                    .map(|source| distance_to_source(x, y, source))
+                    .fold(f32::INFINITY, f32::min);
+                let source = nearest_sources[y * width + x].unwrap();
+                assert_eq!(distance_to_source(x, y, source), nearest, "at {x}, {y}");
+            }
+        }
+    }
+}
@@ -169,6 +169,22 @@ pub struct TextureOptions {
     ///
     /// - This may not be available on all backends (currently only `egui_glow`).
     pub mipmap_mode: Option<TextureFilter>,
+
+    /// Is this texture a signed distance field?
+    ///
+    /// If so, the alpha channel stores the distance to the edge of a shape,
+    /// with `0.5` on the edge and larger values inside.
+    /// The backend should turn that into a sharp, anti-aliased edge,
+    /// e.g. using the screen-space derivative of the distance.
+    ///
+    /// This is used for the font atlas of [`crate::text::GlyphRendering::DistanceField`],
+    /// which stays sharp at any zoom level.
+    ///
+    /// # Notes
+    ///
+    /// - This may not be available on all backends (currently only `egui_glow` and `egui-wgpu`).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub distance_field: bool,
 }
 
 impl TextureOptions {
@@ -178,6 +194,7 @@ impl TextureOptions {
         minification: TextureFilter::Linear,
         wrap_mode: TextureWrapMode::ClampToEdge,
         mipmap_mode: None,
+        distance_field: false,
     };
 
     /// Nearest magnification and minification.
@@ -186,6 +203,7 @@ impl TextureOptions {
         minification: TextureFilter::Nearest,
         wrap_mode: TextureWrapMode::ClampToEdge,
         mipmap_mode: None,
+        distance_field: false,
     };
 
     /// Linear magnification and minification, but with the texture repeated.
@@ -194,6 +212,7 @@ impl TextureOptions {
         minification: TextureFilter::Linear,
         wrap_mode: TextureWrapMode::Repeat,
         mipmap_mode: None,
+        distance_field: false,
     };
 
     /// Linear magnification and minification, but with the texture mirrored and repeated.
@@ -202,6 +221,7 @@ impl TextureOptions {
         minification: TextureFilter::Linear,
         wrap_mode: TextureWrapMode::MirroredRepeat,
         mipmap_mode: None,
+        distance_field: false,
     };
 
     /// Nearest magnification and minification, but with the texture repeated.
@@ -210,6 +230,7 @@ impl TextureOptions {
         minification: TextureFilter::Nearest,
         wrap_mode: TextureWrapMode::Repeat,
         mipmap_mode: None,
+        distance_field: false,
     };
 
     /// Nearest magnification and minification, but with the texture mirrored and repeated.
@@ -218,6 +239,7 @@ impl TextureOptions {
         minification: TextureFilter::Nearest,
         wrap_mode: TextureWrapMode::MirroredRepeat,
         mipmap_mode: None,
+        distance_field: false,
     };
 
     pub const fn with_mipmap_mode(self, mipmap_mode: Option<TextureFilter>) -> Self {